@@ -1,20 +1,28 @@
 use crate::ConfMetadata;
-use crate::de::{DeserializeOrDefault, DiagCtxt, FromDefault, create_value_list_msg, find_closest_match};
+use crate::de::{
+    Deserialize, DeserializeOrDefault, DiagCtxt, FromDefault, TomlValue, create_value_list_msg, find_closest_match,
+};
+use crate::print::{ConfFormat, ResolvedField, ResolvedOverride, ValueSource, format_resolved, toml_to_json};
 use crate::types::{
-    DisallowedPath, DisallowedPathWithoutReplacement, InherentImplLintScope, MacroMatcher, MatchLintBehaviour,
-    PubUnderscoreFieldsBehaviour, Rename, SourceItemOrdering, SourceItemOrderingModuleItemGroupings,
-    SourceItemOrderingTraitAssocItemKinds, SourceItemOrderingWithinModuleItemGroupings, TraitImplItemOrder,
+    AllowedDependencies, DisallowedImpl, DisallowedPath, DisallowedPathWithoutReplacement, InherentImplLintScope,
+    MacroMatcher, MatchLintBehaviour, PubUnderscoreFieldsBehaviour, Rename, SourceItemOrdering,
+    SourceItemOrderingModuleItemGroupings, SourceItemOrderingTraitAssocItemKinds,
+    SourceItemOrderingWithinModuleItemGroupings, TraitImplItemOrder,
 };
+use rustc_arena::TypedArena;
 use rustc_attr_parsing::parse_version;
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::Applicability;
 use rustc_hir::attrs::RustcVersion;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
-use rustc_span::{Pos as _, SourceFile, Symbol};
-use std::path::PathBuf;
+use rustc_span::def_id::LocalDefId;
+use rustc_span::{FileName, Pos as _, SourceFile, Symbol};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, OnceLock};
-use std::{env, fs, io};
-use toml::de::DeTable;
+use std::{env, fs, io, mem};
+use toml::de::{DeArray, DeTable, DeValue};
 
 #[rustfmt::skip]
 static DEFAULT_DOC_VALID_IDENTS: &[&str] = &[
@@ -54,6 +62,34 @@ static DEFAULT_ALLOWED_TRAITS_WITH_RENAMED_PARAMS: &[&str] =
 
 static DEFAULT_ALLOWED_SCRIPTS: &[&str] = &["Latin"];
 static DEFAULT_IGNORE_INTERIOR_MUTABILITY: &[&str] = &["bytes::Bytes"];
+static DEFAULT_BLOCKING_APIS: &[&str] = &[
+    "std::fs::copy",
+    "std::fs::create_dir",
+    "std::fs::create_dir_all",
+    "std::fs::metadata",
+    "std::fs::read",
+    "std::fs::read_dir",
+    "std::fs::read_to_string",
+    "std::fs::remove_dir_all",
+    "std::fs::remove_file",
+    "std::fs::rename",
+    "std::fs::write",
+    "std::fs::File::create",
+    "std::fs::File::open",
+    "std::io::Stdin::read_line",
+    "std::net::TcpListener::accept",
+    "std::net::TcpStream::connect",
+    "std::process::Child::wait",
+    "std::process::Command::output",
+    "std::process::Command::status",
+    "std::sync::Condvar::wait",
+    "std::sync::Mutex::lock",
+    "std::sync::RwLock::read",
+    "std::sync::RwLock::write",
+    "std::sync::mpsc::Receiver::recv",
+    "std::thread::JoinHandle::join",
+    "std::thread::sleep",
+];
 
 macro_rules! first_expr {
     ($e:expr $(,$_e:expr)*) => {
@@ -114,12 +150,18 @@ macro_rules! define_Conf {
         pub struct Conf {
             // TODO: emit documentation
             $($(pub $name: $ty,)?)*
+            /// Configurations which replace some values for specific modules and files.
+            overrides: Vec<ConfOverride>,
+            /// The directory of the config file, if one was found.
+            dir: Option<PathBuf>,
         }
 
         impl Default for Conf {
             fn default() -> Self {
                 Self {
                     $($($name: <$ty as FromDefault<_>>::from_default(first_expr!($($default,)? ())),)?)*
+                    overrides: Vec::new(),
+                    dir: None,
                 }
             }
         }
@@ -141,7 +183,9 @@ macro_rules! define_Conf {
                 )*]
             }
 
-            fn deserialize(dcx: &DiagCtxt<'_>, table: &toml::de::DeTable<'_>) -> Self {
+            /// Deserializes the configuration from a table. Any value not set in the table will be
+            /// taken from `base` if given, or the default value otherwise.
+            fn deserialize(dcx: &DiagCtxt<'_>, table: &toml::de::DeTable<'_>, base: Option<&Self>) -> Self {
                 $($(let mut $name: Option<$ty> = None;)?)*
 
                 for (key, value) in table.iter() {
@@ -196,11 +240,16 @@ macro_rules! define_Conf {
                     }
                 }
 
-                Self {$($(
-                    $name: $name.unwrap_or_else(
-                        || <$ty as FromDefault<_>>::from_default(first_expr!($($default,)? ()))
-                    ),
-                )?)*}
+                Self {
+                    $($(
+                        $name: $name.unwrap_or_else(|| match base {
+                            Some(base) => base.$name.clone(),
+                            None => <$ty as FromDefault<_>>::from_default(first_expr!($($default,)? ())),
+                        }),
+                    )?)*
+                    overrides: Vec::new(),
+                    dir: None,
+                }
             }
         }
 
@@ -301,6 +350,28 @@ define_Conf! {
     /// Whether `useless_vec` should ignore test functions or `#[cfg(test)]`
     #[lints(useless_vec)]
     allow_useless_vec_in_tests("allow-useless-vec-in-tests"): bool = false,
+    /// The layers of the crate's architecture and the items from other layers each of them may use.
+    /// Code inside a layer may use anything from within the same layer or from outside of all
+    /// layers, but only the listed items from other layers.
+    ///
+    /// **Fields:**
+    /// - `path` (required): the path to the module or crate making up the layer
+    /// - `allowed` (optional): the paths to the items or modules from other layers that this layer may use
+    /// - `reason` (optional): explanation of why this layer may not use other layers
+    /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
+    ///   if the path doesn't exist, instead of emitting an error
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// allowed-dependencies = [
+    ///     { path = "app::domain", reason = "the domain must not depend on other layers" },
+    ///     { path = "app::api", allowed = ["app::domain::ports"] },
+    ///     { path = "app::infra", allowed = ["app::domain"] },
+    /// ]
+    /// ```
+    #[lints(disallowed_layer_dependencies)]
+    allowed_dependencies("allowed-dependencies"): Vec<AllowedDependencies>,
     /// Additional dotfiles (files or directories starting with a dot) to allow
     #[lints(path_ends_with_ext)]
     allowed_dotfiles("allowed-dotfiles"): Vec<String>,
@@ -405,6 +476,7 @@ define_Conf! {
         rc_buffer,
         rc_mutex,
         redundant_allocation,
+        redundant_clone,
         ref_option,
         single_call_fn,
         trivially_copy_pass_by_ref,
@@ -421,6 +493,22 @@ define_Conf! {
     await_holding_invalid_types("await-holding-invalid-types"): Vec<DisallowedPathWithoutReplacement>,
     #[rename = disallowed_names]
     blacklisted_names("blacklisted-names"),
+    /// Functions which block the current thread. The value `".."` can be used as part of the list to
+    /// indicate that the configured values should be appended to the default list of blocking
+    /// functions from the standard library. By default, any configuration will replace the default
+    /// value.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// blocking-apis = [
+    ///     "..",
+    ///     "postgres::Client::query",
+    ///     { path = "reqwest::blocking::get", reason = "use `reqwest::get` instead" },
+    /// ]
+    /// ```
+    #[lints(blocking_in_async)]
+    blocking_apis("blocking-apis"): Vec<DisallowedPathWithoutReplacement> = DEFAULT_BLOCKING_APIS,
     /// For internal testing only, ignores the current `publish` settings in the Cargo manifest.
     #[lints(cargo_common_metadata)]
     cargo_ignore_publish("cargo-ignore-publish"): bool = false,
@@ -490,8 +578,22 @@ define_Conf! {
     /// - `replacement` (optional): suggested alternative method
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `contexts` (optional): only disallow the field inside of these contexts: `async`, `const`,
+    ///   `unsafe`, `test` or `module:<path>`
     #[lints(disallowed_fields)]
     disallowed_fields("disallowed-fields"): Vec<DisallowedPath>,
+    /// The list of disallowed trait implementations, written as the fully qualified paths of the
+    /// trait and the implementing type.
+    ///
+    /// **Fields:**
+    /// - `path` (required): the fully qualified path to the trait
+    /// - `for` (required): the fully qualified path to the type which may not implement the trait
+    /// - `reason` (optional): explanation why this implementation is disallowed
+    /// - `replacement` (optional): suggested alternative trait
+    /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
+    ///   if the paths don't exist, instead of emitting an error
+    #[lints(disallowed_impls)]
+    disallowed_impls("disallowed-impls"): Vec<DisallowedImpl>,
     /// The list of disallowed macros, written as fully qualified paths.
     ///
     /// **Fields:**
@@ -500,6 +602,8 @@ define_Conf! {
     /// - `replacement` (optional): suggested alternative macro
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `contexts` (optional): only disallow the macro inside of these contexts: `async`, `const`,
+    ///   `unsafe`, `test` or `module:<path>`
     #[lints(disallowed_macros)]
     disallowed_macros("disallowed-macros"): Vec<DisallowedPath>,
     /// The list of disallowed methods, written as fully qualified paths.
@@ -510,6 +614,8 @@ define_Conf! {
     /// - `replacement` (optional): suggested alternative method
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `contexts` (optional): only disallow the method inside of these contexts: `async`, `const`,
+    ///   `unsafe`, `test` or `module:<path>`
     #[lints(disallowed_methods)]
     disallowed_methods("disallowed-methods"): Vec<DisallowedPath>,
     /// The list of disallowed names to lint about. NB: `bar` is not here since it has legitimate uses. The value
@@ -517,6 +623,18 @@ define_Conf! {
     /// default configuration of Clippy. By default, any configuration will replace the default value.
     #[lints(disallowed_names)]
     disallowed_names("disallowed-names"): Vec<String> = DEFAULT_DISALLOWED_NAMES,
+    /// The list of disallowed traits, written as fully qualified paths.
+    ///
+    /// **Fields:**
+    /// - `path` (required): the fully qualified path to the trait that should be disallowed
+    /// - `reason` (optional): explanation why this trait is disallowed
+    /// - `replacement` (optional): suggested alternative trait
+    /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
+    ///   if the path doesn't exist, instead of emitting an error
+    /// - `contexts` (optional): only disallow the trait inside of these contexts: `async`, `const`,
+    ///   `unsafe`, `test` or `module:<path>`
+    #[lints(disallowed_traits)]
+    disallowed_traits("disallowed-traits"): Vec<DisallowedPath>,
     /// The list of disallowed types, written as fully qualified paths.
     ///
     /// **Fields:**
@@ -525,6 +643,8 @@ define_Conf! {
     /// - `replacement` (optional): suggested alternative type
     /// - `allow-invalid` (optional, `false` by default): when set to `true`, it will ignore this entry
     ///   if the path doesn't exist, instead of emitting an error
+    /// - `contexts` (optional): only disallow the type inside of these contexts: `async`, `const`,
+    ///   `unsafe`, `test` or `module:<path>`
     #[lints(disallowed_types)]
     disallowed_types("disallowed-types"): Vec<DisallowedPath>,
     /// The list of words this lint should not consider as identifiers needing ticks. The value
@@ -658,6 +778,7 @@ define_Conf! {
         from_over_into,
         if_then_some_else_none,
         implicit_saturating_sub,
+        incompatible_dependency_msrv,
         index_refutable_slice,
         inefficient_to_string,
         io_other_error,
@@ -709,6 +830,7 @@ define_Conf! {
         question_mark,
         redundant_field_names,
         redundant_static_lifetimes,
+        regex_creation_in_functions,
         repeat_vec_with_capacity,
         same_item_push,
         seek_from_current,
@@ -726,6 +848,19 @@ define_Conf! {
         zero_ptr,
     )]
     msrv("msrv"): Option<RustcVersion>,
+    /// Additional functions to check with `panic_in_no_panic_fn`, as if they were marked with
+    /// `#[clippy::no_panic]`. Functions from other crates listed here are trusted to never panic.
+    ///
+    /// #### Example
+    ///
+    /// ```toml
+    /// no-panic-functions = [
+    ///     "crate::irq::handle",
+    ///     { path = "arrayvec::ArrayVec::push", reason = "capacity is checked by the caller" },
+    /// ]
+    /// ```
+    #[lints(panic_in_no_panic_fn)]
+    no_panic_functions("no-panic-functions"): Vec<DisallowedPathWithoutReplacement>,
     /// The minimum size (in bytes) to consider a type for passing by reference instead of by value.
     #[lints(large_types_passed_by_value)]
     pass_by_value_size_limit("pass-by-value-size-limit"): u64 = 256,
@@ -860,7 +995,7 @@ pub fn sanitize_explanation(raw_docs: &str) -> String {
 /// # Errors
 ///
 /// Returns any unexpected filesystem error encountered when searching for the config file
-fn load_conf_file(sess: &Session) -> Option<Arc<SourceFile>> {
+fn load_conf_file(sess: &Session) -> Option<(PathBuf, Arc<SourceFile>)> {
     /// Possible filename to search for.
     const CONFIG_FILE_NAMES: [&str; 2] = [".clippy.toml", "clippy.toml"];
 
@@ -917,8 +1052,8 @@ fn load_conf_file(sess: &Session) -> Option<Arc<SourceFile>> {
         }
 
         // Don't mention config files in parent directories.
-        if let Some((_, src)) = loaded_config {
-            return Some(src);
+        if loaded_config.is_some() {
+            return loaded_config;
         }
 
         // If the current directory has no parent, we're done searching.
@@ -928,29 +1063,379 @@ fn load_conf_file(sess: &Session) -> Option<Arc<SourceFile>> {
     }
 }
 
+/// Parses a config file which has been loaded into the source map, resolving any files named by
+/// its `extends` key. All spans in the returned table are relative to the start of the source map
+/// rather than the start of the file so values from different files can be merged together.
+///
+/// `chain` contains the canonical paths of all the files currently being loaded and is used to
+/// detect cycles.
+fn parse_conf_file<'a>(
+    sess: &Session,
+    files: &'a TypedArena<Arc<SourceFile>>,
+    path: &Path,
+    src: Arc<SourceFile>,
+    chain: &mut Vec<PathBuf>,
+) -> DeTable<'a> {
+    let src = &*files.alloc(src);
+    let offset = src.start_pos.to_usize();
+    let dcx = DiagCtxt::new(sess, offset);
+
+    let (toml, errs) = DeTable::parse_recoverable(src.src.as_ref().unwrap().as_str());
+    for e in errs {
+        match e.span() {
+            Some(sp) => dcx.span_err(sp, e.message().to_owned()),
+            None => {
+                dcx.inner
+                    .struct_err(format!("error parsing `clippy.toml`: {}", e.message()))
+                    .emit();
+            },
+        }
+    }
+    let mut table = rebase_table(toml.into_inner(), offset);
+    let Some(extends) = table.remove("extends") else {
+        return table;
+    };
+
+    // All spans have been rebased.
+    let dcx = DiagCtxt::new(sess, 0);
+    let extends = match extends.get_ref() {
+        DeValue::String(_) => vec![extends],
+        DeValue::Array(values) => values.to_vec(),
+        _ => {
+            dcx.span_err(extends.span(), "expected a path or an array of paths");
+            return table;
+        },
+    };
+
+    chain.push(path.to_owned());
+    let mut base = DeTable::new();
+    for value in extends {
+        let Some(extends_path) = value.get_ref().as_str() else {
+            dcx.span_err(value.span(), "expected a path");
+            continue;
+        };
+        let extends_path = path.parent().unwrap_or(path).join(extends_path);
+        let extends_path = match extends_path.canonicalize() {
+            Ok(x) => x,
+            Err(e) => {
+                dcx.span_err(value.span(), format!("error reading `{}`: {e}", extends_path.display()));
+                continue;
+            },
+        };
+        if chain.contains(&extends_path) {
+            dcx.span_err(value.span(), "config file extends itself");
+            continue;
+        }
+        match sess.source_map().load_file(&extends_path) {
+            Ok(src) => merge_conf_tables(&mut base, parse_conf_file(sess, files, &extends_path, src, chain)),
+            Err(e) => dcx.span_err(value.span(), format!("error reading `{}`: {e}", extends_path.display())),
+        }
+    }
+    chain.pop();
+
+    merge_conf_tables(&mut base, table);
+    base
+}
+
+fn rebase_table(table: DeTable<'_>, offset: usize) -> DeTable<'_> {
+    table
+        .into_iter()
+        .map(|(key, value)| {
+            let span = key.span();
+            (
+                toml::Spanned::new(span.start + offset..span.end + offset, key.into_inner()),
+                rebase_value(value, offset),
+            )
+        })
+        .collect()
+}
+
+fn rebase_value(value: TomlValue<'_>, offset: usize) -> TomlValue<'_> {
+    let span = value.span();
+    let value = match value.into_inner() {
+        DeValue::Array(values) => DeValue::Array(values.into_iter().map(|x| rebase_value(x, offset)).collect()),
+        DeValue::Table(table) => DeValue::Table(rebase_table(table, offset)),
+        value => value,
+    };
+    toml::Spanned::new(span.start + offset..span.end + offset, value)
+}
+
+/// Gets the index of the configuration field a key refers to with renames applied.
+fn conf_field_index(key: &str) -> Option<usize> {
+    ConfField::parse(key).map(|field| field.new_field() as usize)
+}
+
+/// Overlays the values from `table` on top of the values inherited from `base`. The value `".."`
+/// in an array will be replaced with the contents of the array it overrides.
+fn merge_conf_tables<'a>(base: &mut DeTable<'a>, table: DeTable<'a>) {
+    for (key, mut value) in table {
+        // Also remove inherited values which were set using a deprecated name.
+        let field = conf_field_index(key.get_ref());
+        let base_key = base
+            .keys()
+            .find(|base_key| {
+                base_key.get_ref() == key.get_ref() || field.is_some() && conf_field_index(base_key.get_ref()) == field
+            })
+            .map(|base_key| base_key.get_ref().clone());
+        let base_value = base_key.and_then(|base_key| base.remove(&*base_key));
+
+        if let Some(base_value) = base_value
+            && let DeValue::Array(base_values) = base_value.into_inner()
+            && let DeValue::Array(values) = value.get_mut()
+        {
+            let mut base_values = Some(base_values);
+            let mut merged = DeArray::new();
+            for x in mem::replace(values, DeArray::new()) {
+                if x.get_ref().as_str() == Some("..")
+                    && let Some(base_values) = base_values.take()
+                {
+                    base_values.into_iter().for_each(|x| merged.push(x));
+                } else {
+                    merged.push(x);
+                }
+            }
+            *values = merged;
+        }
+        base.insert(key, value);
+    }
+}
+
+/// Configuration fields which are looked up for each item and so can be set in an `[[override]]`
+/// table.
+const OVERRIDABLE_FIELDS: &[&str] = &[
+    "allow-expect-in-tests",
+    "allow-unwrap-in-tests",
+    "cognitive-complexity-threshold",
+    "too-many-arguments-threshold",
+    "too-many-lines-threshold",
+];
+
+/// A configuration which applies to items in specific modules or files.
+struct ConfOverride {
+    /// The module paths, without a leading `crate`, whose items and submodules are affected.
+    modules: Vec<Vec<Symbol>>,
+    /// Glob patterns matching the files, or directories containing the files, whose items are
+    /// affected. These are relative to `root`.
+    paths: Vec<String>,
+    /// The directory containing the config file the override was declared in.
+    root: PathBuf,
+    conf: Conf,
+}
+
+impl ConfOverride {
+    fn deserialize(sess: &Session, dcx: &DiagCtxt<'_>, value: &TomlValue<'_>, base: &Conf) -> Option<Self> {
+        const MATCHER_NAMES: &[&str] = &["modules", "paths"];
+
+        let Some(table) = value.get_ref().as_table() else {
+            dcx.span_err(value.span(), "expected a table");
+            return None;
+        };
+
+        let mut modules: Vec<String> = Vec::new();
+        let mut paths: Vec<String> = Vec::new();
+        let mut fields = DeTable::new();
+        for (key, value) in table {
+            match &**key.get_ref() {
+                "modules" => modules = Deserialize::deserialize(dcx, value).unwrap_or_default(),
+                "paths" => paths = Deserialize::deserialize(dcx, value).unwrap_or_default(),
+                name if ConfField::parse(name).is_some_and(|f| OVERRIDABLE_FIELDS.contains(&f.new_field().name())) => {
+                    fields.insert(key.clone(), value.clone());
+                },
+                name => {
+                    let names: Vec<_> = MATCHER_NAMES.iter().chain(OVERRIDABLE_FIELDS).copied().collect();
+                    let sp = dcx.make_sp(key.span());
+                    let mut diag = if ConfField::parse(name).is_some() {
+                        dcx.inner
+                            .struct_span_err(sp, "this configuration can't be set in an `[[override]]` table")
+                    } else {
+                        let mut diag = dcx.inner.struct_span_err(sp, "unknown field name");
+                        if let Some(sugg) = find_closest_match(name, &names) {
+                            diag.span_suggestion(sp, "did you mean", sugg, Applicability::MaybeIncorrect);
+                        }
+                        diag
+                    };
+                    diag.note(create_value_list_msg(dcx, &names));
+                    diag.emit();
+                },
+            }
+        }
+        if modules.is_empty() && paths.is_empty() {
+            dcx.span_err(value.span(), "expected at least one of `modules` or `paths`");
+            return None;
+        }
+
+        let file = sess.source_map().lookup_source_file(dcx.make_sp(value.span()).lo());
+        let root = if let FileName::Real(name) = &file.name
+            && let Some(path) = name.local_path()
+            && let Some(root) = path.parent()
+        {
+            root.to_owned()
+        } else {
+            PathBuf::new()
+        };
+
+        Some(Self {
+            modules: modules
+                .iter()
+                .map(|path| {
+                    let path = path.strip_prefix("crate::").unwrap_or(path);
+                    path.split("::").filter(|&x| x != "crate").map(Symbol::intern).collect()
+                })
+                .collect(),
+            paths,
+            root,
+            conf: Conf::deserialize(dcx, &fields, Some(base)),
+        })
+    }
+
+    fn matches(&self, tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+        if !self.modules.is_empty() {
+            let module = if tcx.def_kind(def_id) == DefKind::Mod {
+                def_id
+            } else {
+                tcx.parent_module_from_def_id(def_id).to_local_def_id()
+            };
+            let module_path: Vec<Symbol> = tcx
+                .def_path(module.to_def_id())
+                .data
+                .iter()
+                .filter_map(|x| x.data.get_opt_name())
+                .collect();
+            if self.modules.iter().any(|path| module_path.starts_with(path)) {
+                return true;
+            }
+        }
+
+        if !self.paths.is_empty() {
+            let source_map = tcx.sess.source_map();
+            let file = source_map.lookup_source_file(tcx.def_span(def_id).source_callsite().lo());
+            if let FileName::Real(name) = &file.name
+                && let Some(path) = name.local_path()
+                && let Some(path) = source_map
+                    .working_dir()
+                    .local_path()
+                    .map_or_else(|| path.to_owned(), |dir| dir.join(path))
+                    .strip_prefix(&self.root)
+                    .ok()
+            {
+                let path: Vec<_> = path.components().filter_map(|x| x.as_os_str().to_str()).collect();
+                return self.paths.iter().any(|pattern| {
+                    let pattern: Vec<_> = pattern.split('/').filter(|x| !x.is_empty() && *x != ".").collect();
+                    // Patterns can also name a directory containing the file.
+                    (1..=path.len()).any(|len| glob_matches(&pattern, &path[..len]))
+                });
+            }
+        }
+
+        false
+    }
+}
+
+/// Checks whether the path components match a glob pattern. `**` matches any number of path
+/// components, `*` matches any part of a single component and `?` matches any single character.
+fn glob_matches(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", pattern)) => (0..=path.len()).any(|i| glob_matches(pattern, &path[i..])),
+        Some((pat, pattern)) => path
+            .split_first()
+            .is_some_and(|(name, path)| glob_component_matches(pat, name) && glob_matches(pattern, path)),
+    }
+}
+
+fn glob_component_matches(pattern: &str, name: &str) -> bool {
+    let mut pattern_chars = pattern.chars();
+    match pattern_chars.next() {
+        None => name.is_empty(),
+        Some('*') => name
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([name.len()])
+            .any(|i| glob_component_matches(pattern_chars.as_str(), &name[i..])),
+        Some(c) => {
+            let mut name_chars = name.chars();
+            name_chars.next().is_some_and(|x| {
+                (c == '?' || c == x) && glob_component_matches(pattern_chars.as_str(), name_chars.as_str())
+            })
+        },
+    }
+}
+
 impl Conf {
     pub fn load(sess: &Session) -> &'static Conf {
         static CONF: OnceLock<Conf> = OnceLock::new();
-        CONF.get_or_init(|| Conf::load_inner(sess))
+        CONF.get_or_init(|| Conf::load_inner(sess, |_, _| {}))
     }
 
-    fn load_inner(sess: &Session) -> Conf {
-        let mut conf = if let Some(src) = load_conf_file(sess) {
-            let dcx = DiagCtxt::new(sess, src.start_pos.to_usize());
-            let src = src.src.as_ref().unwrap();
+    /// Loads the configuration and formats every value in effect along with where it was set and
+    /// the lints it affects.
+    pub fn format_resolved(sess: &Session, format: ConfFormat) -> String {
+        let mut file = None;
+        let mut values = FxHashMap::default();
+        let mut overrides = Vec::new();
+        let conf =
+            Conf::load_inner(sess, |path, table| {
+                file = Some(path.to_owned());
+                for (key, value) in table {
+                    if key.get_ref() == "override" {
+                        overrides.extend(value.get_ref().as_array().into_iter().flatten().map(|value| {
+                            ResolvedOverride {
+                                value: toml_to_json(value),
+                                source: ValueSource::from_span(sess, value.span()),
+                            }
+                        }));
+                    } else if let Some(field) = conf_field_index(key.get_ref()) {
+                        let source = ValueSource::from_span(sess, key.span().start..value.span().end);
+                        values.insert(field, (toml_to_json(value), source));
+                    }
+                }
+            });
 
-            let (toml, errs) = DeTable::parse_recoverable(src.as_str());
-            for e in errs {
-                match e.span() {
-                    Some(sp) => dcx.span_err(sp, e.message().to_owned()),
-                    None => {
-                        dcx.inner
-                            .struct_err(format!("error parsing `clippy.toml`: {}", e.message()))
-                            .emit();
-                    },
+        // The MSRV can also be taken from `Cargo.toml`.
+        let msrv = ConfField::msrv as usize;
+        if !values.contains_key(&msrv)
+            && let Some(version) = conf.msrv
+            && let Some(dir) = env::var_os("CARGO_MANIFEST_DIR")
+        {
+            let source = ValueSource::from_file(&Path::new(&dir).join("Cargo.toml"));
+            values.insert(msrv, (version.to_string().into(), source));
+        }
+
+        let fields: Vec<_> = Conf::get_metadata()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, meta)| meta.renamed_to.is_none())
+            .map(|(i, meta)| match values.remove(&i) {
+                Some((value, source)) => ResolvedField::new(meta, value, source),
+                None => ResolvedField::from_default(meta),
+            })
+            .collect();
+        format_resolved(format, file.as_deref(), &fields, &overrides)
+    }
+
+    /// Loads the configuration. `visit_table` will be called with the config file's path and its
+    /// contents once all inherited values have been merged in.
+    fn load_inner(sess: &Session, visit_table: impl FnOnce(&Path, &DeTable<'_>)) -> Conf {
+        let mut conf = if let Some((path, src)) = load_conf_file(sess) {
+            let files = TypedArena::default();
+            let mut table = parse_conf_file(sess, &files, &path, src, &mut Vec::new());
+            visit_table(&path, &table);
+            // Spans in the table are relative to the start of the source map.
+            let dcx = DiagCtxt::new(sess, 0);
+            let overrides = table.remove("override");
+            let mut conf = Conf::deserialize(&dcx, &table, None);
+            conf.dir = path.parent().map(Path::to_path_buf);
+            if let Some(overrides) = overrides {
+                if let Some(values) = overrides.get_ref().as_array() {
+                    conf.overrides = values
+                        .iter()
+                        .filter_map(|value| ConfOverride::deserialize(sess, &dcx, value, &conf))
+                        .collect();
+                } else {
+                    dcx.span_err(overrides.span(), "expected an array of tables");
                 }
             }
-            Conf::deserialize(&dcx, toml.get_ref())
+            conf
         } else {
             Conf::default()
         };
@@ -972,6 +1457,23 @@ impl Conf {
 
         conf
     }
+
+    /// Gets the directory containing the config file, which is also where the `clippy-rules`
+    /// directory is looked for.
+    pub fn dir(&self) -> Option<&Path> {
+        self.dir.as_deref()
+    }
+
+    /// Gets the configuration which applies to the given item. This will be the configuration from
+    /// the last `[[override]]` table matching either the item's module or source file, or the
+    /// crate's configuration if no override matches.
+    pub fn for_item(&'static self, tcx: TyCtxt<'_>, def_id: LocalDefId) -> &'static Conf {
+        self.overrides
+            .iter()
+            .rev()
+            .find(|x| x.matches(tcx, def_id))
+            .map_or(self, |x| &x.conf)
+    }
 }
 
 #[cfg(test)]
@@ -981,6 +1483,26 @@ mod tests {
     use toml::de::DeTable;
     use walkdir::WalkDir;
 
+    #[test]
+    fn glob_matches() {
+        let matches = |pattern: &str, path: &str| {
+            super::glob_matches(
+                &pattern.split('/').collect::<Vec<_>>(),
+                &path.split('/').collect::<Vec<_>>(),
+            )
+        };
+
+        assert!(matches("src/lib.rs", "src/lib.rs"));
+        assert!(matches("src/*.rs", "src/lib.rs"));
+        assert!(matches("src/l?b.rs", "src/lib.rs"));
+        assert!(matches("src/**/*.rs", "src/lib.rs"));
+        assert!(matches("src/**/*.rs", "src/ffi/sys/mod.rs"));
+        assert!(matches("**/generated_*.rs", "src/ffi/generated_bindings.rs"));
+        assert!(!matches("src/*.rs", "src/ffi/mod.rs"));
+        assert!(!matches("src/l?b.rs", "src/lb.rs"));
+        assert!(!matches("*.rs", "src/lib.rs"));
+    }
+
     #[test]
     fn configs_are_tested() {
         let mut names: FxHashSet<_> = super::Conf::get_metadata()
@@ -537,7 +537,10 @@ where
 }
 
 macro_rules! deserialize_table {
-    ($dcx:ident, $table:ident, $($name:ident($name_str:literal): $ty:ty,)+) => {
+    (
+        $dcx:ident, $table:ident, $($name:ident($name_str:literal): $ty:ty,)+
+        $(; unsupported($($unsupported_str:literal),+) => $unsupported:expr $(,)?)?
+    ) => {
         $(let mut $name: Option<$ty> = None;)+
 
         for (key, value) in $table.iter() {
@@ -546,6 +549,7 @@ macro_rules! deserialize_table {
                     // Duplicate keys are handled by the toml parser
                     $name = <$ty as crate::de::Deserialize>::deserialize($dcx, value.into());
                 },)+
+                $($($unsupported_str)|+ => ($unsupported)($dcx.make_sp(key.span())),)?
                 _ => {
                     const NAMES: &[&str] = &[$($name_str),*];
                     let sp = $dcx.make_sp(key.span());
@@ -8,6 +8,7 @@
 )]
 #![expect(clippy::must_use_candidate)]
 
+extern crate rustc_arena;
 extern crate rustc_attr_parsing;
 extern crate rustc_data_structures;
 extern crate rustc_errors;
@@ -20,7 +21,9 @@ extern crate rustc_span;
 mod de;
 mod conf;
 mod metadata;
+mod print;
 pub mod types;
 
 pub use conf::{Conf, sanitize_explanation};
 pub use metadata::ConfMetadata;
+pub use print::ConfFormat;
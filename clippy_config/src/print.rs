@@ -0,0 +1,252 @@
+use crate::ConfMetadata;
+use crate::de::TomlValue;
+use rustc_session::Session;
+use rustc_span::BytePos;
+use serde_json::{Map, Value, json};
+use std::fmt::Write as _;
+use std::ops::Range;
+use std::path::Path;
+use toml::de::{DeTable, DeValue};
+
+/// The format used to print the resolved configuration.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ConfFormat {
+    Toml,
+    Json,
+}
+
+impl ConfFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "toml" => Some(Self::Toml),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// The location a configuration value was set at.
+pub(crate) struct ValueSource {
+    file: String,
+    /// The one-based line and column of the start and end of the value.
+    span: Option<[(usize, usize); 2]>,
+}
+
+impl ValueSource {
+    /// Creates a source from a span relative to the start of the source map.
+    pub(crate) fn from_span(sess: &Session, span: Range<usize>) -> Self {
+        let source_map = sess.source_map();
+        #[expect(clippy::cast_possible_truncation)]
+        let [lo, hi] = [span.start, span.end].map(|pos| source_map.lookup_char_pos(BytePos(pos as u32)));
+        Self {
+            file: lo.file.name.prefer_local_unconditionally().to_string(),
+            span: Some([(lo.line, lo.col.0 + 1), (hi.line, hi.col.0 + 1)]),
+        }
+    }
+
+    pub(crate) fn from_file(path: &Path) -> Self {
+        Self {
+            file: path.display().to_string(),
+            span: None,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self.span {
+            Some([(line, column), (end_line, end_column)]) => json!({
+                "file": self.file,
+                "line": line,
+                "column": column,
+                "end_line": end_line,
+                "end_column": end_column,
+            }),
+            None => json!({ "file": self.file }),
+        }
+    }
+}
+
+/// A configuration field along with the value in effect.
+pub(crate) struct ResolvedField {
+    pub meta: ConfMetadata,
+    /// The value in effect, or `None` if the field is unset and has no representable default.
+    pub value: Option<Value>,
+    /// Where the value was set, or `None` if the default value is used.
+    pub source: Option<ValueSource>,
+}
+
+impl ResolvedField {
+    /// Creates a field which is set in a config file. The value `".."` in a list will be replaced
+    /// by the default values.
+    pub(crate) fn new(meta: ConfMetadata, mut value: Value, source: ValueSource) -> Self {
+        if let Value::Array(values) = &mut value
+            && let Some(i) = values.iter().position(|x| x == "..")
+            && let Some(Value::Array(default)) = parse_default(&meta)
+        {
+            values.splice(i..=i, default);
+        }
+        Self {
+            meta,
+            value: Some(value),
+            source: Some(source),
+        }
+    }
+
+    /// Creates a field which is using its default value.
+    pub(crate) fn from_default(meta: ConfMetadata) -> Self {
+        let value = parse_default(&meta);
+        Self {
+            meta,
+            value,
+            source: None,
+        }
+    }
+}
+
+fn parse_default(meta: &ConfMetadata) -> Option<Value> {
+    // Most default values are displayed as valid toml.
+    DeTable::parse(&format!("value = {}", meta.default))
+        .ok()
+        .and_then(|table| table.get_ref().get("value").map(toml_to_json))
+}
+
+/// An `[[override]]` table.
+pub(crate) struct ResolvedOverride {
+    pub value: Value,
+    pub source: ValueSource,
+}
+
+pub(crate) fn toml_to_json(value: &TomlValue<'_>) -> Value {
+    match value.get_ref() {
+        DeValue::String(x) => Value::String(x.to_string()),
+        DeValue::Integer(x) => {
+            i64::from_str_radix(x.as_str(), x.radix()).map_or_else(|_| Value::String(x.to_string()), Value::from)
+        },
+        DeValue::Float(x) => x
+            .as_str()
+            .parse::<f64>()
+            .map_or_else(|_| Value::String(x.to_string()), Value::from),
+        DeValue::Boolean(x) => Value::Bool(*x),
+        DeValue::Datetime(x) => Value::String(x.to_string()),
+        DeValue::Array(values) => values.iter().map(toml_to_json).collect(),
+        DeValue::Table(table) => table
+            .iter()
+            .map(|(key, value)| (key.get_ref().to_string(), toml_to_json(value)))
+            .collect::<Map<_, _>>()
+            .into(),
+    }
+}
+
+/// Formats the resolved configuration.
+pub(crate) fn format_resolved(
+    format: ConfFormat,
+    file: Option<&Path>,
+    fields: &[ResolvedField],
+    overrides: &[ResolvedOverride],
+) -> String {
+    match format {
+        ConfFormat::Toml => format_toml(file, fields, overrides),
+        ConfFormat::Json => {
+            let fields: Vec<_> = fields
+                .iter()
+                .map(|field| {
+                    json!({
+                        "name": field.meta.name,
+                        "value": field.value,
+                        "default": field.meta.default,
+                        "source": field.source.as_ref().map(ValueSource::to_json),
+                        "lints": field.meta.lints,
+                        "doc": field
+                            .meta
+                            .doc
+                            .lines()
+                            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                            .collect::<Vec<_>>()
+                            .join("\n"),
+                    })
+                })
+                .collect();
+            let overrides: Vec<_> = overrides
+                .iter()
+                .map(|x| json!({ "value": x.value, "source": x.source.to_json() }))
+                .collect();
+            let output = json!({
+                "config_file": file.map(|file| file.display().to_string()),
+                "fields": fields,
+                "overrides": overrides,
+            });
+            serde_json::to_string_pretty(&output).unwrap()
+        },
+    }
+}
+
+fn format_toml(file: Option<&Path>, fields: &[ResolvedField], overrides: &[ResolvedOverride]) -> String {
+    let mut out = String::new();
+    match file {
+        Some(file) => writeln!(out, "# Configuration file: {}", file.display()).unwrap(),
+        None => out.push_str("# No configuration file found\n"),
+    }
+
+    for field in fields {
+        out.push('\n');
+        for line in field.meta.doc.lines().take_while(|line| !line.trim().is_empty()) {
+            writeln!(out, "#{line}").unwrap();
+        }
+        if !field.meta.lints.is_empty() {
+            writeln!(out, "# Affected lints: {}", field.meta.lints.join(", ")).unwrap();
+        }
+        match &field.source {
+            Some(source) => writeln!(out, "# Source: {}", format_toml_source(source)).unwrap(),
+            None => out.push_str("# Source: default\n"),
+        }
+        match &field.value {
+            Some(value) => writeln!(out, "{} = {}", field.meta.name, format_toml_value(value)).unwrap(),
+            None => writeln!(out, "# {} = {}", field.meta.name, field.meta.default).unwrap(),
+        }
+    }
+
+    for x in overrides {
+        writeln!(out, "\n# Source: {}\n[[override]]", format_toml_source(&x.source)).unwrap();
+        if let Value::Object(table) = &x.value {
+            for (key, value) in table {
+                writeln!(out, "{} = {}", format_toml_key(key), format_toml_value(value)).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+fn format_toml_source(source: &ValueSource) -> String {
+    match source.span {
+        Some([(line, column), _]) => format!("{}:{line}:{column}", source.file),
+        None => source.file.clone(),
+    }
+}
+
+fn format_toml_key(key: &str) -> String {
+    if !key.is_empty() && key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_') {
+        key.to_owned()
+    } else {
+        Value::from(key).to_string()
+    }
+}
+
+fn format_toml_value(value: &Value) -> String {
+    match value {
+        // Json's escape sequences are a subset of toml's.
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => value.to_string(),
+        Value::Array(values) => format!(
+            "[{}]",
+            values.iter().map(format_toml_value).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(table) if table.is_empty() => "{}".into(),
+        Value::Object(table) => format!(
+            "{{ {} }}",
+            table
+                .iter()
+                .map(|(key, value)| format!("{} = {}", format_toml_key(key), format_toml_value(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
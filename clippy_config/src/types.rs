@@ -1,18 +1,23 @@
 use crate::de::{
     Deserialize, DeserializeOrDefault, DiagCtxt, FromDefault, TomlValue, create_value_list_msg, find_closest_match,
 };
+use clippy_utils::is_in_test;
 use clippy_utils::paths::{PathNS, find_crates, lookup_path};
 use core::fmt::{self, Display};
 use itertools::Itertools as _;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::{Applicability, Diag};
-use rustc_hir::PrimTy;
 use rustc_hir::def::DefKind;
-use rustc_hir::def_id::DefIdMap;
+use rustc_hir::def_id::{DefId, DefIdMap, LOCAL_CRATE};
+use rustc_hir::{
+    BlockCheckMode, ClosureKind, CoroutineDesugaring, CoroutineKind, Expr, ExprKind, HirId, Node, PrimTy, UnsafeSource,
+};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::Session;
-use rustc_span::{Span, Spanned, Symbol};
+use rustc_span::{DUMMY_SP, Span, Spanned, Symbol};
 use std::collections::HashMap;
+use std::iter;
+use std::sync::OnceLock;
 
 macro_rules! concat_expr {
     ($($e:expr)*) => {
@@ -94,6 +99,7 @@ macro_rules! conf_enum {
         }
     };
 }
+#[derive(Clone)]
 pub struct Rename {
     pub path: String,
     pub rename: String,
@@ -124,6 +130,7 @@ impl Deserialize for Rename {
 
 pub type DisallowedPathWithoutReplacement = DisallowedPath<false>;
 
+#[derive(Clone)]
 pub struct DisallowedPath<const REPLACEMENT_ALLOWED: bool = true> {
     path: Spanned<String>,
     reason: Option<String>,
@@ -134,6 +141,8 @@ pub struct DisallowedPath<const REPLACEMENT_ALLOWED: bool = true> {
     /// This could be useful when conditional compilation is used, or when a clippy.toml file is
     /// shared among multiple projects.
     allow_invalid: bool,
+    /// The contexts the path is disallowed in, or everywhere if empty
+    contexts: Vec<DisallowedContext>,
 }
 
 impl<const REPLACEMENT_ALLOWED: bool> DisallowedPath<REPLACEMENT_ALLOWED> {
@@ -155,6 +164,98 @@ impl<const REPLACEMENT_ALLOWED: bool> DisallowedPath<REPLACEMENT_ALLOWED> {
             }
         }
     }
+
+    /// Checks whether the path is disallowed at `hir_id`, i.e. whether `hir_id` is inside one of
+    /// the configured contexts, if there are any.
+    pub fn applies_in(&self, tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
+        self.contexts.is_empty() || self.contexts.iter().any(|context| context.contains(tcx, hir_id))
+    }
+}
+
+impl<const REPLACEMENT_ALLOWED: bool> FromDefault<&'static str> for DisallowedPath<REPLACEMENT_ALLOWED> {
+    fn from_default(default: &'static str) -> Self {
+        Self {
+            path: Spanned {
+                node: default.into(),
+                span: DUMMY_SP,
+            },
+            reason: None,
+            replacement: None,
+            // The default paths aren't reachable in every crate, e.g. in `no_std` crates
+            allow_invalid: true,
+            contexts: Vec::new(),
+        }
+    }
+    fn display_default(default: &'static str) -> impl Display {
+        format!("{default:?}")
+    }
+}
+
+impl<const REPLACEMENT_ALLOWED: bool> DisallowedPath<REPLACEMENT_ALLOWED> {
+    /// Resolves the path to the definitions matching `def_kind_predicate`, warning if there are
+    /// none.
+    ///
+    /// Unlike [`create_disallowed_map`], a path with a single segment refers to a crate.
+    pub fn resolve(
+        &self,
+        tcx: TyCtxt<'_>,
+        ns: PathNS,
+        def_kind_predicate: impl Fn(DefKind) -> bool,
+        predicate_description: &str,
+    ) -> Vec<DefId> {
+        let sym_path: Vec<Symbol> = self.path.node.split("::").map(Symbol::intern).collect();
+        let mut resolutions = lookup_path_or_crate(tcx, ns, &sym_path);
+        resolutions.retain(|&def_id| def_kind_predicate(tcx.def_kind(def_id)));
+
+        if resolutions.is_empty() {
+            self.warn_unresolved(tcx, &self.path, &sym_path, false, predicate_description);
+        }
+        resolutions
+    }
+
+    /// Resolves the `module:` contexts, warning about those which don't refer to a module.
+    fn check_contexts(&self, tcx: TyCtxt<'_>) {
+        for context in &self.contexts {
+            if let DisallowedContext::Module { path, .. } = context
+                && context.modules(tcx).is_empty()
+            {
+                self.warn_unresolved(tcx, path, &module_sym_path(tcx, &path.node), false, "module");
+            }
+        }
+    }
+
+    fn warn_unresolved(
+        &self,
+        tcx: TyCtxt<'_>,
+        path: &Spanned<String>,
+        sym_path: &[Symbol],
+        found_prim_ty: bool,
+        predicate_description: &str,
+    ) {
+        if self.allow_invalid
+            // Don't warn about unloaded crates:
+            // https://github.com/rust-lang/rust-clippy/pull/14397#issuecomment-2848328221
+            || (sym_path.len() >= 2 && find_crates(tcx, sym_path[0]).is_empty())
+        {
+            return;
+        }
+
+        // Relookup the path in an arbitrary namespace to get a good `expected, found` message
+        let found_def_ids = lookup_path(tcx, PathNS::Arbitrary, sym_path);
+        let message = if let Some(&def_id) = found_def_ids.first() {
+            let (article, description) = tcx.article_and_description(def_id);
+            format!("expected a {predicate_description}, found {article} {description}")
+        } else if found_prim_ty {
+            format!("expected a {predicate_description}, found a primitive type")
+        } else {
+            format!("`{}` does not refer to a reachable {predicate_description}", path.node)
+        };
+        tcx.sess
+            .dcx()
+            .struct_span_warn(path.span, message)
+            .with_help("add `allow-invalid = true` to the entry to suppress this warning")
+            .emit();
+    }
 }
 
 impl Deserialize for DisallowedPath<false> {
@@ -168,12 +269,22 @@ impl Deserialize for DisallowedPath<false> {
                 reason: None,
                 replacement: None,
                 allow_invalid: false,
+                contexts: Vec::new(),
             })
         } else if let Some(table) = value.as_ref().as_table() {
             deserialize_table!(dcx, table,
                 path("path"): Spanned<String>,
                 reason("reason"): String,
                 allow_invalid("allow-invalid"): bool,
+                ; unsupported("contexts") => |sp: Span| {
+                    dcx.inner
+                        .struct_span_err(sp, "`contexts` isn't supported by this option")
+                        .with_note(
+                            "only `disallowed-fields`, `disallowed-macros`, `disallowed-methods`, \
+                            `disallowed-traits` and `disallowed-types` support `contexts`",
+                        )
+                        .emit();
+                },
             );
             let Some(path) = path else {
                 dcx.span_err(value.span(), "missing required field `path`");
@@ -184,6 +295,7 @@ impl Deserialize for DisallowedPath<false> {
                 reason,
                 replacement: None,
                 allow_invalid: allow_invalid.unwrap_or(false),
+                contexts: Vec::new(),
             })
         } else {
             dcx.span_err(value.span(), "expected either a string or an inline table");
@@ -203,6 +315,7 @@ impl Deserialize for DisallowedPath<true> {
                 reason: None,
                 replacement: None,
                 allow_invalid: false,
+                contexts: Vec::new(),
             })
         } else if let Some(table) = value.as_ref().as_table() {
             deserialize_table!(dcx, table,
@@ -210,6 +323,7 @@ impl Deserialize for DisallowedPath<true> {
                 reason("reason"): String,
                 replacement("replacement"): String,
                 allow_invalid("allow-invalid"): bool,
+                contexts("contexts"): Vec<DisallowedContext>,
             );
             let Some(path) = path else {
                 dcx.span_err(value.span(), "missing required field `path`");
@@ -220,6 +334,7 @@ impl Deserialize for DisallowedPath<true> {
                 reason,
                 replacement,
                 allow_invalid: allow_invalid.unwrap_or(false),
+                contexts: contexts.unwrap_or_default(),
             })
         } else {
             dcx.span_err(value.span(), "expected either a string or an inline table");
@@ -228,11 +343,152 @@ impl Deserialize for DisallowedPath<true> {
     }
 }
 
+/// A context a disallowed path can be restricted to.
+#[derive(Clone)]
+pub enum DisallowedContext {
+    /// The body of an `async fn` or an `async` block, excluding closures inside of it
+    Async,
+    /// A const context, e.g. the body of a `const` item or a `const fn`
+    Const,
+    /// An `unsafe` block or the body of an `unsafe fn`
+    Unsafe,
+    /// A test function or code behind `#[cfg(test)]`
+    Test,
+    /// A module and its submodules, written as `module:crate_name::path`
+    Module {
+        path: Spanned<String>,
+        /// The modules `path` resolves to, resolved when the disallowed paths are loaded
+        modules: OnceLock<Vec<DefId>>,
+    },
+}
+
+impl DisallowedContext {
+    const NAMES: &[&str] = &["async", "const", "unsafe", "test", "module:<path>"];
+
+    /// Gets the modules of a `module:` context, resolving them on first use.
+    fn modules(&self, tcx: TyCtxt<'_>) -> &[DefId] {
+        match self {
+            Self::Module { path, modules } => {
+                modules.get_or_init(|| resolve_module(tcx, &module_sym_path(tcx, &path.node)))
+            },
+            _ => &[],
+        }
+    }
+
+    fn contains(&self, tcx: TyCtxt<'_>, hir_id: HirId) -> bool {
+        match self {
+            Self::Async => {
+                for (_, node) in tcx.hir_parent_iter(hir_id) {
+                    match node {
+                        Node::Expr(Expr {
+                            kind: ExprKind::Closure(closure),
+                            ..
+                        }) => {
+                            return matches!(
+                                closure.kind,
+                                ClosureKind::Coroutine(CoroutineKind::Desugared(CoroutineDesugaring::Async, _))
+                            );
+                        },
+                        Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) | Node::ForeignItem(_) => return false,
+                        _ => {},
+                    }
+                }
+                false
+            },
+            Self::Const => tcx
+                .hir_parent_iter(hir_id)
+                .find_map(|(_, node)| node.associated_body())
+                .is_some_and(|(def_id, _)| tcx.hir_body_const_context(def_id).is_some()),
+            Self::Unsafe => {
+                for (_, node) in tcx.hir_parent_iter(hir_id) {
+                    match node {
+                        Node::Block(block)
+                            if block.rules == BlockCheckMode::UnsafeBlock(UnsafeSource::UserProvided) =>
+                        {
+                            return true;
+                        },
+                        Node::Item(_) | Node::ImplItem(_) | Node::TraitItem(_) | Node::ForeignItem(_) => {
+                            return node.fn_sig().is_some_and(|sig| sig.header.is_unsafe());
+                        },
+                        _ => {},
+                    }
+                }
+                false
+            },
+            Self::Test => is_in_test(tcx, hir_id),
+            Self::Module { .. } => {
+                let modules = self.modules(tcx);
+                iter::successors(Some(tcx.parent_module(hir_id).to_def_id()), |&id| tcx.opt_parent(id))
+                    .any(|id| modules.contains(&id))
+            },
+        }
+    }
+}
+
+impl Deserialize for DisallowedContext {
+    fn deserialize(dcx: &DiagCtxt<'_>, value: &TomlValue<'_>) -> Option<Self> {
+        let Some(s) = value.get_ref().as_str() else {
+            dcx.span_err(value.span(), "expected a string");
+            return None;
+        };
+        match s {
+            "async" => Some(Self::Async),
+            "const" => Some(Self::Const),
+            "unsafe" => Some(Self::Unsafe),
+            "test" => Some(Self::Test),
+            _ if let Some(path) = s.strip_prefix("module:") => Some(Self::Module {
+                path: Spanned {
+                    node: path.into(),
+                    span: dcx.make_sp(value.span()),
+                },
+                modules: OnceLock::new(),
+            }),
+            _ => {
+                let sp = dcx.make_sp(value.span());
+                let mut diag = dcx.inner.struct_span_err(sp, "unknown context");
+                if let Some(sugg) = find_closest_match(s, Self::NAMES) {
+                    diag.span_suggestion(sp, "did you mean", sugg, Applicability::MaybeIncorrect);
+                }
+                diag.note(create_value_list_msg(dcx, Self::NAMES));
+                diag.emit();
+                None
+            },
+        }
+    }
+}
+
+/// Resolves a path, treating a single segment as the name of a crate.
+fn lookup_path_or_crate(tcx: TyCtxt<'_>, ns: PathNS, sym_path: &[Symbol]) -> Vec<DefId> {
+    if let &[name] = sym_path {
+        find_crates(tcx, name).to_vec()
+    } else {
+        lookup_path(tcx, ns, sym_path)
+    }
+}
+
+/// Splits the path of a module, replacing a leading `crate` or `self` with the name of the crate
+/// being linted.
+fn module_sym_path(tcx: TyCtxt<'_>, path: &str) -> Vec<Symbol> {
+    path.split("::")
+        .enumerate()
+        .map(|(i, name)| match name {
+            "crate" | "self" if i == 0 => tcx.crate_name(LOCAL_CRATE),
+            _ => Symbol::intern(name),
+        })
+        .collect()
+}
+
+fn resolve_module(tcx: TyCtxt<'_>, sym_path: &[Symbol]) -> Vec<DefId> {
+    let mut modules = lookup_path_or_crate(tcx, PathNS::Type, sym_path);
+    modules.retain(|&def_id| tcx.def_kind(def_id) == DefKind::Mod);
+    modules
+}
+
 /// Creates a map of disallowed items to the reason they were disallowed.
 #[expect(clippy::type_complexity)]
 pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     tcx: TyCtxt<'_>,
-    disallowed_paths: &'static [DisallowedPath<REPLACEMENT_ALLOWED>],
+    disallowed_paths: impl IntoIterator<Item = &'static DisallowedPath<REPLACEMENT_ALLOWED>>,
     ns: PathNS,
     def_kind_predicate: impl Fn(DefKind) -> bool,
     predicate_description: &str,
@@ -258,29 +514,16 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
             (None, false)
         };
 
-        if resolutions.is_empty()
-            && prim_ty.is_none()
-            && !disallowed_path.allow_invalid
-            // Don't warn about unloaded crates:
-            // https://github.com/rust-lang/rust-clippy/pull/14397#issuecomment-2848328221
-            && (sym_path.len() < 2 || !find_crates(tcx, sym_path[0]).is_empty())
-        {
-            // Relookup the path in an arbitrary namespace to get a good `expected, found` message
-            let found_def_ids = lookup_path(tcx, PathNS::Arbitrary, &sym_path);
-            let message = if let Some(&def_id) = found_def_ids.first() {
-                let (article, description) = tcx.article_and_description(def_id);
-                format!("expected a {predicate_description}, found {article} {description}")
-            } else if found_prim_ty {
-                format!("expected a {predicate_description}, found a primitive type")
-            } else {
-                format!("`{path}` does not refer to a reachable {predicate_description}")
-            };
-            tcx.sess
-                .dcx()
-                .struct_span_warn(disallowed_path.path.span, message)
-                .with_help("add `allow-invalid = true` to the entry to suppress this warning")
-                .emit();
+        if resolutions.is_empty() && prim_ty.is_none() {
+            disallowed_path.warn_unresolved(
+                tcx,
+                &disallowed_path.path,
+                &sym_path,
+                found_prim_ty,
+                predicate_description,
+            );
         }
+        disallowed_path.check_contexts(tcx);
 
         for def_id in resolutions {
             def_ids.insert(def_id, (path, disallowed_path));
@@ -293,6 +536,96 @@ pub fn create_disallowed_map<const REPLACEMENT_ALLOWED: bool>(
     (def_ids, prim_tys)
 }
 
+/// An implementation of a trait for a type which is disallowed.
+#[derive(Clone)]
+pub struct DisallowedImpl {
+    /// The trait, and the reason and replacement of the implementation
+    pub trait_path: DisallowedPath,
+    /// The implementing type
+    pub self_ty: DisallowedPathWithoutReplacement,
+}
+
+impl Deserialize for DisallowedImpl {
+    fn deserialize(dcx: &DiagCtxt<'_>, value: &TomlValue<'_>) -> Option<Self> {
+        if let Some(table) = value.as_ref().as_table() {
+            deserialize_table!(dcx, table,
+                path("path"): Spanned<String>,
+                self_ty("for"): Spanned<String>,
+                reason("reason"): String,
+                replacement("replacement"): String,
+                allow_invalid("allow-invalid"): bool,
+            );
+            let Some(path) = path else {
+                dcx.span_err(value.span(), "missing required field `path`");
+                return None;
+            };
+            let Some(self_ty) = self_ty else {
+                dcx.span_err(value.span(), "missing required field `for`");
+                return None;
+            };
+            let allow_invalid = allow_invalid.unwrap_or(false);
+            Some(DisallowedImpl {
+                trait_path: DisallowedPath {
+                    path,
+                    reason,
+                    replacement,
+                    allow_invalid,
+                    contexts: Vec::new(),
+                },
+                self_ty: DisallowedPath {
+                    path: self_ty,
+                    reason: None,
+                    replacement: None,
+                    allow_invalid,
+                    contexts: Vec::new(),
+                },
+            })
+        } else {
+            dcx.span_err(value.span(), "expected an inline table");
+            None
+        }
+    }
+}
+
+/// A layer of the crate's architecture and the items outside of it that the layer may use.
+#[derive(Clone)]
+pub struct AllowedDependencies {
+    /// The module or crate making up the layer, and the reason it may not use other layers
+    pub layer: DisallowedPathWithoutReplacement,
+    /// The items from other layers that the layer may use, including anything inside of them
+    pub allowed: Vec<DisallowedPathWithoutReplacement>,
+}
+
+impl Deserialize for AllowedDependencies {
+    fn deserialize(dcx: &DiagCtxt<'_>, value: &TomlValue<'_>) -> Option<Self> {
+        if let Some(table) = value.as_ref().as_table() {
+            deserialize_table!(dcx, table,
+                path("path"): Spanned<String>,
+                reason("reason"): String,
+                allowed("allowed"): Vec<DisallowedPathWithoutReplacement>,
+                allow_invalid("allow-invalid"): bool,
+            );
+            let Some(path) = path else {
+                dcx.span_err(value.span(), "missing required field `path`");
+                return None;
+            };
+            Some(AllowedDependencies {
+                layer: DisallowedPath {
+                    path,
+                    reason,
+                    replacement: None,
+                    allow_invalid: allow_invalid.unwrap_or(false),
+                    contexts: Vec::new(),
+                },
+                allowed: allowed.unwrap_or_default(),
+            })
+        } else {
+            dcx.span_err(value.span(), "expected an inline table");
+            None
+        }
+    }
+}
+
 conf_enum! {
     #[derive(PartialEq, Eq)]
     pub MatchLintBehaviour {
@@ -327,6 +660,7 @@ impl Deserialize for BraceKind {
     }
 }
 
+#[derive(Clone)]
 pub struct MacroMatcher {
     pub name: String,
     pub braces: (char, char),
@@ -385,6 +719,7 @@ conf_enum! {
 ///
 /// The [`Deserialize`] implementation checks that there are no duplicates in
 /// the user configuration.
+#[derive(Clone)]
 pub struct SourceItemOrdering(Vec<SourceItemOrderingCategory>);
 
 impl SourceItemOrdering {
@@ -0,0 +1,188 @@
+use clippy_config::Conf;
+use clippy_config::types::DisallowedPathWithoutReplacement;
+use clippy_utils::call_graph::{CallSummaries, Summarize};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::paths::PathNS;
+use clippy_utils::resolved_callee;
+use clippy_utils::visitors::for_each_expr_without_closures;
+use core::ops::ControlFlow;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, DefIdMap, LocalDefId};
+use rustc_hir::{Body, Closure, ClosureKind, CoroutineDesugaring, CoroutineKind, Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{TyCtxt, TypeckResults, TypingEnv};
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for calls in async code which block the current thread, either directly or through
+    /// other functions of the crate. The diagnostic shows the chain of calls leading to the
+    /// blocking function.
+    ///
+    /// By default, a set of blocking functions from the standard library, e.g. `std::fs::read`,
+    /// `std::thread::sleep` and `std::sync::Mutex::lock`, is checked for. This can be changed with
+    /// the `blocking-apis` configuration.
+    ///
+    /// ### Why is this bad?
+    /// A blocking call stalls the thread of the executor, and with it every other task scheduled
+    /// on that thread.
+    ///
+    /// ### Known problems
+    /// Closures aren't followed, as they're usually run elsewhere, e.g. by `spawn_blocking`.
+    /// Neither are calls through trait objects or function pointers, or into other crates.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// async fn handler() -> String {
+    ///     load_config()
+    /// }
+    ///
+    /// fn load_config() -> String {
+    ///     std::fs::read_to_string("config.toml").unwrap()
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```ignore
+    /// async fn handler() -> String {
+    ///     load_config().await
+    /// }
+    ///
+    /// async fn load_config() -> String {
+    ///     tokio::fs::read_to_string("config.toml").await.unwrap()
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub BLOCKING_IN_ASYNC,
+    pedantic,
+    "calls in async code which reach a blocking function"
+}
+
+impl_lint_pass!(BlockingInAsync => [BLOCKING_IN_ASYNC]);
+
+pub struct BlockingInAsync {
+    blocking: BlockingFns,
+    /// The calls leading from a function of the crate to a blocking function, if there are any
+    chains: CallSummaries<Vec<DefId>>,
+}
+
+/// The blocking functions, along with their configuration
+struct BlockingFns(DefIdMap<&'static DisallowedPathWithoutReplacement>);
+
+impl BlockingInAsync {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let is_fn = |kind| matches!(kind, DefKind::Fn | DefKind::AssocFn);
+        let blocking = conf
+            .blocking_apis
+            .iter()
+            .flat_map(|path| {
+                path.resolve(tcx, PathNS::Value, is_fn, "function")
+                    .into_iter()
+                    .map(move |def_id| (def_id, path))
+            })
+            .collect();
+        Self {
+            blocking: BlockingFns(blocking),
+            chains: CallSummaries::default(),
+        }
+    }
+}
+
+impl BlockingFns {
+    /// Finds the chain of calls from calling `callee` to a blocking function.
+    fn chain_from(&self, tcx: TyCtxt<'_>, chains: &mut CallSummaries<Vec<DefId>>, callee: DefId) -> Option<Vec<DefId>> {
+        if self.0.contains_key(&callee) {
+            Some(vec![callee])
+        } else if let Some(local_id) = callee.as_local()
+            // Async functions are checked on their own
+            && !tcx.asyncness(callee).is_async()
+        {
+            let mut chain = chains.get(tcx, self, local_id)?;
+            chain.insert(0, callee);
+            Some(chain)
+        } else {
+            None
+        }
+    }
+}
+
+impl Summarize for BlockingFns {
+    type Summary = Vec<DefId>;
+
+    fn summarize(
+        &self,
+        tcx: TyCtxt<'_>,
+        chains: &mut CallSummaries<Vec<DefId>>,
+        def_id: LocalDefId,
+    ) -> Option<Vec<DefId>> {
+        let body = tcx.hir_maybe_body_owned_by(def_id)?;
+        calls(tcx, TypingEnv::post_analysis(tcx, def_id), tcx.typeck(def_id), body)
+            .into_iter()
+            .find_map(|(callee, _)| self.chain_from(tcx, chains, callee))
+    }
+}
+
+/// Collects the functions called in the body, without the ones called inside of closures.
+fn calls<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typing_env: TypingEnv<'tcx>,
+    typeck: &TypeckResults<'tcx>,
+    body: &Body<'tcx>,
+) -> Vec<(DefId, Span)> {
+    let mut calls = Vec::new();
+    for_each_expr_without_closures(body.value, |e| {
+        // Skip the calls `.await` and `for` loops desugar to
+        if e.span.desugaring_kind().is_some() {
+            return ControlFlow::<()>::Continue(());
+        }
+        if let Some(def_id) = resolved_callee(tcx, typing_env, typeck, e)
+            && matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+        {
+            calls.push((def_id, e.span));
+        }
+        ControlFlow::Continue(())
+    });
+    calls
+}
+
+impl<'tcx> LateLintPass<'tcx> for BlockingInAsync {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if let ExprKind::Closure(&Closure {
+            kind: ClosureKind::Coroutine(CoroutineKind::Desugared(CoroutineDesugaring::Async, _)),
+            body,
+            ..
+        }) = expr.kind
+            && !self.blocking.0.is_empty()
+        {
+            let body = cx.tcx.hir_body(body);
+            for (callee, span) in calls(cx.tcx, cx.typing_env(), cx.tcx.typeck_body(body.id()), body) {
+                let Some(chain) = self.blocking.chain_from(cx.tcx, &mut self.chains, callee) else {
+                    continue;
+                };
+                let blocking = *chain.last().unwrap();
+                span_lint_and_then(
+                    cx,
+                    BLOCKING_IN_ASYNC,
+                    span,
+                    format!(
+                        "call in async code reaches the blocking function `{}`",
+                        cx.tcx.def_path_str(blocking)
+                    ),
+                    |diag| {
+                        if chain.len() > 1 {
+                            let chain: Vec<_> = chain
+                                .iter()
+                                .map(|&def_id| format!("`{}`", cx.tcx.def_path_str(def_id)))
+                                .collect();
+                            diag.note(format!("call chain: {}", chain.join(" -> ")));
+                        }
+                        if let Some(path) = self.blocking.0.get(&blocking) {
+                            path.diag_amendment(span)(diag);
+                        }
+                        diag.help("use an async alternative, or move the call to a thread where blocking is fine");
+                    },
+                );
+            }
+        }
+    }
+}
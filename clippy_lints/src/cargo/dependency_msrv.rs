@@ -0,0 +1,80 @@
+use cargo_metadata::{DependencyKind, Metadata, Node, PackageId};
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::msrvs::Msrv;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::CRATE_HIR_ID;
+use rustc_hir::attrs::RustcVersion;
+use rustc_lint::LateContext;
+use rustc_span::DUMMY_SP;
+use std::collections::VecDeque;
+
+use super::INCOMPATIBLE_DEPENDENCY_MSRV;
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata, msrv: Msrv) {
+    let Some(msrv) = msrv.at(cx.tcx, CRATE_HIR_ID) else {
+        return;
+    };
+    let Some(package) = super::checked_package(cx, metadata) else {
+        return;
+    };
+    let Some(resolve) = &metadata.resolve else {
+        return;
+    };
+    let nodes: FxHashMap<&PackageId, &Node> = resolve.nodes.iter().map(|node| (&node.id, node)).collect();
+
+    // Walk the graph breadth first, so each dependency is shown with the shortest path pulling it in
+    let mut parents: FxHashMap<&PackageId, &PackageId> = FxHashMap::default();
+    let mut queue = VecDeque::from([&package.id]);
+    while let Some(id) = queue.pop_front() {
+        let Some(node) = nodes.get(id) else {
+            continue;
+        };
+        for dep in &node.deps {
+            // Dev dependencies aren't built by the users of the crate
+            if dep.pkg == package.id
+                || parents.contains_key(&dep.pkg)
+                || !dep
+                    .dep_kinds
+                    .iter()
+                    .any(|info| matches!(info.kind, DependencyKind::Normal | DependencyKind::Build))
+            {
+                continue;
+            }
+            parents.insert(&dep.pkg, id);
+            queue.push_back(&dep.pkg);
+
+            let dep_package = &metadata[&dep.pkg];
+            if let Some(rust_version) = &dep_package.rust_version
+                && let Ok(major) = u16::try_from(rust_version.major)
+                && let Ok(minor) = u16::try_from(rust_version.minor)
+                && let Ok(patch) = u16::try_from(rust_version.patch)
+                && (RustcVersion { major, minor, patch }) > msrv
+            {
+                span_lint_and_then(
+                    cx,
+                    INCOMPATIBLE_DEPENDENCY_MSRV,
+                    DUMMY_SP,
+                    format!(
+                        "dependency `{} v{}` requires Rust {rust_version}, which is newer than the MSRV of {msrv}",
+                        dep_package.name, dep_package.version
+                    ),
+                    |diag| {
+                        let mut path = vec![&dep.pkg];
+                        while let Some(&parent) = parents.get(path.last().unwrap()) {
+                            path.push(parent);
+                        }
+                        if path.len() > 2 {
+                            let path: Vec<_> = path
+                                .iter()
+                                .rev()
+                                .map(|&id| format!("`{} v{}`", metadata[id].name, metadata[id].version))
+                                .collect();
+                            diag.note(format!("pulled in by {}", path.join(" -> ")));
+                        }
+                        diag.help("use an older version of the dependency, or raise the MSRV");
+                    },
+                );
+            }
+        }
+    }
+}
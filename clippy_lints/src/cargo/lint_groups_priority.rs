@@ -1,23 +1,14 @@
-use super::LINT_GROUPS_PRIORITY;
+use super::{LINT_GROUPS_PRIORITY, toml_span};
 use clippy_utils::diagnostics::span_lint_and_then;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_errors::Applicability;
 use rustc_lint::{LateContext, unerased_lint_store};
-use rustc_span::{BytePos, Pos as _, SourceFile, Span, SyntaxContext};
+use rustc_span::SourceFile;
 use std::ops::Range;
 use std::path::Path;
 use toml::Spanned;
 use toml::de::{DeTable, DeValue};
 
-fn toml_span(range: Range<usize>, file: &SourceFile) -> Span {
-    Span::new(
-        file.start_pos + BytePos::from_usize(range.start),
-        file.start_pos + BytePos::from_usize(range.end),
-        SyntaxContext::root(),
-        None,
-    )
-}
-
 struct LintConfig<'a> {
     sp: Range<usize>,
     level: &'a str,
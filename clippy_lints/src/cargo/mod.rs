@@ -1,18 +1,27 @@
 mod common_metadata;
+mod dependency_msrv;
 mod feature_name;
 mod lint_groups_priority;
 mod multiple_crate_versions;
+mod unused_dependencies;
+mod unused_features;
 mod wildcard_dependencies;
+mod workspace_inheritance;
 
-use cargo_metadata::MetadataCommand;
+use cargo_metadata::{CrateType, Metadata, MetadataCommand, Package};
 use clippy_config::Conf;
 use clippy_utils::diagnostics::span_lint;
 use clippy_utils::is_lint_allowed;
+use clippy_utils::msrvs::Msrv;
 use rustc_data_structures::fx::FxHashSet;
 use rustc_hir::CRATE_HIR_ID;
+use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_lint::{LateContext, LateLintPass, Lint};
-use rustc_session::impl_lint_pass;
-use rustc_span::DUMMY_SP;
+use rustc_session::{config, impl_lint_pass};
+use rustc_span::{BytePos, DUMMY_SP, Pos as _, SourceFile, Span, SyntaxContext};
+use std::env;
+use std::ops::Range;
+use std::path::Path;
 
 declare_clippy_lint! {
     /// ### What it does
@@ -56,6 +65,60 @@ declare_clippy_lint! {
     "common metadata is defined in `Cargo.toml`"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for dependencies, direct or not, whose `rust-version` is newer than the MSRV of the
+    /// crate. The diagnostic shows the path through the dependency graph which pulls them in.
+    ///
+    /// ### Why is this bad?
+    /// The crate can't be built with its MSRV, as Cargo refuses to build the dependency.
+    ///
+    /// ### Known problems
+    /// The versions are the ones of the current `Cargo.lock`. Depending crates may still resolve
+    /// to older versions of the dependency, e.g. with the MSRV aware resolver.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [package]
+    /// rust-version = "1.60"
+    ///
+    /// [dependencies]
+    /// # `rust-version = "1.68"`
+    /// itoa = "1.0.18"
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub INCOMPATIBLE_DEPENDENCY_MSRV,
+    cargo,
+    "dependencies which require a newer Rust version than the MSRV"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for dependencies inherited from the `[workspace.dependencies]` which set
+    /// `default-features = false` while the workspace enables the default features, or which list
+    /// features the workspace already enables.
+    ///
+    /// ### Why is this bad?
+    /// Features of an inherited dependency are added to the ones of the workspace, so Cargo
+    /// ignores the `default-features = false`, and the repeated features have no effect.
+    ///
+    /// ### Example
+    /// ```toml
+    /// # With `serde = { version = "1.0", features = ["derive"] }` in the workspace
+    /// [dependencies]
+    /// serde = { workspace = true, default-features = false, features = ["derive"] }
+    /// ```
+    /// Use instead:
+    /// ```toml
+    /// [dependencies]
+    /// serde = { workspace = true }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES,
+    cargo,
+    "features of an inherited dependency which have no effect"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for lint groups with the same priority as lints in the `Cargo.toml`
@@ -93,6 +156,35 @@ declare_clippy_lint! {
     "a lint group in `Cargo.toml` at the same priority as a lint"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for packages of a workspace with a `[workspace.lints]` table which don't inherit it
+    /// with `lints.workspace = true`.
+    ///
+    /// ### Why is this bad?
+    /// The package is silently linted differently from the rest of the workspace.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [package]
+    /// name = "member"
+    /// version = "0.1.0"
+    /// ```
+    /// Use instead:
+    /// ```toml
+    /// [package]
+    /// name = "member"
+    /// version = "0.1.0"
+    ///
+    /// [lints]
+    /// workspace = true
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub MISSING_WORKSPACE_LINTS,
+    cargo,
+    "packages which don't inherit the lints of the workspace"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks to see if multiple versions of a crate are being
@@ -152,6 +244,32 @@ declare_clippy_lint! {
     "usage of a negative feature name"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for dependencies which are declared in the `[workspace.dependencies]`, but which a
+    /// package of the workspace declares again instead of inheriting them with `workspace = true`.
+    ///
+    /// ### Why is this bad?
+    /// The version, source and features of the dependency have to be kept in sync by hand, and
+    /// easily diverge from the rest of the workspace.
+    ///
+    /// ### Example
+    /// ```toml
+    /// # With `regex = "1.10"` in the workspace
+    /// [dependencies]
+    /// regex = "1.9"
+    /// ```
+    /// Use instead:
+    /// ```toml
+    /// [dependencies]
+    /// regex = { workspace = true }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub NON_INHERITED_WORKSPACE_DEPENDENCIES,
+    cargo,
+    "dependencies which are redeclared instead of being inherited from the workspace"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for feature names with prefix `use-`, `with-` or suffix `-support`
@@ -184,6 +302,63 @@ declare_clippy_lint! {
     "usage of a redundant feature name"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for dependencies in the `[dependencies]` of the `Cargo.toml` which the crate never
+    /// uses, through either an `extern crate` item or a path.
+    ///
+    /// ### Why is this bad?
+    /// Unused dependencies slow down the build, and needlessly grow the set of crates which have
+    /// to be trusted and kept up to date.
+    ///
+    /// ### Known problems
+    /// Only packages with either a library or a single binary are checked. Dependencies which are
+    /// only used by other targets, e.g. an example, are reported as well; those can usually be
+    /// moved to the `[dev-dependencies]`.
+    ///
+    /// Optional and platform specific dependencies aren't checked.
+    ///
+    /// ### Example
+    /// ```toml
+    /// # `itoa` is never used by the crate
+    /// [dependencies]
+    /// itoa = "1.0"
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub UNUSED_CARGO_DEPENDENCIES,
+    cargo,
+    "dependencies in `Cargo.toml` which are never used"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for features in the `[features]` of the `Cargo.toml` which aren't tested by any
+    /// `cfg` of the package, nor by its build script.
+    ///
+    /// Features enabling other features or optional dependencies aren't checked, as they have an
+    /// effect without being tested.
+    ///
+    /// ### Why is this bad?
+    /// Enabling the feature does nothing, which is surprising to the users of the crate.
+    ///
+    /// ### Known problems
+    /// Features are looked for as `feature = "..."` in the Rust files of the package rather than
+    /// in the `cfg`s of the crate being checked, as the other targets of the package, e.g. its
+    /// tests and examples, may test them. So a `feature = "..."` outside of a `cfg`, e.g. in the
+    /// input of a macro, counts as tested.
+    ///
+    /// ### Example
+    /// ```toml
+    /// [features]
+    /// # `std` is never tested in a `cfg`
+    /// std = []
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub UNUSED_CARGO_FEATURES,
+    cargo,
+    "features in `Cargo.toml` which are never tested"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     /// Checks for wildcard dependencies in the `Cargo.toml`.
@@ -215,16 +390,23 @@ declare_clippy_lint! {
 
 impl_lint_pass!(Cargo => [
     CARGO_COMMON_METADATA,
+    INCOMPATIBLE_DEPENDENCY_MSRV,
+    INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES,
     LINT_GROUPS_PRIORITY,
+    MISSING_WORKSPACE_LINTS,
     MULTIPLE_CRATE_VERSIONS,
     NEGATIVE_FEATURE_NAMES,
+    NON_INHERITED_WORKSPACE_DEPENDENCIES,
     REDUNDANT_FEATURE_NAMES,
+    UNUSED_CARGO_DEPENDENCIES,
+    UNUSED_CARGO_FEATURES,
     WILDCARD_DEPENDENCIES,
 ]);
 
 pub struct Cargo {
     allowed_duplicate_crates: &'static FxHashSet<String>,
     ignore_publish: bool,
+    msrv: Msrv,
 }
 
 impl Cargo {
@@ -232,6 +414,7 @@ impl Cargo {
         Self {
             allowed_duplicate_crates: &conf.allowed_duplicate_crates,
             ignore_publish: conf.cargo_ignore_publish,
+            msrv: conf.msrv.into(),
         }
     }
 }
@@ -240,11 +423,19 @@ impl LateLintPass<'_> for Cargo {
     fn check_crate(&mut self, cx: &LateContext<'_>) {
         static NO_DEPS_LINTS: &[&Lint] = &[
             CARGO_COMMON_METADATA,
+            INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES,
+            MISSING_WORKSPACE_LINTS,
+            NON_INHERITED_WORKSPACE_DEPENDENCIES,
             REDUNDANT_FEATURE_NAMES,
             NEGATIVE_FEATURE_NAMES,
+            UNUSED_CARGO_FEATURES,
             WILDCARD_DEPENDENCIES,
         ];
-        static WITH_DEPS_LINTS: &[&Lint] = &[MULTIPLE_CRATE_VERSIONS];
+        static WITH_DEPS_LINTS: &[&Lint] = &[
+            INCOMPATIBLE_DEPENDENCY_MSRV,
+            MULTIPLE_CRATE_VERSIONS,
+            UNUSED_CARGO_DEPENDENCIES,
+        ];
 
         lint_groups_priority::check(cx);
 
@@ -256,7 +447,9 @@ impl LateLintPass<'_> for Cargo {
                 Ok(metadata) => {
                     common_metadata::check(cx, &metadata, self.ignore_publish);
                     feature_name::check(cx, &metadata);
+                    unused_features::check(cx, &metadata);
                     wildcard_dependencies::check(cx, &metadata);
+                    workspace_inheritance::check(cx, &metadata);
                 },
                 Err(e) => {
                     for lint in NO_DEPS_LINTS {
@@ -272,7 +465,9 @@ impl LateLintPass<'_> for Cargo {
         {
             match MetadataCommand::new().exec() {
                 Ok(metadata) => {
+                    dependency_msrv::check(cx, &metadata, self.msrv);
                     multiple_crate_versions::check(cx, &metadata, self.allowed_duplicate_crates);
+                    unused_dependencies::check(cx, &metadata);
                 },
                 Err(e) => {
                     for lint in WITH_DEPS_LINTS {
@@ -283,3 +478,41 @@ impl LateLintPass<'_> for Cargo {
         }
     }
 }
+
+/// Finds the package of the crate being compiled, if the crate is the library of the package, or
+/// its binary if it has a single one and no library. This keeps the lints checking the whole
+/// package from running once per target.
+fn checked_package<'a>(cx: &LateContext<'_>, metadata: &'a Metadata) -> Option<&'a Package> {
+    if cx.tcx.sess.is_test_crate() {
+        return None;
+    }
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR")?;
+    let package = metadata.packages.iter().find(|p| {
+        p.manifest_path
+            .parent()
+            .is_some_and(|dir| dir == Path::new(&manifest_dir))
+    })?;
+    let is_executable = cx.tcx.crate_types().contains(&config::CrateType::Executable);
+    let target = match package
+        .targets
+        .iter()
+        .find(|t| !t.crate_types.contains(&CrateType::Bin))
+    {
+        Some(lib) if !is_executable => lib,
+        None if is_executable => match package.targets.iter().filter(|t| t.is_bin()).collect::<Vec<_>>()[..] {
+            [bin] => bin,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    (target.name.replace('-', "_") == cx.tcx.crate_name(LOCAL_CRATE).as_str()).then_some(package)
+}
+
+fn toml_span(range: Range<usize>, file: &SourceFile) -> Span {
+    Span::new(
+        file.start_pos + BytePos::from_usize(range.start),
+        file.start_pos + BytePos::from_usize(range.end),
+        SyntaxContext::root(),
+        None,
+    )
+}
@@ -0,0 +1,67 @@
+use cargo_metadata::{DependencyKind, Metadata};
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_lint::LateContext;
+use rustc_session::cstore::ExternCrate;
+use rustc_span::{DUMMY_SP, Symbol};
+
+use super::UNUSED_CARGO_DEPENDENCIES;
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
+    let Some(package) = super::checked_package(cx, metadata) else {
+        return;
+    };
+    // Each target is compiled separately, so the dependencies used only by the binaries of a package
+    // with a library can't be seen.
+    if package
+        .targets
+        .iter()
+        .filter(|t| !(t.is_example() || t.is_test() || t.is_bench() || t.is_custom_build()))
+        .count()
+        > 1
+    {
+        return;
+    }
+    let Some(node) = metadata
+        .resolve
+        .as_ref()
+        .and_then(|resolve| resolve.nodes.iter().find(|node| node.id == package.id))
+    else {
+        return;
+    };
+
+    // The crates passed with `--extern` are only loaded once the crate names them
+    let used: FxHashSet<_> = cx
+        .tcx
+        .crates(())
+        .iter()
+        .filter(|&&cnum| cx.tcx.extern_crate(cnum).is_some_and(ExternCrate::is_direct))
+        .map(|&cnum| cx.tcx.crate_name(cnum))
+        .collect();
+
+    for dep in &node.deps {
+        let dep_package = &metadata[&dep.pkg];
+        let is_checked = dep
+            .dep_kinds
+            .iter()
+            .any(|info| info.kind == DependencyKind::Normal && info.target.is_none())
+            && !package.dependencies.iter().any(|d| {
+                d.optional
+                    && d.kind == DependencyKind::Normal
+                    && d.name == *dep_package.name
+                    && d.rename
+                        .as_ref()
+                        .is_none_or(|rename| rename.replace('-', "_") == dep.name)
+            });
+        if is_checked && !used.contains(&Symbol::intern(&dep.name)) {
+            span_lint_and_help(
+                cx,
+                UNUSED_CARGO_DEPENDENCIES,
+                DUMMY_SP,
+                format!("dependency `{}` is never used", dep.name),
+                None,
+                "remove it from the `[dependencies]`, or move it to the `[dev-dependencies]` if it's only used by tests or examples",
+            );
+        }
+    }
+}
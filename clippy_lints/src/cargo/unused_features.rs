@@ -0,0 +1,124 @@
+use cargo_metadata::Metadata;
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_lexer::{FrontmatterAllowed, LiteralKind, TokenKind, tokenize};
+use rustc_lint::LateContext;
+use rustc_span::DUMMY_SP;
+use std::fs;
+use std::path::Path;
+
+use super::UNUSED_CARGO_FEATURES;
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
+    let Some(package) = super::checked_package(cx, metadata) else {
+        return;
+    };
+    let Some(dir) = package.manifest_path.parent() else {
+        return;
+    };
+
+    let mut tested = Tested::default();
+    tested.visit_dir(dir.as_std_path());
+
+    for (feature, enables) in &package.features {
+        if feature != "default"
+            && enables.is_empty()
+            && !tested.cfgs.contains(feature)
+            && !tested.env_vars.contains(&feature.to_uppercase().replace('-', "_"))
+        {
+            span_lint_and_help(
+                cx,
+                UNUSED_CARGO_FEATURES,
+                DUMMY_SP,
+                format!("feature `{feature}` is never tested in any `cfg`"),
+                None,
+                format!("remove it from the `[features]`, or use it in a `#[cfg(feature = \"{feature}\")]`"),
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+struct Tested {
+    /// The features named in a `feature = "..."`, as in `cfg`, `cfg_attr` and `cfg!`
+    cfgs: FxHashSet<String>,
+    /// The features read by the build script through the `CARGO_FEATURE_*` variables
+    env_vars: FxHashSet<String>,
+}
+
+impl Tested {
+    fn visit_dir(&mut self, dir: &Path) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            if name
+                .to_str()
+                .is_some_and(|name| name.starts_with('.') || name == "target")
+            {
+                continue;
+            }
+            if path.is_dir() {
+                // Nested packages, e.g. the members of a workspace, have their own features
+                if !path.join("Cargo.toml").exists() {
+                    self.visit_dir(&path);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs")
+                && let Ok(text) = fs::read_to_string(&path)
+            {
+                self.visit_text(&text);
+            }
+        }
+    }
+
+    /// Collects the features from the tokens of a source file, so the ones in comments and strings
+    /// aren't mistaken for a `feature = "..."`.
+    fn visit_text(&mut self, text: &str) {
+        let mut pos = 0;
+        let tokens: Vec<_> = tokenize(text, FrontmatterAllowed::Yes)
+            .filter_map(|token| {
+                let token_text = &text[pos..pos + token.len as usize];
+                pos += token.len as usize;
+                (!matches!(
+                    token.kind,
+                    TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. }
+                ))
+                .then_some((token.kind, token_text))
+            })
+            .collect();
+
+        for window in tokens.windows(3) {
+            if let [
+                (TokenKind::Ident, "feature"),
+                (TokenKind::Eq, _),
+                (
+                    TokenKind::Literal {
+                        kind: LiteralKind::Str { .. },
+                        ..
+                    },
+                    feature,
+                ),
+            ] = window
+            {
+                self.cfgs.insert(feature.trim_matches('"').to_owned());
+            }
+        }
+        for (kind, token_text) in tokens {
+            if let TokenKind::Literal {
+                kind: LiteralKind::Str { .. } | LiteralKind::RawStr { .. },
+                ..
+            } = kind
+            {
+                for (i, _) in token_text.match_indices("CARGO_FEATURE_") {
+                    let rest = &token_text[i + "CARGO_FEATURE_".len()..];
+                    let end = rest
+                        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                        .unwrap_or(rest.len());
+                    self.env_vars.insert(rest[..end].to_owned());
+                }
+            }
+        }
+    }
+}
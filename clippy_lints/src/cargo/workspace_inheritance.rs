@@ -0,0 +1,283 @@
+use cargo_metadata::Metadata;
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_and_then};
+use rustc_errors::Applicability;
+use rustc_lint::LateContext;
+use rustc_span::SourceFile;
+use std::env;
+use std::path::Path;
+use std::sync::Arc;
+use toml::Spanned;
+use toml::de::{DeString, DeTable, DeValue};
+
+use super::{
+    INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES, MISSING_WORKSPACE_LINTS, NON_INHERITED_WORKSPACE_DEPENDENCIES,
+    toml_span,
+};
+
+static DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+type Entry<'a, 'i> = (&'a Spanned<DeString<'i>>, &'a Spanned<DeValue<'i>>);
+
+fn entry<'a, 'i>(table: &'a DeTable<'i>, key: &str) -> Option<Entry<'a, 'i>> {
+    table.iter().find(|(name, _)| name.get_ref() == key)
+}
+
+fn table<'a, 'i>(table: &'a DeTable<'i>, key: &str) -> Option<&'a DeTable<'i>> {
+    table.get(key)?.get_ref().as_table()
+}
+
+fn is_inherited(value: &DeValue<'_>) -> bool {
+    value
+        .as_table()
+        .and_then(|tbl| tbl.get("workspace"))
+        .and_then(|workspace| workspace.get_ref().as_bool())
+        == Some(true)
+}
+
+/// Loads the manifest at `path`, which is shown relative to the workspace like the source files.
+fn load_manifest(cx: &LateContext<'_>, path: &Path) -> Option<Arc<SourceFile>> {
+    let path = env::current_dir()
+        .ok()
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path);
+    cx.tcx.sess.source_map().load_file(path).ok()
+}
+
+struct Manifest<'a, 'i> {
+    file: &'a SourceFile,
+    src: &'i str,
+    table: &'a DeTable<'i>,
+}
+
+pub(super) fn check(cx: &LateContext<'_>, metadata: &Metadata) {
+    let Some(package) = super::checked_package(cx, metadata) else {
+        return;
+    };
+    if let Some(root_file) = load_manifest(cx, metadata.workspace_root.join("Cargo.toml").as_std_path())
+        && let Some(member_file) = load_manifest(cx, package.manifest_path.as_std_path())
+        && let Some(root_src) = root_file.src.as_deref()
+        && let Some(member_src) = member_file.src.as_deref()
+        && let Ok(root_table) = DeTable::parse(root_src)
+        && let Ok(member_table) = DeTable::parse(member_src)
+        && let Some(workspace) = table(root_table.get_ref(), "workspace")
+    {
+        let root = Manifest {
+            file: &root_file,
+            src: root_src,
+            table: workspace,
+        };
+        let member = Manifest {
+            file: &member_file,
+            src: member_src,
+            table: member_table.get_ref(),
+        };
+        check_dependencies(cx, &root, &member);
+        check_lints(cx, &root, &member);
+    }
+}
+
+fn check_dependencies(cx: &LateContext<'_>, root: &Manifest<'_, '_>, member: &Manifest<'_, '_>) {
+    let Some(workspace_deps) = table(root.table, "dependencies") else {
+        return;
+    };
+    let mut dep_tables: Vec<&DeTable<'_>> = DEPENDENCY_TABLES
+        .iter()
+        .filter_map(|name| table(member.table, name))
+        .collect();
+    if let Some(targets) = table(member.table, "target") {
+        for (_, target) in targets {
+            if let Some(target) = target.get_ref().as_table() {
+                dep_tables.extend(DEPENDENCY_TABLES.iter().filter_map(|name| table(target, name)));
+            }
+        }
+    }
+
+    for (name, dep) in dep_tables.into_iter().flatten() {
+        let Some(workspace_dep) = entry(workspace_deps, name.get_ref()) else {
+            continue;
+        };
+        if is_inherited(dep.get_ref()) {
+            check_features(cx, root, member, (name, dep), workspace_dep);
+        } else {
+            lint_non_inherited(cx, root, member, (name, dep), workspace_dep);
+        }
+    }
+}
+
+fn dep_version<'a>(value: &'a DeValue<'_>) -> Option<&'a str> {
+    match value {
+        DeValue::String(version) => Some(version),
+        DeValue::Table(tbl) => tbl.get("version")?.get_ref().as_str(),
+        _ => None,
+    }
+}
+
+fn default_features<'a, 'i>(dep: &'a DeTable<'i>) -> Option<Entry<'a, 'i>> {
+    ["default-features", "default_features"]
+        .iter()
+        .find_map(|key| entry(dep, key))
+}
+
+fn lint_non_inherited(
+    cx: &LateContext<'_>,
+    root: &Manifest<'_, '_>,
+    member: &Manifest<'_, '_>,
+    (name, dep): Entry<'_, '_>,
+    (workspace_name, workspace_dep): Entry<'_, '_>,
+) {
+    span_lint_and_then(
+        cx,
+        NON_INHERITED_WORKSPACE_DEPENDENCIES,
+        toml_span(name.span(), member.file),
+        format!(
+            "dependency `{}` is redeclared instead of being inherited from the workspace",
+            name.get_ref()
+        ),
+        |diag| {
+            diag.span_note(
+                toml_span(workspace_name.span(), root.file),
+                "the workspace declares it here",
+            );
+            if let Some(version) = dep_version(dep.get_ref())
+                && let Some(workspace_version) = dep_version(workspace_dep.get_ref())
+                && version != workspace_version
+            {
+                diag.note(format!(
+                    "the version `{version}` diverges from the version `{workspace_version}` of the workspace"
+                ));
+            }
+
+            // Only the features and whether the dependency is optional can be set along with
+            // `workspace = true`
+            let is_inline = member.src[dep.span()].starts_with(['"', '{']);
+            if is_inline {
+                let mut fields = vec!["workspace = true".to_owned()];
+                if let Some(tbl) = dep.get_ref().as_table() {
+                    for key in ["features", "optional"] {
+                        if let Some((_, value)) = entry(tbl, key) {
+                            fields.push(format!("{key} = {}", &member.src[value.span()]));
+                        }
+                    }
+                }
+                diag.span_suggestion_verbose(
+                    toml_span(dep.span(), member.file),
+                    "inherit the dependency from the workspace",
+                    format!("{{ {} }}", fields.join(", ")),
+                    Applicability::MaybeIncorrect,
+                );
+            } else {
+                diag.help("inherit the dependency from the workspace with `workspace = true`");
+            }
+        },
+    );
+}
+
+fn check_features(
+    cx: &LateContext<'_>,
+    root: &Manifest<'_, '_>,
+    member: &Manifest<'_, '_>,
+    (name, dep): Entry<'_, '_>,
+    (workspace_name, workspace_dep): Entry<'_, '_>,
+) {
+    let Some(tbl) = dep.get_ref().as_table() else {
+        return;
+    };
+    let workspace_tbl = workspace_dep.get_ref().as_table();
+    if let Some((key, value)) = default_features(tbl)
+        && value.get_ref().as_bool() == Some(false)
+        && workspace_tbl
+            .and_then(default_features)
+            .and_then(|(_, value)| value.get_ref().as_bool())
+            != Some(false)
+    {
+        span_lint_and_then(
+            cx,
+            INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES,
+            toml_span(key.span().start..value.span().end, member.file),
+            format!(
+                "`default-features = false` has no effect on the inherited dependency `{}`",
+                name.get_ref()
+            ),
+            |diag| {
+                diag.span_note(
+                    toml_span(workspace_name.span(), root.file),
+                    "the workspace enables the default features here",
+                );
+                diag.help("set `default-features = false` in the `[workspace.dependencies]` instead");
+            },
+        );
+    }
+
+    if let Some(features) = tbl.get("features").and_then(|features| features.get_ref().as_array())
+        && let Some(workspace_features) = workspace_tbl
+            .and_then(|tbl| tbl.get("features"))
+            .and_then(|features| features.get_ref().as_array())
+    {
+        for feature in &**features {
+            if let Some(feature_name) = feature.get_ref().as_str()
+                && workspace_features
+                    .iter()
+                    .any(|workspace_feature| workspace_feature.get_ref().as_str() == Some(feature_name))
+            {
+                span_lint_and_help(
+                    cx,
+                    INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES,
+                    toml_span(feature.span(), member.file),
+                    format!(
+                        "feature `{feature_name}` of `{}` is already enabled by the workspace",
+                        name.get_ref()
+                    ),
+                    Some(toml_span(workspace_name.span(), root.file)),
+                    "the features of the workspace are always enabled for inherited dependencies",
+                );
+            }
+        }
+    }
+}
+
+fn check_lints(cx: &LateContext<'_>, root: &Manifest<'_, '_>, member: &Manifest<'_, '_>) {
+    let Some((workspace_lints, _)) = entry(root.table, "lints") else {
+        return;
+    };
+    match entry(member.table, "lints") {
+        Some((_, lints)) if is_inherited(lints.get_ref()) => {},
+        Some((lints, _)) => span_lint_and_then(
+            cx,
+            MISSING_WORKSPACE_LINTS,
+            toml_span(lints.span(), member.file),
+            "the package declares its own lints instead of inheriting the `[workspace.lints]`",
+            |diag| {
+                diag.span_note(
+                    toml_span(workspace_lints.span(), root.file),
+                    "the workspace declares its lints here",
+                );
+                diag.help("move the lints to the `[workspace.lints]`, and set `lints.workspace = true` instead");
+            },
+        ),
+        None => {
+            let Some((package, _)) = entry(member.table, "package") else {
+                return;
+            };
+            let end = member.src.len();
+            let separator = if member.src.ends_with('\n') { "\n" } else { "\n\n" };
+            span_lint_and_then(
+                cx,
+                MISSING_WORKSPACE_LINTS,
+                toml_span(package.span(), member.file),
+                "the package doesn't inherit the `[workspace.lints]`",
+                |diag| {
+                    diag.span_note(
+                        toml_span(workspace_lints.span(), root.file),
+                        "the workspace declares its lints here",
+                    );
+                    diag.span_suggestion_verbose(
+                        toml_span(end..end, member.file),
+                        "inherit the lints of the workspace",
+                        format!("{separator}[lints]\nworkspace = true\n"),
+                        Applicability::MachineApplicable,
+                    );
+                },
+            );
+        },
+    }
+}
@@ -43,12 +43,14 @@ declare_clippy_lint! {
 impl_lint_pass!(CognitiveComplexity => [COGNITIVE_COMPLEXITY]);
 
 pub struct CognitiveComplexity {
+    conf: &'static Conf,
     limit: LimitStack,
 }
 
 impl CognitiveComplexity {
     pub fn new(conf: &'static Conf) -> Self {
         Self {
+            conf,
             limit: LimitStack::new(conf.cognitive_complexity_threshold),
         }
     }
@@ -56,12 +58,12 @@ impl CognitiveComplexity {
 
 impl CognitiveComplexity {
     fn check<'tcx>(
-        &self,
         cx: &LateContext<'tcx>,
         kind: FnKind<'tcx>,
         decl: &'tcx FnDecl<'_>,
         expr: &'tcx Expr<'_>,
         body_span: Span,
+        limit: u64,
     ) {
         if body_span.from_expansion() {
             return;
@@ -103,7 +105,7 @@ impl CognitiveComplexity {
             cc -= ret_adjust;
         }
 
-        if cc > self.limit.limit() {
+        if cc > limit {
             let fn_span = match kind {
                 FnKind::ItemFn(ident, _, _) | FnKind::Method(ident, _) => ident.span,
                 FnKind::Closure => {
@@ -123,10 +125,7 @@ impl CognitiveComplexity {
                 cx,
                 COGNITIVE_COMPLEXITY,
                 fn_span,
-                format!(
-                    "the function has a cognitive complexity of ({cc}/{})",
-                    self.limit.limit()
-                ),
+                format!("the function has a cognitive complexity of ({cc}/{limit})"),
                 None,
                 "you could split it up into multiple smaller functions",
             );
@@ -157,7 +156,10 @@ impl<'tcx> LateLintPass<'tcx> for CognitiveComplexity {
                 body.value
             };
 
-            self.check(cx, kind, decl, expr, span);
+            let limit = self
+                .limit
+                .limit_or(self.conf.for_item(cx.tcx, def_id).cognitive_complexity_threshold);
+            Self::check(cx, kind, decl, expr, span, limit);
         }
     }
 
@@ -14,6 +14,11 @@
 //! late passes via `skippable_lints`, but the early pass runner has
 //! no such filtering, so a plain forward is equivalent and loses nothing.
 //!
+//! With the `pass-timings` feature each field's calls are also timed (see
+//! [`pass_timings`](crate::pass_timings)). With the `self-profile` feature each
+//! call is recorded as a `clippy_early_pass::<Field>` event when rustc's self
+//! profiler is enabled with `-Zself-profile`.
+//!
 //! [`combined_late_pass`]: crate::combined_late_pass
 
 /// Run one field's `check_*`.
@@ -23,9 +28,17 @@
 /// be ambiguous on the concrete field type.
 #[macro_export]
 macro_rules! run_combined_early_lint_pass_field {
-    ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
-        rustc_lint::EarlyLintPass::$name(&mut $self.$field, $($arg),*);
-    };
+    ($self:ident, $field:ident, $name:ident, ($cx:expr $(, $arg:expr)* $(,)?)) => {{
+        #[cfg(feature = "self-profile")]
+        let _guard = rustc_lint::LintContext::sess($cx)
+            .prof
+            .generic_activity(concat!("clippy_early_pass::", stringify!($field)));
+        #[cfg(feature = "pass-timings")]
+        let start = std::time::Instant::now();
+        rustc_lint::EarlyLintPass::$name(&mut $self.$field, $cx $(, $arg)*);
+        #[cfg(feature = "pass-timings")]
+        $self.timings.record(stringify!($field), start);
+    }};
 }
 
 /// Forward one `check_*` method to every field of the combined pass.
@@ -62,12 +75,16 @@ macro_rules! combined_early_lint_pass {
         #[allow(non_snake_case)]
         pub struct $name {
             $($field: $fty,)*
+            #[cfg(feature = "pass-timings")]
+            timings: $crate::pass_timings::PassTimings,
         }
 
         impl $name {
             pub fn new($($pname: $pty,)*) -> Self {
                 Self {
                     $($field: $ctor,)*
+                    #[cfg(feature = "pass-timings")]
+                    timings: $crate::pass_timings::PassTimings::new("early"),
                 }
             }
         }
@@ -9,6 +9,11 @@
 //! [`Gated`] with a precomputed `active` flag (the same "lint still needs to run"
 //! predicate `rustc_lint::late` uses). Disabled passes are skipped by a branch
 //! rather than dropped from a `Vec`, keeping clippy's allow-by-default fast path.
+//!
+//! With the `pass-timings` feature each field's calls are also timed (see
+//! [`pass_timings`](crate::pass_timings)). With the `self-profile` feature each
+//! call is recorded as a `clippy_late_pass::<Field>` event when rustc's self
+//! profiler is enabled with `-Zself-profile`.
 
 use rustc_lint::{LintPass, LintVec};
 
@@ -35,9 +40,17 @@ impl<P: LintPass> Gated<P> {
 /// nesting level.
 #[macro_export]
 macro_rules! run_combined_late_lint_pass_field {
-    ($self:ident, $field:ident, $name:ident, ($($arg:expr),* $(,)?)) => {
+    ($self:ident, $field:ident, $name:ident, ($cx:expr $(, $arg:expr)* $(,)?)) => {
         if $self.$field.active {
-            rustc_lint::LateLintPass::$name(&mut $self.$field.pass, $($arg),*);
+            #[cfg(feature = "self-profile")]
+            let _guard = rustc_lint::LintContext::sess($cx)
+                .prof
+                .generic_activity(concat!("clippy_late_pass::", stringify!($field)));
+            #[cfg(feature = "pass-timings")]
+            let start = std::time::Instant::now();
+            rustc_lint::LateLintPass::$name(&mut $self.$field.pass, $cx $(, $arg)*);
+            #[cfg(feature = "pass-timings")]
+            $self.timings.record(stringify!($field), start);
         }
     };
 }
@@ -76,12 +89,16 @@ macro_rules! combined_late_lint_pass {
         #[allow(non_snake_case)]
         pub struct $name<'tcx> {
             $($field: $crate::combined_late_pass::Gated<$fty>,)*
+            #[cfg(feature = "pass-timings")]
+            timings: $crate::pass_timings::PassTimings,
         }
 
         impl<'tcx> $name<'tcx> {
             pub fn new<F: Fn(&rustc_lint::LintVec) -> bool>($($pname: $pty,)* is_active: &F) -> Self {
                 Self {
                     $($field: $crate::combined_late_pass::Gated::new(is_active, $ctor),)*
+                    #[cfg(feature = "pass-timings")]
+                    timings: $crate::pass_timings::PassTimings::new("late"),
                 }
             }
         }
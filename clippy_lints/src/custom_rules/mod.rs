@@ -0,0 +1,348 @@
+mod pattern;
+
+use clippy_config::Conf;
+use clippy_utils::diagnostics::{span_lint_and_then, span_lint_hir_at_level_and_then};
+use clippy_utils::matcher::ExprPat;
+use clippy_utils::source::snippet_with_context;
+use pattern::{TypeConstraint, parse_pattern, replace_vars};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_errors::{Applicability, Diag};
+use rustc_hir::Expr;
+use rustc_lint::{LateContext, LateLintPass, Level};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
+use rustc_span::{BytePos, DUMMY_SP, Pos as _, SourceFile, Span, Symbol, SyntaxContext};
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+use toml::de::{DeTable, DeValue};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for expressions matching the rules declared in the `clippy-rules` directory next to
+    /// the `clippy.toml`. Each `.toml` file in it holds any number of rules:
+    ///
+    /// ```toml
+    /// [[rule]]
+    /// name = "lock_unwrap"
+    /// pattern = "$mutex.lock().unwrap()"
+    /// types = { mutex = "std::sync::Mutex" }
+    /// message = "`lock().unwrap()` on `$mutex`"
+    /// help = "use the `lock_or_poison` helper"
+    /// replacement = "lock_or_poison(&$mutex)"
+    /// ```
+    ///
+    /// The pattern is a Rust expression, in which `$name` matches any expression and `$_` does so
+    /// without binding it. Paths have to start with the crate name, e.g.
+    /// `std::vec::Vec::with_capacity(0)`, and match whatever refers to the same item. Calls,
+    /// method calls, field accesses, operators, references, indexing, literals, tuples and arrays
+    /// are supported.
+    ///
+    /// The optional `types` table restricts the type of metavariables, ignoring references, to a
+    /// struct, enum or union, or to a primitive type. The `$name` of bound metavariables are
+    /// replaced by their source in the `message`, `help` and `replacement`.
+    ///
+    /// The optional `level`, one of `allow`, `warn` or `deny`, sets the level of the rule instead
+    /// of the level of this lint, e.g. `level = "allow"` turns the rule off. Allowing or expecting
+    /// this lint still applies to every rule.
+    ///
+    /// ### Why is this bad?
+    /// The rules encode the conventions of the project.
+    ///
+    /// ### Example
+    /// With the rule above:
+    /// ```rust,ignore
+    /// let guard = state.lock().unwrap();
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// let guard = lock_or_poison(&state);
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub CUSTOM_RULES,
+    style,
+    "expressions matching the rules declared in the `clippy-rules` directory"
+}
+
+impl_lint_pass!(CustomRules => [CUSTOM_RULES]);
+
+/// The directory holding the rules, next to the config file
+const RULES_DIR: &str = "clippy-rules";
+
+const FIELDS: &str = "`name`, `pattern`, `types`, `message`, `help`, `replacement` or `level`";
+
+struct Rule {
+    name: String,
+    /// The file declaring the rule, relative to the directory of the config file
+    file: String,
+    pattern: ExprPat,
+    types: Vec<(Symbol, TypeConstraint)>,
+    message: String,
+    help: Option<String>,
+    replacement: Option<String>,
+    /// The level of the rule, if it doesn't have the level of the lint
+    level: Option<Level>,
+}
+
+pub struct CustomRules {
+    rules: Vec<Rule>,
+}
+
+impl CustomRules {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let mut rules = Vec::new();
+        if let Some(dir) = conf.dir()
+            && let Ok(entries) = fs::read_dir(dir.join(RULES_DIR))
+        {
+            let mut paths: Vec<_> = entries
+                .filter_map(|entry| Some(entry.ok()?.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+                .collect();
+            paths.sort();
+            for path in paths {
+                load_rules(tcx, &path, &mut rules);
+            }
+        }
+        Self { rules }
+    }
+}
+
+struct RuleFile<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    file: &'a SourceFile,
+    name: String,
+}
+
+impl RuleFile<'_, '_> {
+    fn span(&self, range: Range<usize>) -> Span {
+        Span::new(
+            self.file.start_pos + BytePos::from_usize(range.start),
+            self.file.start_pos + BytePos::from_usize(range.end),
+            SyntaxContext::root(),
+            None,
+        )
+    }
+
+    fn err(&self, range: Range<usize>, msg: impl Into<String>) {
+        self.tcx.sess.dcx().span_err(self.span(range), msg.into());
+    }
+
+    fn parse_rule(&self, table: &DeTable<'_>, table_range: Range<usize>) -> Option<Rule> {
+        let mut strings: [Option<(&str, Range<usize>)>; 6] = Default::default();
+        let mut types = Vec::new();
+        let mut is_valid = true;
+        for (key, value) in table {
+            let index = match &**key.get_ref() {
+                "name" => 0,
+                "pattern" => 1,
+                "message" => 2,
+                "help" => 3,
+                "replacement" => 4,
+                "level" => 5,
+                "types" => {
+                    if let DeValue::Table(types_table) = value.get_ref() {
+                        types.extend(types_table.iter());
+                    } else {
+                        self.err(value.span(), "expected a table of metavariable names to types");
+                        is_valid = false;
+                    }
+                    continue;
+                },
+                unknown => {
+                    self.err(
+                        key.span(),
+                        format!("unknown field `{unknown}`, expected one of {FIELDS}"),
+                    );
+                    is_valid = false;
+                    continue;
+                },
+            };
+            if let Some(s) = value.get_ref().as_str() {
+                strings[index] = Some((s, value.span()));
+            } else {
+                self.err(value.span(), "expected a string");
+                is_valid = false;
+            }
+        }
+
+        let [name, pattern, message, help, replacement, level] = strings;
+        let level = level.map(|(level, range)| self.parse_level(level, range));
+        for (field, field_name) in [(&name, "name"), (&pattern, "pattern"), (&message, "message")] {
+            if field.is_none() {
+                self.err(table_range.clone(), format!("missing field `{field_name}`"));
+            }
+        }
+        let (Some((name, _)), Some((pattern, pattern_range)), Some(message)) = (name, pattern, message) else {
+            return None;
+        };
+
+        let pattern = match parse_pattern(self.tcx, pattern) {
+            Ok(pattern) => pattern,
+            Err(msg) => {
+                self.err(pattern_range, msg);
+                return None;
+            },
+        };
+        let mut vars = FxHashSet::default();
+        pattern.for_each_binding(&mut |name| {
+            vars.insert(name);
+        });
+
+        let types = types
+            .into_iter()
+            .filter_map(|(var, ty)| {
+                let var_name = Symbol::intern(var.get_ref());
+                if !vars.contains(&var_name) {
+                    self.err(var.span(), format!("`${var_name}` isn't bound by the pattern"));
+                    return None;
+                }
+                let Some(path) = ty.get_ref().as_str() else {
+                    self.err(ty.span(), "expected a path to a type");
+                    return None;
+                };
+                match TypeConstraint::parse(self.tcx, path) {
+                    Ok(constraint) => Some((var_name, constraint)),
+                    Err(msg) => {
+                        self.err(ty.span(), msg);
+                        None
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (template, range) in [Some(message.clone()), help.clone(), replacement.clone()]
+            .into_iter()
+            .flatten()
+        {
+            replace_vars(template, |var| {
+                if !vars.contains(&Symbol::intern(var)) {
+                    self.err(range.clone(), format!("`${var}` isn't bound by the pattern"));
+                    is_valid = false;
+                }
+                String::new()
+            });
+        }
+
+        (is_valid && level.is_none_or(|level| level.is_some())).then(|| Rule {
+            name: name.to_owned(),
+            file: self.name.clone(),
+            pattern,
+            types,
+            message: message.0.to_owned(),
+            help: help.map(|(help, _)| help.to_owned()),
+            replacement: replacement.map(|(replacement, _)| replacement.to_owned()),
+            level: level.flatten(),
+        })
+    }
+
+    fn parse_level(&self, level: &str, range: Range<usize>) -> Option<Level> {
+        match level {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => {
+                self.err(range, "expected `allow`, `warn` or `deny`");
+                None
+            },
+        }
+    }
+}
+
+fn load_rules(tcx: TyCtxt<'_>, path: &Path, rules: &mut Vec<Rule>) {
+    let file = match tcx.sess.source_map().load_file(path) {
+        Ok(file) => file,
+        Err(e) => {
+            tcx.sess.dcx().err(format!("error reading `{}`: {e}", path.display()));
+            return;
+        },
+    };
+    let Some(src) = file.src.as_deref() else {
+        return;
+    };
+    let rule_file = RuleFile {
+        tcx,
+        file: &file,
+        name: format!("{RULES_DIR}/{}", path.file_name().unwrap_or_default().to_string_lossy()),
+    };
+
+    let (table, errors) = DeTable::parse_recoverable(src);
+    for e in errors {
+        let span = e.span().map_or(DUMMY_SP, |range| rule_file.span(range));
+        tcx.sess.dcx().span_err(span, e.message().to_owned());
+    }
+    for (key, value) in table.get_ref() {
+        if key.get_ref() != "rule" {
+            rule_file.err(key.span(), format!("unknown key `{}`, expected `rule`", key.get_ref()));
+            continue;
+        }
+        let Some(array) = value.get_ref().as_array() else {
+            rule_file.err(value.span(), "expected an array of tables, e.g. `[[rule]]`");
+            continue;
+        };
+        for value in &**array {
+            if let Some(table) = value.get_ref().as_table() {
+                rules.extend(
+                    rule_file
+                        .parse_rule(table, value.span())
+                        .filter(|rule| rule.level != Some(Level::Allow)),
+                );
+            } else {
+                rule_file.err(value.span(), "expected a table");
+            }
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for CustomRules {
+    fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) {
+        if self.rules.is_empty() || expr.span.from_expansion() {
+            return;
+        }
+        for rule in &self.rules {
+            if let Some(bindings) = rule.pattern.matches(cx, expr)
+                && rule.types.iter().all(|&(var, ref constraint)| {
+                    bindings
+                        .get_sym(var)
+                        .is_some_and(|bound| constraint.matches(cx.typeck_results().expr_ty(bound)))
+                })
+            {
+                let mut app = Applicability::MaybeIncorrect;
+                let mut render = |template: &str| {
+                    replace_vars(template, |var| {
+                        let bound = &bindings[var];
+                        snippet_with_context(cx, bound.span, expr.span.ctxt(), "..", &mut app)
+                            .0
+                            .into_owned()
+                    })
+                };
+                let message = render(&rule.message);
+                let help = rule.help.as_deref().map(&mut render);
+                let replacement = rule.replacement.as_deref().map(&mut render);
+                let decorate = |diag: &mut Diag<'_, ()>| {
+                    match (help, replacement) {
+                        (help, Some(replacement)) => {
+                            diag.span_suggestion(expr.span, help.unwrap_or_else(|| "try".to_owned()), replacement, app);
+                        },
+                        (Some(help), None) => {
+                            diag.help(help);
+                        },
+                        (None, None) => {},
+                    }
+                    diag.note(format!("declared by the rule `{}` in `{}`", rule.name, rule.file));
+                };
+                match rule.level {
+                    Some(level) => span_lint_hir_at_level_and_then(
+                        cx,
+                        CUSTOM_RULES,
+                        level,
+                        expr.hir_id,
+                        expr.span,
+                        message,
+                        decorate,
+                    ),
+                    None => span_lint_and_then(cx, CUSTOM_RULES, expr.span, message, decorate),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use clippy_utils::matcher::{self, ExprPat};
+use clippy_utils::paths::{PathNS, lookup_path_str};
+use rustc_ast::ast::{self, BorrowKind, LitKind};
+use rustc_ast::token;
+use rustc_errors::DiagCtxt;
+use rustc_errors::emitter::SilentEmitter;
+use rustc_hir::PrimTy;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_parse::lexer::StripTokens;
+use rustc_parse::new_parser_from_source_str;
+use rustc_session::parse::ParseSess;
+use rustc_span::source_map::{FilePathMapping, SourceMap};
+use rustc_span::{FileName, Symbol};
+use std::sync::Arc;
+
+/// Metavariables are renamed to identifiers starting with this, so the pattern parses as Rust.
+const VAR_PREFIX: &str = "__clippy_rule_var_";
+
+/// Parses an expression pattern from a Rust expression in which `$name` is a metavariable.
+pub(super) fn parse_pattern(tcx: TyCtxt<'_>, src: &str) -> Result<ExprPat, String> {
+    // The errors of the parser are replaced by a single one pointing into the rule file
+    #[expect(clippy::arc_with_non_send_sync, reason = "the parser requires an `Arc`")]
+    let source_map = Arc::new(SourceMap::new(FilePathMapping::empty()));
+    let psess = ParseSess::with_dcx(DiagCtxt::new(Box::new(SilentEmitter)), source_map);
+    let name = FileName::Custom("clippy-rules pattern".to_owned());
+    let src = replace_vars(src, |name| format!("{VAR_PREFIX}{name}"));
+    let mut parser = match new_parser_from_source_str(&psess, name, src, StripTokens::Nothing) {
+        Ok(parser) => parser,
+        Err(diags) => {
+            for diag in diags {
+                diag.cancel();
+            }
+            return Err("expected a Rust expression".to_owned());
+        },
+    };
+    match parser.parse_expr() {
+        Ok(expr) if parser.token.kind == token::Eof => lower_expr(tcx, &expr),
+        Ok(_) => Err("expected a single Rust expression".to_owned()),
+        Err(diag) => {
+            diag.cancel();
+            Err("expected a Rust expression".to_owned())
+        },
+    }
+}
+
+fn lower_expr(tcx: TyCtxt<'_>, expr: &ast::Expr) -> Result<ExprPat, String> {
+    let lower = |expr| lower_expr(tcx, expr);
+    let lower_all =
+        |exprs: &[Box<ast::Expr>]| -> Result<Vec<_>, _> { exprs.iter().map(|e| lower_expr(tcx, e)).collect() };
+    Ok(match &expr.kind {
+        ast::ExprKind::Paren(inner) => return lower_expr(tcx, inner),
+        ast::ExprKind::Path(None, path) => {
+            if let [segment] = &*path.segments
+                && let Some(name) = segment.ident.as_str().strip_prefix(VAR_PREFIX)
+            {
+                if name == "_" {
+                    matcher::any()
+                } else {
+                    matcher::bind(name)
+                }
+            } else {
+                let path = path
+                    .segments
+                    .iter()
+                    .map(|segment| segment.ident.as_str())
+                    .collect::<Vec<_>>()
+                    .join("::");
+                let def_ids = lookup_path_str(tcx, PathNS::Value, &path);
+                if def_ids.is_empty() {
+                    return Err(format!("cannot find `{path}`, paths have to start with the crate name"));
+                }
+                matcher::def_ids(def_ids)
+            }
+        },
+        ast::ExprKind::Lit(lit) => {
+            matcher::lit(LitKind::from_token_lit(*lit).map_err(|_| "invalid literal".to_owned())?)
+        },
+        ast::ExprKind::Call(callee, args) => matcher::call(lower(callee)?, lower_all(args)?),
+        ast::ExprKind::MethodCall(call) => {
+            matcher::method_call(call.seg.ident.as_str(), lower(&call.receiver)?, lower_all(&call.args)?)
+        },
+        ast::ExprKind::Field(base, field) => matcher::field(lower(base)?, field.as_str()),
+        ast::ExprKind::Unary(op, inner) => matcher::unary(*op, lower(inner)?),
+        ast::ExprKind::Binary(op, lhs, rhs) => matcher::binary(op.node, lower(lhs)?, lower(rhs)?),
+        ast::ExprKind::AddrOf(BorrowKind::Ref, mutbl, inner) => matcher::addr_of(*mutbl, lower(inner)?),
+        ast::ExprKind::Index(base, index, _) => matcher::index(lower(base)?, lower(index)?),
+        ast::ExprKind::Tup(exprs) => matcher::tup(lower_all(exprs)?),
+        ast::ExprKind::Array(exprs) => matcher::array(lower_all(exprs)?),
+        _ => return Err("unsupported expression in pattern".to_owned()),
+    })
+}
+
+/// Replaces each `$name` in `src` by the result of `f(name)`.
+pub(super) fn replace_vars(src: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut result = String::with_capacity(src.len());
+    let mut rest = src;
+    while let Some(pos) = rest.find('$') {
+        result.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let len = after
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(after.len());
+        if len == 0 {
+            result.push('$');
+        } else {
+            result.push_str(&f(&after[..len]));
+        }
+        rest = &after[len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// A constraint on the type of a metavariable, ignoring references.
+#[derive(Debug)]
+pub(super) enum TypeConstraint {
+    /// A path to a struct, enum or union, e.g. `std::sync::Mutex`
+    Adt(Vec<DefId>),
+    Prim(PrimTy),
+}
+
+impl TypeConstraint {
+    pub(super) fn parse(tcx: TyCtxt<'_>, path: &str) -> Result<Self, String> {
+        if let Some(prim) = PrimTy::from_name(Symbol::intern(path)) {
+            return Ok(Self::Prim(prim));
+        }
+        let def_ids = lookup_path_str(tcx, PathNS::Type, path);
+        if def_ids.is_empty() {
+            Err(format!(
+                "cannot find the type `{path}`, paths have to start with the crate name"
+            ))
+        } else {
+            Ok(Self::Adt(def_ids))
+        }
+    }
+
+    pub(super) fn matches(&self, ty: Ty<'_>) -> bool {
+        let ty = ty.peel_refs();
+        match (self, ty.kind()) {
+            (Self::Adt(def_ids), ty::Adt(adt, _)) => def_ids.contains(&adt.did()),
+            (Self::Prim(prim), _) => ty.is_primitive_ty() && ty.to_string() == prim.name_str(),
+            _ => false,
+        }
+    }
+}
@@ -37,6 +37,7 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::bit_width::MANUAL_BIT_WIDTH_INFO,
     crate::bit_width::MISMATCHED_BIT_WIDTH_TYPE_INFO,
     crate::block_scrutinee::BLOCK_SCRUTINEE_INFO,
+    crate::blocking_in_async::BLOCKING_IN_ASYNC_INFO,
     crate::blocks_in_conditions::BLOCKS_IN_CONDITIONS_INFO,
     crate::bool_assert_comparison::BOOL_ASSERT_COMPARISON_INFO,
     crate::bool_comparison::BOOL_COMPARISON_INFO,
@@ -47,10 +48,16 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::box_default::BOX_DEFAULT_INFO,
     crate::byte_char_slices::BYTE_CHAR_SLICES_INFO,
     crate::cargo::CARGO_COMMON_METADATA_INFO,
+    crate::cargo::INCOMPATIBLE_DEPENDENCY_MSRV_INFO,
+    crate::cargo::INCONSISTENT_WORKSPACE_DEPENDENCY_FEATURES_INFO,
     crate::cargo::LINT_GROUPS_PRIORITY_INFO,
+    crate::cargo::MISSING_WORKSPACE_LINTS_INFO,
     crate::cargo::MULTIPLE_CRATE_VERSIONS_INFO,
     crate::cargo::NEGATIVE_FEATURE_NAMES_INFO,
+    crate::cargo::NON_INHERITED_WORKSPACE_DEPENDENCIES_INFO,
     crate::cargo::REDUNDANT_FEATURE_NAMES_INFO,
+    crate::cargo::UNUSED_CARGO_DEPENDENCIES_INFO,
+    crate::cargo::UNUSED_CARGO_FEATURES_INFO,
     crate::cargo::WILDCARD_DEPENDENCIES_INFO,
     crate::casts::AS_POINTER_UNDERSCORE_INFO,
     crate::casts::AS_PTR_CAST_MUT_INFO,
@@ -92,6 +99,7 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::copy_iterator::COPY_ITERATOR_INFO,
     crate::crate_in_macro_def::CRATE_IN_MACRO_DEF_INFO,
     crate::create_dir::CREATE_DIR_INFO,
+    crate::custom_rules::CUSTOM_RULES_INFO,
     crate::dbg_macro::DBG_MACRO_INFO,
     crate::default::DEFAULT_TRAIT_ACCESS_INFO,
     crate::default::FIELD_REASSIGN_WITH_DEFAULT_INFO,
@@ -111,10 +119,13 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::derive::EXPL_IMPL_CLONE_ON_COPY_INFO,
     crate::derive::UNSAFE_DERIVE_DESERIALIZE_INFO,
     crate::disallowed_fields::DISALLOWED_FIELDS_INFO,
+    crate::disallowed_layer_dependencies::DISALLOWED_LAYER_DEPENDENCIES_INFO,
     crate::disallowed_macros::DISALLOWED_MACROS_INFO,
     crate::disallowed_methods::DISALLOWED_METHODS_INFO,
     crate::disallowed_names::DISALLOWED_NAMES_INFO,
     crate::disallowed_script_idents::DISALLOWED_SCRIPT_IDENTS_INFO,
+    crate::disallowed_traits::DISALLOWED_IMPLS_INFO,
+    crate::disallowed_traits::DISALLOWED_TRAITS_INFO,
     crate::disallowed_types::DISALLOWED_TYPES_INFO,
     crate::doc::DOC_BROKEN_LINK_INFO,
     crate::doc::DOC_COMMENT_DOUBLE_SPACE_LINEBREAKS_INFO,
@@ -635,6 +646,7 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::operators::VERBOSE_BIT_MASK_INFO,
     crate::option_env_unwrap::OPTION_ENV_UNWRAP_INFO,
     crate::option_if_let_else::OPTION_IF_LET_ELSE_INFO,
+    crate::panic_in_no_panic_fn::PANIC_IN_NO_PANIC_FN_INFO,
     crate::panic_in_result_fn::PANIC_IN_RESULT_FN_INFO,
     crate::panic_unimplemented::PANIC_INFO,
     crate::panic_unimplemented::TODO_INFO,
@@ -684,6 +696,7 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::ref_patterns::REF_PATTERNS_INFO,
     crate::reference::DEREF_ADDROF_INFO,
     crate::regex::INVALID_REGEX_INFO,
+    crate::regex::REGEX_CREATION_IN_FUNCTIONS_INFO,
     crate::regex::REGEX_CREATION_IN_LOOPS_INFO,
     crate::regex::TRIVIAL_REGEX_INFO,
     crate::repeat_vec_with_capacity::REPEAT_VEC_WITH_CAPACITY_INFO,
@@ -702,6 +715,11 @@ pub static LINTS: &[&::declare_clippy_lint::LintInfo] = &[
     crate::semicolon_block::SEMICOLON_OUTSIDE_BLOCK_INFO,
     crate::semicolon_if_nothing_returned::SEMICOLON_IF_NOTHING_RETURNED_INFO,
     crate::serde_api::SERDE_API_MISUSE_INFO,
+    crate::serde_attrs::SERDE_CONFLICTING_RENAME_INFO,
+    crate::serde_attrs::SERDE_DEFAULT_WITHOUT_DEFAULT_INFO,
+    crate::serde_attrs::SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN_INFO,
+    crate::serde_attrs::SERDE_MISMATCHED_SKIP_SERIALIZING_IF_INFO,
+    crate::serde_attrs::SERDE_SHADOWED_UNTAGGED_VARIANT_INFO,
     crate::set_contains_or_insert::SET_CONTAINS_OR_INSERT_INFO,
     crate::shadow::SHADOW_REUSE_INFO,
     crate::shadow::SHADOW_SAME_INFO,
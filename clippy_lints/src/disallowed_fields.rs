@@ -94,7 +94,9 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedFields {
             },
             _ => return,
         };
-        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id) {
+        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id)
+            && disallowed_path.applies_in(cx.tcx, expr.hir_id)
+        {
             span_lint_and_then(
                 cx,
                 DISALLOWED_FIELDS,
@@ -120,6 +122,7 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedFields {
                             None
                         }
                     }) && let Some(&(path, disallowed_path)) = self.disallowed.get(&def_id)
+                        && disallowed_path.applies_in(cx.tcx, pat.hir_id)
                     {
                         span_lint_and_then(
                             cx,
@@ -143,6 +146,7 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedFields {
                             None
                         }
                     }) && let Some(&(path, disallowed_path)) = self.disallowed.get(&def_id)
+                        && disallowed_path.applies_in(cx.tcx, pat.hir_id)
                     {
                         span_lint_and_then(
                             cx,
@@ -0,0 +1,136 @@
+use clippy_config::Conf;
+use clippy_config::types::DisallowedPathWithoutReplacement;
+use clippy_utils::diagnostics::span_lint_and_then;
+use clippy_utils::paths::PathNS;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, DefIdMap, DefIdSet};
+use rustc_hir::{HirId, Item, ItemKind, Node, Path};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
+use std::iter;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for uses of items which cross between the layers of an architecture declared with
+    /// `allowed-dependencies` in clippy.toml. This includes `use` items, path expressions, and
+    /// type and trait references.
+    ///
+    /// Code inside a layer may use anything from within the same layer or from outside of all
+    /// layers, but only the listed items from other layers.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// layers are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// A layered architecture relies on the lower layers not knowing about the higher ones, e.g.
+    /// the domain logic shouldn't depend on how it's stored or exposed.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// allowed-dependencies = [
+    ///     # The domain may not use any of the other layers.
+    ///     { path = "app::domain", reason = "the domain must not depend on other layers" },
+    ///     # The API may only use the ports of the domain.
+    ///     { path = "app::api", allowed = ["app::domain::ports"] },
+    ///     # A layer can also be a crate.
+    ///     { path = "app_infra", allowed = ["app::domain"] },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// mod domain {
+    ///     use app_infra::PgPool;
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// mod domain {
+    ///     use crate::domain::ports::Repository;
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub DISALLOWED_LAYER_DEPENDENCIES,
+    style,
+    "use of items from a layer of the architecture that isn't allowed"
+}
+
+impl_lint_pass!(DisallowedLayerDependencies => [DISALLOWED_LAYER_DEPENDENCIES]);
+
+pub struct DisallowedLayerDependencies {
+    /// The index of the layer each module or crate root makes up
+    layers: DefIdMap<usize>,
+    /// Each layer along with the items from other layers it may use
+    rules: Vec<(&'static DisallowedPathWithoutReplacement, DefIdSet)>,
+}
+
+impl DisallowedLayerDependencies {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let mut layers = DefIdMap::default();
+        let rules = conf
+            .allowed_dependencies
+            .iter()
+            .enumerate()
+            .map(|(i, dependencies)| {
+                for def_id in dependencies
+                    .layer
+                    .resolve(tcx, PathNS::Type, |kind| kind == DefKind::Mod, "module")
+                {
+                    layers.insert(def_id, i);
+                }
+                let allowed = dependencies
+                    .allowed
+                    .iter()
+                    .flat_map(|path| path.resolve(tcx, PathNS::Arbitrary, |_| true, "item"))
+                    .collect();
+                (&dependencies.layer, allowed)
+            })
+            .collect();
+        Self { layers, rules }
+    }
+
+    /// Finds the innermost layer containing `def_id`.
+    fn layer_of(&self, tcx: TyCtxt<'_>, def_id: DefId) -> Option<usize> {
+        iter::successors(Some(def_id), |&id| tcx.opt_parent(id)).find_map(|id| self.layers.get(&id).copied())
+    }
+
+    fn is_allowed(&self, tcx: TyCtxt<'_>, layer: usize, def_id: DefId) -> bool {
+        let allowed = &self.rules[layer].1;
+        iter::successors(Some(def_id), |&id| tcx.opt_parent(id)).any(|id| allowed.contains(&id))
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedLayerDependencies {
+    fn check_path(&mut self, cx: &LateContext<'tcx>, path: &Path<'tcx>, hir_id: HirId) {
+        if self.layers.is_empty() || path.span.from_expansion() {
+            return;
+        }
+
+        if let Res::Def(kind, def_id) = path.res
+            // The type namespace of a `use` already covers the constructor of a unit or tuple struct
+            && !(matches!(kind, DefKind::Ctor(..))
+                && matches!(cx.tcx.hir_node(hir_id), Node::Item(Item { kind: ItemKind::Use(..), .. })))
+            && let Some(to) = self.layer_of(cx.tcx, def_id)
+            && let Some(from) = self.layer_of(cx.tcx, cx.tcx.parent_module(hir_id).to_def_id())
+            && from != to
+            && !self.is_allowed(cx.tcx, from, def_id)
+        {
+            let (from_layer, _) = self.rules[from];
+            let (to_layer, _) = self.rules[to];
+            span_lint_and_then(
+                cx,
+                DISALLOWED_LAYER_DEPENDENCIES,
+                path.span,
+                format!(
+                    "layer `{}` may not use `{}` from layer `{}`",
+                    from_layer.path(),
+                    cx.tcx.def_path_str(def_id),
+                    to_layer.path()
+                ),
+                from_layer.diag_amendment(path.span),
+            );
+        }
+    }
+}
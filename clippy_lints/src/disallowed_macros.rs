@@ -42,7 +42,10 @@ declare_clippy_lint! {
     ///     { path = "serde::Serialize", reason = "no serializing" },
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
-    ///     { path = "std::invalid_macro", reason = "use alternative instead", allow-invalid = true }
+    ///     { path = "std::invalid_macro", reason = "use alternative instead", allow-invalid = true },
+    ///     # Can be restricted to `async`, `const`, `unsafe` or `test` code, or to a module with
+    ///     # `module:<path>`.
+    ///     { path = "std::dbg", contexts = ["module:my_crate::hot_path"] },
     /// ]
     /// ```
     /// ```no_run
@@ -105,7 +108,14 @@ impl DisallowedMacros {
                 return;
             }
 
-            if let Some(&(path, disallowed_path)) = self.disallowed.get(&mac.def_id) {
+            if let Some(&(path, disallowed_path)) = self.disallowed.get(&mac.def_id)
+                && disallowed_path.applies_in(
+                    cx.tcx,
+                    derive_src.map_or(cx.last_node_with_lint_attrs, |owner| {
+                        cx.tcx.local_def_id_to_hir_id(owner.def_id)
+                    }),
+                )
+            {
                 let msg = format!("use of a disallowed macro `{path}`");
                 let add_note = disallowed_path.diag_amendment(mac.span);
                 if matches!(mac.kind, MacroKind::Derive)
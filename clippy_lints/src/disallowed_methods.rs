@@ -37,6 +37,9 @@ declare_clippy_lint! {
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
     ///     { path = "std::fs::InvalidPath", reason = "use alternative instead", allow-invalid = true },
+    ///     # Can be restricted to `async`, `const`, `unsafe` or `test` code, or to a module with
+    ///     # `module:<path>`.
+    ///     { path = "std::thread::sleep", reason = "blocks the executor", contexts = ["async"] },
     /// ]
     /// ```
     ///
@@ -101,7 +104,9 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedMethods {
             },
             _ => return,
         };
-        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id) {
+        if let Some(&(path, disallowed_path)) = self.disallowed.get(&id)
+            && disallowed_path.applies_in(cx.tcx, expr.hir_id)
+        {
             span_lint_and_then(
                 cx,
                 DISALLOWED_METHODS,
@@ -0,0 +1,270 @@
+use clippy_config::Conf;
+use clippy_config::types::{DisallowedImpl, DisallowedPath, DisallowedPathWithoutReplacement, create_disallowed_map};
+use clippy_utils::diagnostics::{span_lint_and_then, span_lint_hir_and_then};
+use clippy_utils::macros::macro_backtrace;
+use clippy_utils::paths::PathNS;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, DefIdMap};
+use rustc_hir::{Impl, Item, ItemKind, PolyTraitRef, PrimTy, QPath, TyKind};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::TyCtxt;
+use rustc_session::impl_lint_pass;
+use rustc_span::{ExpnId, MacroKind};
+use std::iter;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the implementations of traits for types configured in clippy.toml, including
+    /// derived implementations.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// implementations are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some traits shouldn't be implemented for particular types, e.g. `Default` for a type
+    /// without a meaningful default value.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-impls = [
+    ///     { path = "std::default::Default", for = "app::UserId", reason = "user IDs are assigned by the database" },
+    ///     # Can also add a `replacement` trait that will be offered as a suggestion.
+    ///     { path = "std::fmt::Display", for = "app::Password", replacement = "std::fmt::Debug" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// #[derive(Default)]
+    /// struct UserId(u64);
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// struct UserId(u64);
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub DISALLOWED_IMPLS,
+    style,
+    "implementation of a trait for a type which is disallowed"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Denies the configured traits in clippy.toml. This covers implementations of the trait,
+    /// including derived ones, generic bounds, and `dyn Trait` and `impl Trait` types.
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if
+    /// traits are defined in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some traits are undesirable in certain contexts, e.g. `Deref` on types which aren't smart
+    /// pointers, or `serde::Deserialize` on types which have to uphold invariants.
+    ///
+    /// ### Example
+    /// An example clippy.toml configuration:
+    /// ```toml
+    /// # clippy.toml
+    /// disallowed-traits = [
+    ///     # Can use a string as the path of the disallowed trait.
+    ///     "std::ops::Deref",
+    ///     # When using an inline table, can add a `reason` for why the trait
+    ///     # is disallowed.
+    ///     { path = "std::any::Any", reason = "use an enum instead" },
+    ///     # Can also add a `replacement` that will be offered as a suggestion.
+    ///     { path = "std::fmt::Display", replacement = "std::fmt::Debug" },
+    /// ]
+    /// ```
+    ///
+    /// ```rust,ignore
+    /// struct Meters(f64);
+    ///
+    /// impl std::ops::Deref for Meters {
+    ///     type Target = f64;
+    ///
+    ///     fn deref(&self) -> &f64 {
+    ///         &self.0
+    ///     }
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// struct Meters(f64);
+    ///
+    /// impl Meters {
+    ///     fn get(&self) -> f64 {
+    ///         self.0
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub DISALLOWED_TRAITS,
+    style,
+    "use of a disallowed trait"
+}
+
+impl_lint_pass!(DisallowedTraits => [DISALLOWED_IMPLS, DISALLOWED_TRAITS]);
+
+/// A disallowed implementation resolved to the definitions it refers to
+struct ImplMatcher {
+    traits: Vec<DefId>,
+    self_tys: DefIdMap<(&'static str, &'static DisallowedPathWithoutReplacement)>,
+    prim_tys: FxHashMap<PrimTy, (&'static str, &'static DisallowedPathWithoutReplacement)>,
+    disallowed_impl: &'static DisallowedImpl,
+}
+
+impl ImplMatcher {
+    fn matches(&self, trait_id: DefId, self_ty: Res) -> bool {
+        self.traits.contains(&trait_id)
+            && match self_ty {
+                Res::Def(_, def_id) => self.self_tys.contains_key(&def_id),
+                Res::PrimTy(prim) => self.prim_tys.contains_key(&prim),
+                _ => false,
+            }
+    }
+}
+
+pub struct DisallowedTraits {
+    traits: DefIdMap<(&'static str, &'static DisallowedPath)>,
+    impls: Vec<ImplMatcher>,
+    /// The derives already reported for each trait. Derives can expand to more than one
+    /// implementation of the trait, e.g. for helper types.
+    seen_derives: FxHashSet<(ExpnId, DefId)>,
+}
+
+impl DisallowedTraits {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let (traits, _) = create_disallowed_map(
+            tcx,
+            &conf.disallowed_traits,
+            PathNS::Type,
+            |def_kind| def_kind == DefKind::Trait,
+            "trait",
+            false,
+        );
+        let impls = conf
+            .disallowed_impls
+            .iter()
+            .map(|disallowed_impl| {
+                let (self_tys, prim_tys) = create_disallowed_map(
+                    tcx,
+                    iter::once(&disallowed_impl.self_ty),
+                    PathNS::Type,
+                    crate::disallowed_types::def_kind_predicate,
+                    "type",
+                    true,
+                );
+                ImplMatcher {
+                    traits: disallowed_impl.trait_path.resolve(
+                        tcx,
+                        PathNS::Type,
+                        |def_kind| def_kind == DefKind::Trait,
+                        "trait",
+                    ),
+                    self_tys,
+                    prim_tys,
+                    disallowed_impl,
+                }
+            })
+            .collect();
+        Self {
+            traits,
+            impls,
+            seen_derives: FxHashSet::default(),
+        }
+    }
+
+    fn check_impl(&mut self, cx: &LateContext<'_>, item: &Item<'_>, imp: &Impl<'_>) {
+        let Some(of_trait) = imp.of_trait else {
+            return;
+        };
+        let Some(trait_id) = of_trait.trait_ref.trait_def_id() else {
+            return;
+        };
+        let self_ty = match imp.self_ty.kind {
+            TyKind::Path(QPath::Resolved(None, path)) => path.res,
+            _ => Res::Err,
+        };
+
+        // Derived implementations are reported at the derive and can be allowed on the type
+        let (hir_id, span, derive) =
+            if let Some(derive) = macro_backtrace(item.span).find(|mac| mac.kind == MacroKind::Derive) {
+                let hir_id = match self_ty {
+                    Res::Def(_, def_id) if let Some(local_id) = def_id.as_local() => {
+                        cx.tcx.local_def_id_to_hir_id(local_id)
+                    },
+                    _ => item.hir_id(),
+                };
+                (hir_id, derive.span, Some(derive.expn))
+            } else if item.span.from_expansion() {
+                return;
+            } else {
+                (item.hir_id(), of_trait.trait_ref.path.span, None)
+            };
+
+        let disallowed_trait = self
+            .traits
+            .get(&trait_id)
+            .filter(|(_, disallowed_path)| disallowed_path.applies_in(cx.tcx, hir_id));
+        let disallowed_impl = self.impls.iter().find(|matcher| matcher.matches(trait_id, self_ty));
+        if (disallowed_trait.is_none() && disallowed_impl.is_none())
+            || derive.is_some_and(|expn| !self.seen_derives.insert((expn, trait_id)))
+        {
+            return;
+        }
+
+        if let Some(&(path, disallowed_path)) = disallowed_trait {
+            span_lint_hir_and_then(
+                cx,
+                DISALLOWED_TRAITS,
+                hir_id,
+                span,
+                format!("use of a disallowed trait `{path}`"),
+                disallowed_path.diag_amendment(span),
+            );
+        }
+
+        if let Some(ImplMatcher { disallowed_impl, .. }) = disallowed_impl {
+            span_lint_hir_and_then(
+                cx,
+                DISALLOWED_IMPLS,
+                hir_id,
+                span,
+                format!(
+                    "disallowed implementation of `{}` for `{}`",
+                    disallowed_impl.trait_path.path(),
+                    disallowed_impl.self_ty.path()
+                ),
+                disallowed_impl.trait_path.diag_amendment(span),
+            );
+        }
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for DisallowedTraits {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'tcx>) {
+        if let ItemKind::Impl(imp) = item.kind
+            && !(self.traits.is_empty() && self.impls.is_empty())
+        {
+            self.check_impl(cx, item, &imp);
+        }
+    }
+
+    fn check_poly_trait_ref(&mut self, cx: &LateContext<'tcx>, poly: &'tcx PolyTraitRef<'tcx>) {
+        let path = poly.trait_ref.path;
+        if let Res::Def(DefKind::Trait, trait_id) = path.res
+            && !path.span.from_expansion()
+            && let Some(&(name, disallowed_path)) = self.traits.get(&trait_id)
+            && disallowed_path.applies_in(cx.tcx, poly.trait_ref.hir_ref_id)
+        {
+            span_lint_and_then(
+                cx,
+                DISALLOWED_TRAITS,
+                path.span,
+                format!("use of a disallowed trait `{name}`"),
+                disallowed_path.diag_amendment(path.span),
+            );
+        }
+    }
+}
@@ -5,7 +5,7 @@ use clippy_utils::paths::PathNS;
 use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefIdMap;
-use rustc_hir::{AmbigArg, Item, ItemKind, PolyTraitRef, PrimTy, Ty, TyKind, UseKind};
+use rustc_hir::{AmbigArg, HirId, Item, ItemKind, PolyTraitRef, PrimTy, Ty, TyKind, UseKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::TyCtxt;
 use rustc_session::impl_lint_pass;
@@ -37,7 +37,10 @@ declare_clippy_lint! {
     ///     { path = "std::sync::Mutex", reason = "prefer faster & simpler non-poisonable mutex", replacement = "parking_lot::Mutex" },
     ///     # This would normally error if the path is incorrect, but with `allow-invalid` = `true`,
     ///     # it will be silently ignored
-    ///     { path = "std::invalid::Type", reason = "use alternative instead", allow-invalid = true }
+    ///     { path = "std::invalid::Type", reason = "use alternative instead", allow-invalid = true },
+    ///     # Can be restricted to `async`, `const`, `unsafe` or `test` code, or to a module with
+    ///     # `module:<path>`.
+    ///     { path = "std::sync::MutexGuard", reason = "blocks the executor", contexts = ["async"] },
     /// ]
     /// ```
     ///
@@ -77,12 +80,15 @@ impl DisallowedTypes {
         Self { def_ids, prim_tys }
     }
 
-    fn check_res_emit(&self, cx: &LateContext<'_>, res: &Res, span: Span) {
+    fn check_res_emit(&self, cx: &LateContext<'_>, res: &Res, span: Span, hir_id: HirId) {
         let (path, disallowed_path) = match res {
             Res::Def(_, did) if let Some(&x) = self.def_ids.get(did) => x,
             Res::PrimTy(prim) if let Some(&x) = self.prim_tys.get(prim) => x,
             _ => return,
         };
+        if !disallowed_path.applies_in(cx.tcx, hir_id) {
+            return;
+        }
         span_lint_and_then(
             cx,
             DISALLOWED_TYPES,
@@ -111,17 +117,22 @@ impl<'tcx> LateLintPass<'tcx> for DisallowedTypes {
         if let ItemKind::Use(path, UseKind::Single(_)) = &item.kind
             && let Some(res) = path.res.type_ns
         {
-            self.check_res_emit(cx, &res, item.span);
+            self.check_res_emit(cx, &res, item.span, item.hir_id());
         }
     }
 
     fn check_ty(&mut self, cx: &LateContext<'tcx>, ty: &'tcx Ty<'tcx, AmbigArg>) {
         if let TyKind::Path(path) = &ty.kind {
-            self.check_res_emit(cx, &cx.qpath_res(path, ty.hir_id), ty.span);
+            self.check_res_emit(cx, &cx.qpath_res(path, ty.hir_id), ty.span, ty.hir_id);
         }
     }
 
     fn check_poly_trait_ref(&mut self, cx: &LateContext<'tcx>, poly: &'tcx PolyTraitRef<'tcx>) {
-        self.check_res_emit(cx, &poly.trait_ref.path.res, poly.trait_ref.path.span);
+        self.check_res_emit(
+            cx,
+            &poly.trait_ref.path.res,
+            poly.trait_ref.path.span,
+            poly.trait_ref.hir_ref_id,
+        );
     }
 }
@@ -36,6 +36,7 @@ extern crate rustc_infer;
 extern crate rustc_lexer;
 extern crate rustc_lint;
 extern crate rustc_middle;
+extern crate rustc_parse;
 extern crate rustc_parse_format;
 extern crate rustc_resolve;
 extern crate rustc_session;
@@ -56,6 +57,9 @@ mod combined_late_pass;
 
 pub mod declared_lints;
 pub mod deprecated_lints;
+pub mod lint_metadata;
+#[cfg(feature = "pass-timings")]
+pub mod pass_timings;
 
 // begin lints modules, do not remove this comment, it's used in `update_lints`
 mod absolute_paths;
@@ -74,6 +78,7 @@ mod attrs;
 mod await_holding_invalid;
 mod bit_width;
 mod block_scrutinee;
+mod blocking_in_async;
 mod blocks_in_conditions;
 mod bool_assert_comparison;
 mod bool_comparison;
@@ -95,6 +100,7 @@ mod comparison_chain;
 mod copy_iterator;
 mod crate_in_macro_def;
 mod create_dir;
+mod custom_rules;
 mod dbg_macro;
 mod default;
 mod default_constructed_unit_structs;
@@ -106,10 +112,12 @@ mod dereference;
 mod derivable_impls;
 mod derive;
 mod disallowed_fields;
+mod disallowed_layer_dependencies;
 mod disallowed_macros;
 mod disallowed_methods;
 mod disallowed_names;
 mod disallowed_script_idents;
+mod disallowed_traits;
 mod disallowed_types;
 mod doc;
 mod double_parens;
@@ -288,6 +296,7 @@ mod only_used_in_recursion;
 mod operators;
 mod option_env_unwrap;
 mod option_if_let_else;
+mod panic_in_no_panic_fn;
 mod panic_in_result_fn;
 mod panic_unimplemented;
 mod panicking_overflow_checks;
@@ -336,6 +345,7 @@ mod self_named_constructors;
 mod semicolon_block;
 mod semicolon_if_nothing_returned;
 mod serde_api;
+mod serde_attrs;
 mod set_contains_or_insert;
 mod shadow;
 mod significant_drop_tightening;
@@ -624,7 +634,7 @@ rustc_lint::late_lint_methods!(
         DerivableImpls: derivable_impls::DerivableImpls = derivable_impls::DerivableImpls::new(conf),
         DropForgetRef: drop_forget_ref::DropForgetRef = drop_forget_ref::DropForgetRef,
         EmptyEnums: empty_enums::EmptyEnums = empty_enums::EmptyEnums,
-        Regex: regex::Regex = <regex::Regex>::default(),
+        Regex: regex::Regex = regex::Regex::new(conf),
         CopyAndPaste: ifs::CopyAndPaste<'tcx> = ifs::CopyAndPaste::new(tcx, conf),
         CopyIterator: copy_iterator::CopyIterator = copy_iterator::CopyIterator,
         UselessFormat: format::UselessFormat = format::UselessFormat::new(format_args.clone()),
@@ -663,7 +673,7 @@ rustc_lint::late_lint_methods!(
         Unwrap: unwrap::Unwrap = unwrap::Unwrap::new(conf),
         IndexingSlicing: indexing_slicing::IndexingSlicing = indexing_slicing::IndexingSlicing::new(conf),
         NonCopyConst: non_copy_const::NonCopyConst<'tcx> = non_copy_const::NonCopyConst::new(tcx, conf),
-        RedundantClone: redundant_clone::RedundantClone = redundant_clone::RedundantClone,
+        RedundantClone: redundant_clone::RedundantClone = redundant_clone::RedundantClone::new(conf),
         SlowVectorInit: slow_vector_initialization::SlowVectorInit = slow_vector_initialization::SlowVectorInit,
         UnnecessaryWraps: unnecessary_wraps::UnnecessaryWraps = unnecessary_wraps::UnnecessaryWraps::new(conf),
         AssertionsOnConstants: assertions_on_constants::AssertionsOnConstants = assertions_on_constants::AssertionsOnConstants::new(conf),
@@ -869,6 +879,12 @@ rustc_lint::late_lint_methods!(
         BlockScrutinee: block_scrutinee::BlockScrutinee = block_scrutinee::BlockScrutinee,
         NonnullUncheckedOnBoxPtr: nonnull_unchecked_on_box_ptr::NonnullUncheckedOnBoxPtr = nonnull_unchecked_on_box_ptr::NonnullUncheckedOnBoxPtr::new(conf),
         NeedlessNonzeroGet: needless_nonzero_get::NeedlessNonzeroGet = needless_nonzero_get::NeedlessNonzeroGet::new(conf),
+        DisallowedLayerDependencies: disallowed_layer_dependencies::DisallowedLayerDependencies = disallowed_layer_dependencies::DisallowedLayerDependencies::new(tcx, conf),
+        DisallowedTraits: disallowed_traits::DisallowedTraits = disallowed_traits::DisallowedTraits::new(tcx, conf),
+        SerdeAttrs: serde_attrs::SerdeAttrs = serde_attrs::SerdeAttrs,
+        BlockingInAsync: blocking_in_async::BlockingInAsync = blocking_in_async::BlockingInAsync::new(tcx, conf),
+        PanicInNoPanicFn: panic_in_no_panic_fn::PanicInNoPanicFn = panic_in_no_panic_fn::PanicInNoPanicFn::new(tcx, conf),
+        CustomRules: custom_rules::CustomRules = custom_rules::CustomRules::new(tcx, conf),
         // add late passes here, used by `cargo dev new_lint`
     ]]
 );
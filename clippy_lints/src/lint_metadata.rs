@@ -0,0 +1,94 @@
+use crate::declared_lints::LINTS;
+use crate::deprecated_lints::{DEPRECATED, DEPRECATED_VERSION, RENAMED};
+use clippy_config::Conf;
+use rustc_data_structures::fx::FxHashMap;
+use serde::Serialize;
+
+/// The most applicable suggestion of each lint, as collected from the UI tests by
+/// `cargo collect-metadata`. Each line holds a lint name and its applicability.
+const APPLICABILITIES: &str = include_str!("../lint_applicabilities.txt");
+
+/// Machine-readable information about a lint. Used by `cargo clippy --list-lints` and the
+/// metadata collector.
+#[derive(Serialize)]
+pub struct LintMetadata {
+    /// The lint name in lowercase without the `clippy::` prefix.
+    pub name: String,
+    pub group: &'static str,
+    pub level: &'static str,
+    /// The Clippy version the lint was added in.
+    pub version: &'static str,
+    pub description: &'static str,
+    /// Whether the lint's behavior depends on the configured MSRV.
+    pub msrv: bool,
+    /// The most applicable suggestion the lint emits, or `None` if none of the UI tests emit the
+    /// lint. The `tests/ui-cargo` tests aren't collected, so this is always `None` for the lints
+    /// of the `cargo` group.
+    pub applicability: Option<&'static str>,
+    /// The configuration options affecting the lint.
+    pub configs: Vec<&'static str>,
+    /// The names the lint was previously known by.
+    pub past_names: Vec<&'static str>,
+    /// e.g. `clippy_lints/src/absolute_paths.rs#L43`
+    pub location: Option<&'static str>,
+    pub deprecation_reason: Option<&'static str>,
+}
+
+/// Gets the metadata for all declared and deprecated lints sorted by name.
+pub fn lint_metadata() -> Vec<LintMetadata> {
+    let configs = Conf::get_metadata();
+    let applicabilities: FxHashMap<_, _> = APPLICABILITIES
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .collect();
+    let mut lints: Vec<_> = LINTS
+        .iter()
+        .map(|lint| {
+            let name = lint.name_lower();
+            let lint_configs: Vec<_> = configs
+                .iter()
+                .filter(|conf| conf.renamed_to.is_none() && conf.lints.contains(&name.as_str()))
+                .map(|conf| conf.name)
+                .collect();
+            let past_names = RENAMED
+                .iter()
+                .filter(|(_, new_name)| new_name.strip_prefix("clippy::") == Some(&name))
+                .filter_map(|(old_name, _)| old_name.strip_prefix("clippy::"))
+                .collect();
+            let applicability = applicabilities.get(name.as_str()).copied();
+            LintMetadata {
+                name,
+                group: lint.category.name(),
+                level: lint.lint.default_level.as_str(),
+                version: lint.version,
+                description: lint.lint.desc,
+                msrv: lint_configs.contains(&"msrv"),
+                applicability,
+                configs: lint_configs,
+                past_names,
+                location: Some(lint.location),
+                deprecation_reason: None,
+            }
+        })
+        .chain(
+            DEPRECATED
+                .iter()
+                .zip(DEPRECATED_VERSION)
+                .map(|(&(name, reason), &version)| LintMetadata {
+                    name: name.strip_prefix("clippy::").unwrap_or(name).into(),
+                    group: "deprecated",
+                    level: "none",
+                    version,
+                    description: reason,
+                    msrv: false,
+                    applicability: None,
+                    configs: Vec::new(),
+                    past_names: Vec::new(),
+                    location: None,
+                    deprecation_reason: Some(reason),
+                }),
+        )
+        .collect();
+    lints.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    lints
+}
@@ -5083,12 +5083,10 @@ impl_lint_pass!(Methods => [
     ZST_OFFSET,
 ]);
 
-#[expect(clippy::struct_excessive_bools)]
 pub struct Methods {
     avoid_breaking_exported_api: bool,
     msrv: Msrv,
-    allow_expect_in_tests: bool,
-    allow_unwrap_in_tests: bool,
+    conf: &'static Conf,
     allow_expect_in_consts: bool,
     allow_unwrap_in_consts: bool,
     allowed_dotfiles: FxHashSet<&'static str>,
@@ -5106,8 +5104,7 @@ impl Methods {
         Self {
             avoid_breaking_exported_api: conf.avoid_breaking_exported_api,
             msrv: conf.msrv.into(),
-            allow_expect_in_tests: conf.allow_expect_in_tests,
-            allow_unwrap_in_tests: conf.allow_unwrap_in_tests,
+            conf,
             allow_expect_in_consts: conf.allow_expect_in_consts,
             allow_unwrap_in_consts: conf.allow_unwrap_in_consts,
             allowed_dotfiles,
@@ -5161,13 +5158,14 @@ impl<'tcx> LateLintPass<'tcx> for Methods {
                 swap_with_temporary::check(cx, expr, func, args);
                 ip_constant::check(cx, expr, func, args);
                 clone_on_copy::check_function(cx, expr);
+                let conf = self.conf.for_item(cx.tcx, expr.hir_id.owner.def_id);
                 unwrap_expect_used::check_call(
                     cx,
                     expr,
                     func,
                     args,
-                    self.allow_unwrap_in_tests,
-                    self.allow_expect_in_tests,
+                    conf.allow_unwrap_in_tests,
+                    conf.allow_expect_in_tests,
                     self.allow_unwrap_in_consts,
                     self.allow_expect_in_consts,
                     &self.unwrap_allowed_ids,
@@ -5934,7 +5932,9 @@ impl Methods {
                         recv,
                         false,
                         self.allow_expect_in_consts,
-                        self.allow_expect_in_tests,
+                        self.conf
+                            .for_item(cx.tcx, expr.hir_id.owner.def_id)
+                            .allow_expect_in_tests,
                         &self.unwrap_allowed_ids,
                         &self.unwrap_allowed_aliases,
                         unwrap_expect_used::Variant::Expect,
@@ -5948,7 +5948,9 @@ impl Methods {
                         recv,
                         true,
                         self.allow_expect_in_consts,
-                        self.allow_expect_in_tests,
+                        self.conf
+                            .for_item(cx.tcx, expr.hir_id.owner.def_id)
+                            .allow_expect_in_tests,
                         &self.unwrap_allowed_ids,
                         &self.unwrap_allowed_aliases,
                         unwrap_expect_used::Variant::Expect,
@@ -5977,7 +5979,9 @@ impl Methods {
                         recv,
                         false,
                         self.allow_unwrap_in_consts,
-                        self.allow_unwrap_in_tests,
+                        self.conf
+                            .for_item(cx.tcx, expr.hir_id.owner.def_id)
+                            .allow_unwrap_in_tests,
                         &self.unwrap_allowed_ids,
                         &self.unwrap_allowed_aliases,
                         unwrap_expect_used::Variant::Unwrap,
@@ -5990,7 +5994,9 @@ impl Methods {
                         recv,
                         true,
                         self.allow_unwrap_in_consts,
-                        self.allow_unwrap_in_tests,
+                        self.conf
+                            .for_item(cx.tcx, expr.hir_id.owner.def_id)
+                            .allow_unwrap_in_tests,
                         &self.unwrap_allowed_ids,
                         &self.unwrap_allowed_aliases,
                         unwrap_expect_used::Variant::Unwrap,
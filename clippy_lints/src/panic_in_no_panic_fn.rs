@@ -0,0 +1,368 @@
+use clippy_config::Conf;
+use clippy_utils::call_graph::{CallSummaries, Summarize};
+use clippy_utils::consts::{ConstEvalCtxt, FullInt};
+use clippy_utils::diagnostics::{span_lint, span_lint_and_then};
+use clippy_utils::paths::PathNS;
+use clippy_utils::res::MaybeDef as _;
+use clippy_utils::visitors::{Descend, for_each_expr};
+use clippy_utils::{get_builtin_attr, resolved_callee, sym};
+use core::ops::ControlFlow;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, DefIdSet, LocalDefId};
+use rustc_hir::intravisit::FnKind;
+use rustc_hir::{BinOpKind, Body, Expr, ExprKind, FnDecl, UnOp};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::ty::{self, TyCtxt, TypingEnv};
+use rustc_session::impl_lint_pass;
+use rustc_span::Span;
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks functions marked with `#[clippy::no_panic]`, or listed in the `no-panic-functions`
+    /// configuration, for anything which may panic. The functions of the crate they call are
+    /// checked as well, and the diagnostic shows the chain of calls leading to the panic.
+    ///
+    /// The following are considered to possibly panic:
+    /// * `panic!` and the macros built on it, e.g. `assert!`, `unreachable!` and `todo!`
+    /// * `unwrap` and `expect` of `Option` and `Result`
+    /// * indexing and slicing, unless an array is indexed with a constant in bounds
+    /// * integer arithmetic which may overflow or divide by zero
+    /// * calls into other crates, unless the function is listed in `no-panic-functions`
+    /// * calls through function pointers and trait objects
+    ///
+    /// Note: Even though this lint is warn-by-default, it will only trigger if functions are
+    /// marked with `#[clippy::no_panic]` or listed in the clippy.toml file.
+    ///
+    /// ### Why is this bad?
+    /// Some code, e.g. interrupt handlers, must not panic at all. Restriction lints such as
+    /// `unwrap_used` or `indexing_slicing` can rule out a single kind of panic, but not all of
+    /// them, and not in the functions being called.
+    ///
+    /// ### Known problems
+    /// The calls inserted by the compiler, e.g. for `for` loops, `?` and `.await`, aren't checked,
+    /// neither are `Drop` implementations.
+    ///
+    /// ### Example
+    /// ```no_run
+    /// #[clippy::no_panic]
+    /// fn checksum(data: &[u8]) -> u8 {
+    ///     data[0] ^ data[1]
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```no_run
+    /// #[clippy::no_panic]
+    /// fn checksum(data: &[u8]) -> u8 {
+    ///     match data {
+    ///         [a, b, ..] => a ^ b,
+    ///         _ => 0,
+    ///     }
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub PANIC_IN_NO_PANIC_FN,
+    style,
+    "code which may panic in a function marked `#[clippy::no_panic]`"
+}
+
+impl_lint_pass!(PanicInNoPanicFn => [PANIC_IN_NO_PANIC_FN]);
+
+pub struct PanicInNoPanicFn {
+    no_panic: NoPanicFns,
+    /// The first panic reachable from a function of the crate, if there is one
+    findings: CallSummaries<Finding>,
+}
+
+struct NoPanicFns {
+    /// The functions listed in the configuration. Local ones are checked, the others are trusted.
+    listed: DefIdSet,
+}
+
+#[derive(Clone, Copy)]
+enum PanicSource {
+    Panic,
+    Unwrap,
+    Indexing,
+    Overflow,
+    Division,
+    NonAudited(DefId),
+    IndirectCall,
+}
+
+impl PanicSource {
+    fn describe(self, tcx: TyCtxt<'_>) -> String {
+        match self {
+            Self::Panic => "explicit panic".to_owned(),
+            Self::Unwrap => "unwrap".to_owned(),
+            Self::Indexing => "indexing".to_owned(),
+            Self::Overflow => "arithmetic which may overflow".to_owned(),
+            Self::Division => "division which may panic".to_owned(),
+            Self::NonAudited(def_id) => format!("call to the non-audited function `{}`", tcx.def_path_str(def_id)),
+            Self::IndirectCall => "call through a function pointer or trait object".to_owned(),
+        }
+    }
+}
+
+/// What may panic in a body
+enum Reached {
+    Source(PanicSource, Span),
+    Call(DefId, Span),
+}
+
+#[derive(Clone)]
+struct Finding {
+    source: PanicSource,
+    span: Span,
+    /// The functions called on the way to the panic, starting with the callee
+    chain: Vec<DefId>,
+}
+
+impl PanicInNoPanicFn {
+    pub fn new(tcx: TyCtxt<'_>, conf: &'static Conf) -> Self {
+        let is_fn = |kind| matches!(kind, DefKind::Fn | DefKind::AssocFn);
+        Self {
+            no_panic: NoPanicFns {
+                listed: conf
+                    .no_panic_functions
+                    .iter()
+                    .flat_map(|path| path.resolve(tcx, PathNS::Value, is_fn, "function"))
+                    .collect(),
+            },
+            findings: CallSummaries::default(),
+        }
+    }
+}
+
+impl NoPanicFns {
+    fn is_root(&self, tcx: TyCtxt<'_>, def_id: LocalDefId) -> bool {
+        self.listed.contains(&def_id.to_def_id())
+            || get_builtin_attr(tcx.hir_attrs(tcx.local_def_id_to_hir_id(def_id)), sym::no_panic)
+                .next()
+                .is_some()
+    }
+
+    /// Checks whether a call of `callee` at `span` may panic. Functions checked on their own and
+    /// trusted ones are assumed not to, while the ones which can't be checked are assumed to.
+    fn call_finding(
+        &self,
+        tcx: TyCtxt<'_>,
+        findings: &mut CallSummaries<Finding>,
+        callee: DefId,
+        span: Span,
+    ) -> Option<Finding> {
+        if let Some(local_id) = callee.as_local()
+            && matches!(tcx.def_kind(callee), DefKind::Fn | DefKind::AssocFn)
+            && tcx.hir_maybe_body_owned_by(local_id).is_some()
+        {
+            if self.is_root(tcx, local_id) {
+                return None;
+            }
+            let mut finding = findings.get(tcx, self, local_id)?;
+            finding.chain.insert(0, callee);
+            Some(finding)
+        } else if self.listed.contains(&callee) {
+            None
+        } else {
+            Some(Finding {
+                source: PanicSource::NonAudited(callee),
+                span,
+                chain: Vec::new(),
+            })
+        }
+    }
+}
+
+impl Summarize for NoPanicFns {
+    type Summary = Finding;
+
+    fn summarize(&self, tcx: TyCtxt<'_>, findings: &mut CallSummaries<Finding>, def_id: LocalDefId) -> Option<Finding> {
+        let body = tcx.hir_maybe_body_owned_by(def_id)?;
+        reached(tcx, def_id, body)
+            .into_iter()
+            .find_map(|reached| match reached {
+                Reached::Source(source, span) => Some(Finding {
+                    source,
+                    span,
+                    chain: Vec::new(),
+                }),
+                Reached::Call(callee, span) => self.call_finding(tcx, findings, callee, span),
+            })
+    }
+}
+
+impl<'tcx> LateLintPass<'tcx> for PanicInNoPanicFn {
+    fn check_fn(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        kind: FnKind<'tcx>,
+        _: &'tcx FnDecl<'tcx>,
+        body: &'tcx Body<'tcx>,
+        _: Span,
+        def_id: LocalDefId,
+    ) {
+        if matches!(kind, FnKind::Closure) || !self.no_panic.is_root(cx.tcx, def_id) {
+            return;
+        }
+        for reached in reached(cx.tcx, def_id, body) {
+            let (span, finding) = match reached {
+                Reached::Source(source, span) => (
+                    span,
+                    Finding {
+                        source,
+                        span,
+                        chain: Vec::new(),
+                    },
+                ),
+                Reached::Call(callee, span) => {
+                    match self.no_panic.call_finding(cx.tcx, &mut self.findings, callee, span) {
+                        Some(finding) => (span, finding),
+                        None => continue,
+                    }
+                },
+            };
+            let source = finding.source.describe(cx.tcx);
+            let Some(&callee) = finding.chain.first() else {
+                span_lint(
+                    cx,
+                    PANIC_IN_NO_PANIC_FN,
+                    span,
+                    format!("{source} in a `no_panic` function"),
+                );
+                continue;
+            };
+            span_lint_and_then(
+                cx,
+                PANIC_IN_NO_PANIC_FN,
+                span,
+                format!("call to `{}` may panic", cx.tcx.def_path_str(callee)),
+                |diag| {
+                    diag.span_note(
+                        finding.span,
+                        format!("{source} in `{}`", cx.tcx.def_path_str(*finding.chain.last().unwrap())),
+                    );
+                    if finding.chain.len() > 1 {
+                        let chain: Vec<_> = finding
+                            .chain
+                            .iter()
+                            .map(|&def_id| format!("`{}`", cx.tcx.def_path_str(def_id)))
+                            .collect();
+                        diag.note(format!("call chain: {}", chain.join(" -> ")));
+                    }
+                },
+            );
+        }
+    }
+}
+
+/// Checks whether the function is `unwrap` or `expect` of `Option` or `Result`, or one of their
+/// `_err` variants.
+fn is_unwrap(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    matches!(
+        tcx.item_name(def_id),
+        sym::unwrap | sym::expect | sym::unwrap_err | sym::expect_err
+    ) && def_id.assoc_fn_parent(&tcx).opt_impl_ty(&tcx).is_some_and(|ty| {
+        matches!(
+            ty.instantiate_identity().skip_norm_wip().opt_diag_name(&tcx),
+            Some(sym::Option | sym::Result)
+        )
+    })
+}
+
+/// Checks whether the function is one of the standard library's panic entry points, which
+/// `panic!` and the macros built on it call.
+fn is_panic_fn(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    matches!(tcx.crate_name(def_id.krate), sym::core | sym::std)
+        && tcx
+            .def_path(def_id)
+            .data
+            .first()
+            .and_then(|data| data.data.get_opt_name())
+            == Some(sym::panicking)
+}
+
+/// Collects what may panic in the body, including inside of closures, along with the calls to
+/// other functions.
+fn reached<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId, body: &'tcx Body<'tcx>) -> Vec<Reached> {
+    let typing_env = TypingEnv::post_analysis(tcx, def_id);
+    let typeck = tcx.typeck(def_id);
+    let ecx = ConstEvalCtxt::with_env(tcx, typing_env, typeck);
+    let int_value = |e: &Expr<'_>| ecx.eval(e)?.int_value(tcx, typeck.expr_ty(e));
+
+    let mut reached = Vec::new();
+    for_each_expr(tcx, body.value, |e| {
+        // Skip the calls `for` loops, `?` and `.await` desugar to
+        if e.span.desugaring_kind().is_some() {
+            return ControlFlow::<(), _>::Continue(Descend::Yes);
+        }
+        let span = e.span.source_callsite();
+        match e.kind {
+            ExprKind::Call(callee, _) | ExprKind::MethodCall(_, callee, ..) => {
+                if let Some(callee_id) = resolved_callee(tcx, typing_env, typeck, e) {
+                    if is_panic_fn(tcx, callee_id) {
+                        reached.push(Reached::Source(PanicSource::Panic, span));
+                        // The arguments are the panic message
+                        return ControlFlow::Continue(Descend::No);
+                    } else if is_unwrap(tcx, callee_id) {
+                        reached.push(Reached::Source(PanicSource::Unwrap, span));
+                    } else if !matches!(tcx.def_kind(callee_id), DefKind::Ctor(..)) {
+                        reached.push(Reached::Call(callee_id, span));
+                    }
+                } else if matches!(e.kind, ExprKind::Call(..))
+                    // The bodies of closures are checked where they're defined
+                    && !matches!(typeck.expr_ty(callee).kind(), ty::Closure(..))
+                {
+                    reached.push(Reached::Source(PanicSource::IndirectCall, span));
+                }
+            },
+            ExprKind::Index(base, index, _) => {
+                let in_bounds = if let ty::Array(_, len) = typeck.expr_ty_adjusted(base).peel_refs().kind()
+                    && let Some(len) = len.try_to_target_usize(tcx)
+                    && let Some(FullInt::U(index)) = int_value(index)
+                {
+                    index < u128::from(len)
+                } else {
+                    false
+                };
+                if !in_bounds {
+                    reached.push(Reached::Source(PanicSource::Indexing, span));
+                }
+            },
+            ExprKind::Binary(op, lhs, rhs) if !typeck.is_method_call(e) && typeck.expr_ty(lhs).is_integral() => {
+                reached.extend(
+                    arithmetic_panic(op.node, ecx.eval(lhs).is_some(), int_value(rhs))
+                        .map(|source| Reached::Source(source, span)),
+                );
+            },
+            ExprKind::AssignOp(op, lhs, rhs) if !typeck.is_method_call(e) && typeck.expr_ty(lhs).is_integral() => {
+                reached.extend(
+                    arithmetic_panic(op.node.into(), ecx.eval(lhs).is_some(), int_value(rhs))
+                        .map(|source| Reached::Source(source, span)),
+                );
+            },
+            ExprKind::Unary(UnOp::Neg, operand)
+                if !typeck.is_method_call(e) && typeck.expr_ty(operand).is_signed() && ecx.eval(operand).is_none() =>
+            {
+                reached.push(Reached::Source(PanicSource::Overflow, span));
+            },
+            _ => {},
+        }
+        ControlFlow::Continue(Descend::Yes)
+    });
+    reached
+}
+
+/// Checks how a builtin integer operation may panic at runtime. Operations which are known to
+/// panic from constant operands are already rejected by the compiler.
+fn arithmetic_panic(op: BinOpKind, lhs_is_const: bool, rhs_value: Option<FullInt>) -> Option<PanicSource> {
+    match op {
+        BinOpKind::Add | BinOpKind::Sub | BinOpKind::Mul if !lhs_is_const || rhs_value.is_none() => {
+            Some(PanicSource::Overflow)
+        },
+        // Dividing the minimum value of a signed type by `-1` overflows
+        BinOpKind::Div | BinOpKind::Rem if !matches!(rhs_value, Some(FullInt::U(1..) | FullInt::S(..-1 | 1..))) => {
+            Some(PanicSource::Division)
+        },
+        BinOpKind::Shl | BinOpKind::Shr if rhs_value.is_none() => Some(PanicSource::Overflow),
+        _ => None,
+    }
+}
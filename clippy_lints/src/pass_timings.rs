@@ -0,0 +1,64 @@
+//! Time spent in each field of the combined lint passes.
+//!
+//! Only compiled with the `pass-timings` feature. Every `check_*` call forwarded by
+//! `CombinedEarlyLintPass` and `CombinedLateLintPass` is timed, and the totals are merged into a
+//! global table when the combined pass is dropped. The measurement itself isn't free and is also
+//! paid for methods a pass doesn't implement, so the numbers are best used to rank passes rather
+//! than as absolute costs.
+
+use rustc_data_structures::fx::FxHashMap;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static TIMINGS: Mutex<BTreeMap<(&'static str, &'static str), Duration>> = Mutex::new(BTreeMap::new());
+
+/// The total time spent in one pass.
+pub struct PassTiming {
+    /// Either `early` or `late`
+    pub kind: &'static str,
+    /// The name of the field in the combined pass, e.g. `Methods`
+    pub pass: &'static str,
+    pub time: Duration,
+}
+
+/// The timings of one combined pass instance.
+pub struct PassTimings {
+    kind: &'static str,
+    times: FxHashMap<&'static str, Duration>,
+}
+
+impl PassTimings {
+    pub fn new(kind: &'static str) -> Self {
+        Self {
+            kind,
+            times: FxHashMap::default(),
+        }
+    }
+
+    #[inline]
+    pub fn record(&mut self, pass: &'static str, start: Instant) {
+        *self.times.entry(pass).or_default() += start.elapsed();
+    }
+}
+
+impl Drop for PassTimings {
+    fn drop(&mut self) {
+        let mut timings = TIMINGS.lock().unwrap_or_else(std::sync::PoisonError::into_inner);
+        #[expect(
+            rustc::potential_query_instability,
+            reason = "the times are summed into an ordered map"
+        )]
+        for (&pass, &time) in &self.times {
+            *timings.entry((self.kind, pass)).or_default() += time;
+        }
+    }
+}
+
+/// Takes the timings of all combined passes which have finished running.
+pub fn take() -> Vec<PassTiming> {
+    std::mem::take(&mut *TIMINGS.lock().unwrap_or_else(std::sync::PoisonError::into_inner))
+        .into_iter()
+        .map(|((kind, pass), time)| PassTiming { kind, pass, time })
+        .collect()
+}
@@ -1,5 +1,6 @@
+use clippy_config::Conf;
 use clippy_utils::diagnostics::{span_lint_hir, span_lint_hir_and_then};
-use clippy_utils::mir::{LocalUsage, PossibleBorrowerMap, visit_local_usage};
+use clippy_utils::mir::{LocalUsage, ParamUsage, ParamUsages, PossibleBorrowerMap, visit_local_usage};
 use clippy_utils::res::MaybeDef as _;
 use clippy_utils::source::SpanExt as _;
 use clippy_utils::ty::{has_drop, is_copy, peel_and_count_ty_refs};
@@ -10,7 +11,7 @@ use rustc_hir::{Body, FnDecl, LangItem, def_id};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::mir;
 use rustc_middle::ty::{self, Ty};
-use rustc_session::declare_lint_pass;
+use rustc_session::impl_lint_pass;
 use rustc_span::def_id::LocalDefId;
 use rustc_span::{BytePos, Span};
 
@@ -19,6 +20,9 @@ declare_clippy_lint! {
     /// Checks for a redundant `clone()` (and its relatives) which clones an owned
     /// value that is going to be dropped without further use.
     ///
+    /// Also checks for clones passed to a function of the crate which only reads
+    /// its parameter, so that it could take a reference instead.
+    ///
     /// ### Why is this bad?
     /// It is not always possible for the compiler to eliminate useless
     /// allocations and deallocations generated by redundant `clone()`s.
@@ -44,6 +48,11 @@ declare_clippy_lint! {
     /// ["lorem", "ipsum"].join(" ").to_string();
     ///
     /// Path::new("/a/b").join("c").to_path_buf();
+    ///
+    /// fn print(x: Foo) {}
+    /// let x = Foo::new();
+    /// print(x.clone()); // `print` could take `&Foo`
+    /// call(x);
     /// ```
     #[clippy::version = "1.32.0"]
     pub REDUNDANT_CLONE,
@@ -51,7 +60,21 @@ declare_clippy_lint! {
     "`clone()` of an owned value that is going to be dropped immediately"
 }
 
-declare_lint_pass!(RedundantClone => [REDUNDANT_CLONE]);
+impl_lint_pass!(RedundantClone => [REDUNDANT_CLONE]);
+
+pub struct RedundantClone {
+    param_usages: ParamUsages,
+    avoid_breaking_exported_api: bool,
+}
+
+impl RedundantClone {
+    pub fn new(conf: &'static Conf) -> Self {
+        Self {
+            param_usages: ParamUsages::default(),
+            avoid_breaking_exported_api: conf.avoid_breaking_exported_api,
+        }
+    }
+}
 
 impl<'tcx> LateLintPass<'tcx> for RedundantClone {
     #[expect(clippy::too_many_lines)]
@@ -123,6 +146,11 @@ impl<'tcx> LateLintPass<'tcx> for RedundantClone {
                 // if `arg` is the only borrow of `cloned` at this point.
 
                 if cannot_move_out || !possible_borrower.only_borrowers(&[arg], cloned, loc) {
+                    // The clone can't be removed, but it may be moved into a function only reading it
+                    if cloned != clone_ret {
+                        let clone_usage = visit_clone_usage(cloned, clone_ret, mir, bb);
+                        self.check_read_only_callee(cx, mir, terminator, arg_ty, clone_ret, &clone_usage);
+                    }
                     continue;
                 }
 
@@ -181,11 +209,13 @@ impl<'tcx> LateLintPass<'tcx> for RedundantClone {
                     cloned_use_loc: None.into(),
                     cloned_consume_or_mutate_loc: None,
                     clone_consumed_or_mutated: true,
+                    clone_consumed_at: None,
                 }
             } else {
                 let clone_usage = visit_clone_usage(local, ret_local, mir, bb);
                 if clone_usage.cloned_use_loc.maybe_used() && clone_usage.clone_consumed_or_mutated {
                     // cloned value is used, and the clone is modified or moved
+                    self.check_read_only_callee(cx, mir, terminator, arg_ty, ret_local, &clone_usage);
                     continue;
                 } else if let MirLocalUsage::Used(loc) = clone_usage.cloned_use_loc
                     && possible_borrower.local_is_alive_at(ret_local, loc)
@@ -244,6 +274,72 @@ impl<'tcx> LateLintPass<'tcx> for RedundantClone {
     }
 }
 
+impl RedundantClone {
+    /// Lints a temporary clone only moved into a call of a crate-local function which only reads
+    /// the parameter, if the cloned value is neither consumed nor mutated before the call.
+    /// Borrowing it would do if the function took a reference.
+    fn check_read_only_callee<'tcx>(
+        &mut self,
+        cx: &LateContext<'tcx>,
+        mir: &mir::Body<'tcx>,
+        terminator: &mir::Terminator<'tcx>,
+        cloned_ty: Ty<'tcx>,
+        clone: mir::Local,
+        clone_usage: &CloneUsage,
+    ) {
+        let Some(call_loc) = clone_usage.clone_consumed_at else {
+            return;
+        };
+        // A reference to the cloned value has to be usable in place of the clone
+        if mir.local_kind(clone) != mir::LocalKind::Temp || mir.local_decls[clone].ty != cloned_ty {
+            return;
+        }
+        if let Some(loc) = clone_usage.cloned_consume_or_mutate_loc
+            && (loc.block == call_loc.block || !mir.basic_blocks.dominators().dominates(call_loc.block, loc.block))
+        {
+            return;
+        }
+        let call_data = &mir.basic_blocks[call_loc.block];
+        if call_loc.statement_index == call_data.statements.len()
+            && let mir::TerminatorKind::Call {
+                func, args, fn_span, ..
+            } = &call_data.terminator().kind
+            && let ty::FnDef(callee, _) = *func.ty(mir, cx.tcx).kind()
+            && let Some(index) = args
+                .iter()
+                .position(|arg| matches!(arg.node, mir::Operand::Move(place) if place.as_local() == Some(clone)))
+            // Changing the parameter of an exported function breaks the other crates calling it
+            && !(self.avoid_breaking_exported_api
+                && callee.as_local().is_some_and(|callee| cx.effective_visibilities.is_exported(callee)))
+            && self.param_usages.get(cx, callee, index) == Some(ParamUsage::Borrowed)
+            // A reference may not satisfy the bounds of a generic parameter
+            && !matches!(cx.tcx.fn_sig(callee).skip_binder().inputs().skip_binder()[index].kind(), ty::Param(_))
+            && let span = terminator.source_info.span
+            && !fn_span.from_expansion()
+        {
+            let node = mir.source_scopes[terminator.source_info.scope]
+                .local_data
+                .as_ref()
+                .unwrap_crate_local()
+                .lint_root;
+            let callee = cx.tcx.item_name(callee);
+            span_lint_hir_and_then(cx, REDUNDANT_CLONE, node, span, "redundant clone", |diag| {
+                diag.span_note(*fn_span, format!("`{callee}` only reads this argument"));
+                if let Some(snip) = span.get_text(cx)
+                    && let Some(dot) = snip.rfind('.')
+                {
+                    diag.help(format!(
+                        "change the parameter of `{callee}` to a reference, and pass `&{}` instead",
+                        &snip[..dot]
+                    ));
+                } else {
+                    diag.help(format!("change the parameter of `{callee}` to a reference"));
+                }
+            });
+        }
+    }
+}
+
 /// If `kind` is `y = func(x: &T)` where `T: !Copy`, returns `(DefId of func, x, T, y)`.
 fn is_call_with_ref_arg<'tcx>(
     cx: &LateContext<'tcx>,
@@ -360,6 +456,8 @@ struct CloneUsage {
     cloned_consume_or_mutate_loc: Option<mir::Location>,
     /// Whether the clone value is mutated.
     clone_consumed_or_mutated: bool,
+    /// The location where the clone is consumed or mutated, if it is the only one.
+    clone_consumed_at: Option<mir::Location>,
 }
 
 fn visit_clone_usage(cloned: mir::Local, clone: mir::Local, mir: &mir::Body<'_>, bb: mir::BasicBlock) -> CloneUsage {
@@ -389,12 +487,18 @@ fn visit_clone_usage(cloned: mir::Local, clone: mir::Local, mir: &mir::Body<'_>,
             // TODO: Actually check for mutation of non-temporaries.
             clone_consumed_or_mutated: mir.local_kind(clone) != mir::LocalKind::Temp
                 || !clone_consume_or_mutate_locs.is_empty(),
+            clone_consumed_at: if let [loc] = *clone_consume_or_mutate_locs {
+                Some(loc)
+            } else {
+                None
+            },
         }
     } else {
         CloneUsage {
             cloned_use_loc: MirLocalUsage::Unknown,
             cloned_consume_or_mutate_loc: None,
             clone_consumed_or_mutated: true,
+            clone_consumed_at: None,
         }
     }
 }
@@ -1,14 +1,21 @@
 use std::fmt::Display;
 
+use clippy_config::Conf;
 use clippy_utils::consts::{ConstEvalCtxt, Constant};
-use clippy_utils::diagnostics::{span_lint, span_lint_and_help};
-use clippy_utils::paths;
+use clippy_utils::diagnostics::{span_lint, span_lint_and_help, span_lint_and_then};
+use clippy_utils::msrvs::{self, Msrv};
 use clippy_utils::paths::PathLookup;
 use clippy_utils::res::MaybeQPath as _;
-use clippy_utils::source::SpanExt as _;
+use clippy_utils::source::{SpanExt as _, indent_of, snippet_opt};
+use clippy_utils::visitors::{for_each_expr, for_each_local_use_after_expr};
+use clippy_utils::{get_parent_expr, is_entrypoint_fn, is_in_test, is_no_std_crate, paths, sym};
+use core::ops::ControlFlow;
 use rustc_ast::ast::{LitKind, StrStyle};
+use rustc_errors::Applicability;
+use rustc_hir::def::{DefKind, Res};
 use rustc_hir::def_id::DefIdMap;
-use rustc_hir::{BorrowKind, Expr, ExprKind, OwnerId};
+use rustc_hir::intravisit::{Visitor, walk_path};
+use rustc_hir::{BindingMode, Block, BorrowKind, Expr, ExprKind, HirId, Node, OwnerId, PatKind, Path, StmtKind};
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_session::impl_lint_pass;
 use rustc_span::{BytePos, Span};
@@ -17,7 +24,12 @@ declare_clippy_lint! {
     /// ### What it does
     /// Checks [regex](https://crates.io/crates/regex) creation
     /// (with `Regex::new`, `RegexBuilder::new`, or `RegexSet::new`) for correct
-    /// regex syntax.
+    /// regex syntax. The `Regex::new` and `RegexBuilder::new` functions of
+    /// [`regex-lite`](https://crates.io/crates/regex-lite) and
+    /// [`fancy-regex`](https://crates.io/crates/fancy-regex) are checked as well.
+    ///
+    /// The flags set on a builder which change the syntax, e.g. `ignore_whitespace`
+    /// or `case_insensitive`, are taken into account.
     ///
     /// ### Why is this bad?
     /// This will lead to a runtime panic.
@@ -37,6 +49,42 @@ declare_clippy_lint! {
     "invalid regular expressions"
 }
 
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for [regex](https://crates.io/crates/regex) compilation from a constant pattern
+    /// inside of a function, which compiles the regex again every time the function is called.
+    ///
+    /// ### Why is this bad?
+    /// Compiling a regex is a much more expensive operation than using one. A regex compiled
+    /// once in a `LazyLock` static can be used by every call of the function.
+    ///
+    /// ### Known problems
+    /// There's no way to know how often a function is called, so this also lints functions which
+    /// are only called once. `main`, tests and closures aren't linted, neither are regexes which
+    /// are returned or passed on, e.g. by constructors. Regexes compiled inside of loops are linted
+    /// by `regex_creation_in_loops` instead.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// fn is_version(s: &str) -> bool {
+    ///     let re = regex::Regex::new(r"^\d+\.\d+\.\d+$").unwrap();
+    ///     re.is_match(s)
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// fn is_version(s: &str) -> bool {
+    ///     static RE: std::sync::LazyLock<regex::Regex> =
+    ///         std::sync::LazyLock::new(|| regex::Regex::new(r"^\d+\.\d+\.\d+$").unwrap());
+    ///     RE.is_match(s)
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub REGEX_CREATION_IN_FUNCTIONS,
+    pedantic,
+    "regular expression compilation performed on every call of a function"
+}
+
 declare_clippy_lint! {
     /// ### What it does
     ///
@@ -105,7 +153,12 @@ declare_clippy_lint! {
     "trivial regular expressions"
 }
 
-impl_lint_pass!(Regex => [INVALID_REGEX, REGEX_CREATION_IN_LOOPS, TRIVIAL_REGEX]);
+impl_lint_pass!(Regex => [
+    INVALID_REGEX,
+    REGEX_CREATION_IN_FUNCTIONS,
+    REGEX_CREATION_IN_LOOPS,
+    TRIVIAL_REGEX,
+]);
 
 #[derive(Copy, Clone)]
 enum RegexKind {
@@ -113,34 +166,56 @@ enum RegexKind {
     UnicodeSet,
     Bytes,
     BytesSet,
+    Fancy,
+}
+
+#[derive(Copy, Clone)]
+struct RegexFn {
+    kind: RegexKind,
+    /// Whether the function creates a builder, whose flags may change the syntax
+    is_builder: bool,
 }
 
-#[derive(Default)]
 pub struct Regex {
-    definitions: DefIdMap<RegexKind>,
+    definitions: DefIdMap<RegexFn>,
     loop_stack: Vec<(OwnerId, Span)>,
+    msrv: Msrv,
+}
+
+impl Regex {
+    pub fn new(conf: &'static Conf) -> Self {
+        Self {
+            definitions: DefIdMap::default(),
+            loop_stack: Vec::new(),
+            msrv: conf.msrv.into(),
+        }
+    }
 }
 
 impl<'tcx> LateLintPass<'tcx> for Regex {
     fn check_crate(&mut self, cx: &LateContext<'tcx>) {
-        let mut resolve = |path: &PathLookup, kind: RegexKind| {
+        let mut resolve = |path: &PathLookup, kind: RegexKind, is_builder: bool| {
             for &id in path.get(cx) {
-                self.definitions.insert(id, kind);
+                self.definitions.insert(id, RegexFn { kind, is_builder });
             }
         };
 
-        resolve(&paths::REGEX_NEW, RegexKind::Unicode);
-        resolve(&paths::REGEX_BUILDER_NEW, RegexKind::Unicode);
-        resolve(&paths::REGEX_SET_NEW, RegexKind::UnicodeSet);
-        resolve(&paths::REGEX_BYTES_NEW, RegexKind::Bytes);
-        resolve(&paths::REGEX_BYTES_BUILDER_NEW, RegexKind::Bytes);
-        resolve(&paths::REGEX_BYTES_SET_NEW, RegexKind::BytesSet);
+        resolve(&paths::REGEX_NEW, RegexKind::Unicode, false);
+        resolve(&paths::REGEX_BUILDER_NEW, RegexKind::Unicode, true);
+        resolve(&paths::REGEX_SET_NEW, RegexKind::UnicodeSet, false);
+        resolve(&paths::REGEX_BYTES_NEW, RegexKind::Bytes, false);
+        resolve(&paths::REGEX_BYTES_BUILDER_NEW, RegexKind::Bytes, true);
+        resolve(&paths::REGEX_BYTES_SET_NEW, RegexKind::BytesSet, false);
+        resolve(&paths::REGEX_LITE_NEW, RegexKind::Unicode, false);
+        resolve(&paths::REGEX_LITE_BUILDER_NEW, RegexKind::Unicode, true);
+        resolve(&paths::FANCY_REGEX_NEW, RegexKind::Fancy, false);
+        resolve(&paths::FANCY_REGEX_BUILDER_NEW, RegexKind::Fancy, true);
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>) {
         if let ExprKind::Call(fun, [arg]) = expr.kind
             && let Some(def_id) = fun.res(cx).opt_def_id()
-            && let Some(regex_kind) = self.definitions.get(&def_id)
+            && let Some(&regex_fn) = self.definitions.get(&def_id)
         {
             if let Some(&(loop_item_id, loop_span)) = self.loop_stack.last()
                 && loop_item_id == fun.hir_id.owner
@@ -154,13 +229,20 @@ impl<'tcx> LateLintPass<'tcx> for Regex {
                     Some(loop_span),
                     "move the regex construction outside this loop",
                 );
+            } else if !regex_fn.is_builder {
+                check_creation_in_fn(cx, &self.definitions, expr, fun, arg, self.msrv);
             }
 
-            match regex_kind {
-                RegexKind::Unicode => check_regex(cx, arg, true),
-                RegexKind::UnicodeSet => check_set(cx, arg, true),
-                RegexKind::Bytes => check_regex(cx, arg, false),
-                RegexKind::BytesSet => check_set(cx, arg, false),
+            let utf8 = !matches!(regex_fn.kind, RegexKind::Bytes | RegexKind::BytesSet);
+            let mut parser = regex_syntax::ParserBuilder::new();
+            parser.unicode(true).utf8(utf8);
+            if regex_fn.is_builder && apply_builder_flags(cx, expr, &mut parser).is_none() {
+                return;
+            }
+            match regex_fn.kind {
+                RegexKind::Unicode | RegexKind::Bytes => check_regex(cx, arg, &parser, false),
+                RegexKind::UnicodeSet | RegexKind::BytesSet => check_set(cx, arg, &parser),
+                RegexKind::Fancy => check_regex(cx, arg, &parser, true),
             }
         } else if let ExprKind::Loop(block, _, _, span) = expr.kind {
             self.loop_stack.push((block.hir_id.owner, span));
@@ -174,6 +256,177 @@ impl<'tcx> LateLintPass<'tcx> for Regex {
     }
 }
 
+/// Checks an unwrapped call of `Regex::new` (or similar) for a regex compiled every time the
+/// surrounding function is called.
+fn check_creation_in_fn<'tcx>(
+    cx: &LateContext<'tcx>,
+    definitions: &DefIdMap<RegexFn>,
+    call: &'tcx Expr<'tcx>,
+    fun: &Expr<'_>,
+    arg: &'tcx Expr<'_>,
+    msrv: Msrv,
+) {
+    let owner = cx.tcx.hir_enclosing_body_owner(call.hir_id);
+    if matches!(cx.tcx.def_kind(owner), DefKind::Fn | DefKind::AssocFn)
+        && !is_entrypoint_fn(cx, owner.to_def_id())
+        && !is_in_test(cx.tcx, call.hir_id)
+        && is_constant_pattern(cx, arg)
+        && let Some(unwrap) = get_parent_expr(cx, call)
+        && !refers_to_generics(unwrap)
+        && let ExprKind::MethodCall(method, receiver, args, _) = unwrap.kind
+        && receiver.hir_id == call.hir_id
+        && match (method.ident.name, args) {
+            (sym::unwrap, []) => true,
+            (sym::expect, [msg]) => matches!(msg.kind, ExprKind::Lit(_)),
+            _ => false,
+        }
+        && !unwrap.span.from_expansion()
+        && let ExprKind::Block(fn_block, _) = cx.tcx.hir_body_owned_by(owner).value.kind
+        && !fn_block.span.from_expansion()
+        && msrv.meets(cx, msrvs::LAZY_CELL)
+        && !is_no_std_crate(cx)
+        && let Some(init) = snippet_opt(cx, unwrap.span)
+        && let Some(ty) = snippet_opt(cx, fun.span).and_then(|fun| Some(fun.strip_suffix("::new")?.to_owned()))
+        && let Some((name, replacement)) =
+            local_use_replacement(cx, unwrap, &static_suffix(cx, definitions, call, fn_block))
+    {
+        // An item of the function with the same name would conflict with the static
+        let applicability = if fn_block.stmts.iter().any(|stmt| {
+            matches!(stmt.kind, StmtKind::Item(item)
+                if cx.tcx.opt_item_name(item.owner_id.to_def_id()).is_some_and(|item| item.as_str() == name))
+        }) {
+            Applicability::MaybeIncorrect
+        } else {
+            Applicability::MachineApplicable
+        };
+        let indent = fn_block
+            .stmts
+            .first()
+            .map(|stmt| stmt.span)
+            .or(fn_block.expr.map(|e| e.span))
+            .and_then(|span| indent_of(cx, span))
+            .unwrap_or(4);
+        let static_item = format!(
+            "\n{}static {name}: std::sync::LazyLock<{ty}> = std::sync::LazyLock::new(|| {init});",
+            " ".repeat(indent)
+        );
+        let block_start = fn_block.span.with_lo(fn_block.span.lo() + BytePos(1)).shrink_to_lo();
+
+        span_lint_and_then(
+            cx,
+            REGEX_CREATION_IN_FUNCTIONS,
+            unwrap.span,
+            "compiling a regex every time the function is called",
+            |diag| {
+                diag.multipart_suggestion(
+                    "compile the regex once in a static",
+                    vec![(block_start, static_item), (unwrap.span, replacement)],
+                    applicability,
+                );
+            },
+        );
+    }
+}
+
+/// Gets the suffix which keeps the name of the static unique among the regexes created in the
+/// function, i.e. `_2` for the second one.
+fn static_suffix<'tcx>(
+    cx: &LateContext<'tcx>,
+    definitions: &DefIdMap<RegexFn>,
+    call: &Expr<'_>,
+    fn_block: &'tcx Block<'tcx>,
+) -> String {
+    let mut earlier = 0;
+    for_each_expr(cx.tcx, fn_block, |e| {
+        if let ExprKind::Call(fun, [_]) = e.kind
+            && e.span.lo() < call.span.lo()
+            && fun
+                .res(cx)
+                .opt_def_id()
+                .is_some_and(|def_id| definitions.contains_key(&def_id))
+        {
+            earlier += 1;
+        }
+        ControlFlow::<()>::Continue(())
+    });
+    if earlier == 0 {
+        String::new()
+    } else {
+        format!("_{}", earlier + 1)
+    }
+}
+
+/// If the regex is only used locally, i.e. as the receiver of method calls, gets the name of the
+/// static to move it to along with the replacement for the unwrapped regex.
+///
+/// Regexes which are returned or passed on, e.g. by a constructor, are most likely not compiled
+/// repeatedly.
+fn local_use_replacement(cx: &LateContext<'_>, unwrap: &Expr<'_>, suffix: &str) -> Option<(String, String)> {
+    let is_receiver = |e: &Expr<'_>| {
+        matches!(
+            get_parent_expr(cx, e),
+            Some(Expr { kind: ExprKind::MethodCall(_, receiver, ..), .. }) if receiver.hir_id == e.hir_id
+        )
+    };
+    match cx.tcx.parent_hir_node(unwrap.hir_id) {
+        // `Regex::new(..).unwrap().is_match(..)`
+        Node::Expr(_) if is_receiver(unwrap) => Some((format!("REGEX{suffix}"), format!("REGEX{suffix}"))),
+        // `let re = Regex::new(..).unwrap();`, where `re` becomes a reference
+        Node::LetStmt(local)
+            if let PatKind::Binding(BindingMode::NONE, binding_id, ident, None) = local.pat.kind
+                && local.ty.is_none()
+                && for_each_local_use_after_expr(cx, binding_id, unwrap.hir_id, |e| {
+                    if is_receiver(e) {
+                        ControlFlow::Continue(())
+                    } else {
+                        ControlFlow::Break(())
+                    }
+                })
+                .is_continue() =>
+        {
+            let name = format!("{}{suffix}", ident.as_str().to_uppercase());
+            let replacement = format!("&*{name}");
+            Some((name, replacement))
+        },
+        _ => None,
+    }
+}
+
+/// Checks whether the pattern, or all the patterns of a set, are known at compile time.
+fn is_constant_pattern<'tcx>(cx: &LateContext<'tcx>, arg: &'tcx Expr<'_>) -> bool {
+    match arg.kind {
+        ExprKind::Lit(_) => true,
+        ExprKind::AddrOf(BorrowKind::Ref, _, array) if let ExprKind::Array(patterns) = array.kind => {
+            patterns.iter().all(|pattern| is_constant_pattern(cx, pattern))
+        },
+        _ => const_str(cx, arg).is_some(),
+    }
+}
+
+/// Checks whether the expression refers to `Self` or to generic parameters, which a static item
+/// inside the function can't use.
+fn refers_to_generics(expr: &Expr<'_>) -> bool {
+    struct V;
+    impl<'tcx> Visitor<'tcx> for V {
+        type Result = ControlFlow<()>;
+
+        fn visit_path(&mut self, path: &Path<'tcx>, _: HirId) -> Self::Result {
+            if matches!(
+                path.res,
+                Res::SelfTyParam { .. }
+                    | Res::SelfTyAlias { .. }
+                    | Res::SelfCtor(_)
+                    | Res::Def(DefKind::TyParam | DefKind::ConstParam, _)
+            ) {
+                ControlFlow::Break(())
+            } else {
+                walk_path(self, path)
+            }
+        }
+    }
+    V.visit_expr(expr).is_break()
+}
+
 fn lint_syntax_error(cx: &LateContext<'_>, error: &regex_syntax::Error, unescaped: &str, base: Span, offset: u8) {
     let parts: Option<(_, _, &dyn Display)> = match &error {
         regex_syntax::Error::Parse(e) => Some((e.span(), e.auxiliary_span(), e.kind())),
@@ -258,22 +511,76 @@ fn is_trivial_regex(s: &regex_syntax::hir::Hir) -> Option<&'static str> {
     }
 }
 
-fn check_set<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
+fn check_set<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, parser: &regex_syntax::ParserBuilder) {
     if let ExprKind::AddrOf(BorrowKind::Ref, _, expr) = expr.kind
         && let ExprKind::Array(exprs) = expr.kind
     {
         for expr in exprs {
-            check_regex(cx, expr, utf8);
+            check_regex(cx, expr, parser, false);
         }
     }
 }
 
-fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
-    let mut parser = regex_syntax::ParserBuilder::new().unicode(true).utf8(utf8).build();
+/// Applies the flags set on a regex builder which change how the pattern is parsed, e.g.
+/// `RegexBuilder::new(pattern).ignore_whitespace(true)`. Returns `None` if any of them isn't
+/// known.
+fn apply_builder_flags(
+    cx: &LateContext<'_>,
+    new_call: &Expr<'_>,
+    parser: &mut regex_syntax::ParserBuilder,
+) -> Option<()> {
+    let mut receiver = new_call;
+    while let Some(parent) = get_parent_expr(cx, receiver)
+        && let ExprKind::MethodCall(method, parent_receiver, [arg], _) = parent.kind
+        && parent_receiver.hir_id == receiver.hir_id
+    {
+        let set: fn(&mut regex_syntax::ParserBuilder, bool) -> &mut regex_syntax::ParserBuilder =
+            match method.ident.name {
+                sym::case_insensitive => regex_syntax::ParserBuilder::case_insensitive,
+                sym::crlf => regex_syntax::ParserBuilder::crlf,
+                sym::dot_matches_new_line => regex_syntax::ParserBuilder::dot_matches_new_line,
+                sym::ignore_whitespace => regex_syntax::ParserBuilder::ignore_whitespace,
+                sym::multi_line => regex_syntax::ParserBuilder::multi_line,
+                sym::swap_greed => regex_syntax::ParserBuilder::swap_greed,
+                sym::unicode => regex_syntax::ParserBuilder::unicode,
+                // Other settings, e.g. `size_limit`, don't change how the pattern is parsed
+                _ => {
+                    receiver = parent;
+                    continue;
+                },
+            };
+        let Some(Constant::Bool(enabled)) = ConstEvalCtxt::new(cx).eval(arg) else {
+            return None;
+        };
+        set(parser, enabled);
+        receiver = parent;
+    }
+    Some(())
+}
+
+/// Checks whether the pattern uses any of the extensions of `fancy-regex`, which `regex-syntax`
+/// can't parse.
+fn uses_fancy_syntax(pattern: &str) -> bool {
+    [
+        "(?=", "(?!", "(?<=", "(?<!", "(?>", "(?(", "\\k", "\\G", "\\K", "++", "*+", "?+", "}+",
+    ]
+    .iter()
+    .any(|ext| pattern.contains(ext))
+        || pattern
+            .as_bytes()
+            .windows(2)
+            .any(|w| w[0] == b'\\' && matches!(w[1], b'1'..=b'9'))
+}
+
+fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, parser: &regex_syntax::ParserBuilder, fancy: bool) {
+    let mut parser = parser.build();
 
     if let ExprKind::Lit(lit) = expr.kind {
         if let LitKind::Str(ref r, style) = lit.node {
             let r = r.as_str();
+            if fancy && uses_fancy_syntax(r) {
+                return;
+            }
             let offset = if let StrStyle::Raw(n) = style { 2 + n } else { 1 };
             match parser.parse(r) {
                 Ok(r) => {
@@ -284,7 +591,9 @@ fn check_regex<'tcx>(cx: &LateContext<'tcx>, expr: &'tcx Expr<'_>, utf8: bool) {
                 Err(e) => lint_syntax_error(cx, &e, r, expr.span, offset),
             }
         }
-    } else if let Some(r) = const_str(cx, expr) {
+    } else if let Some(r) = const_str(cx, expr)
+        && !(fancy && uses_fancy_syntax(&r))
+    {
         match parser.parse(&r) {
             Ok(r) => {
                 if let Some(repl) = is_trivial_regex(&r) {
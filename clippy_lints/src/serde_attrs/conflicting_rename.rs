@@ -0,0 +1,104 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::sym;
+use rustc_hir::{FieldDef, HirId};
+use rustc_lint::LateContext;
+use rustc_span::{Ident, Span};
+
+use super::utils::{RenameRule, SerdeMetaItems, field_name};
+use super::{SERDE_CONFLICTING_RENAME, SerdeVariant};
+
+/// A field or variant along with the name it's serialized as
+struct Named {
+    hir_id: HirId,
+    ident: Ident,
+    /// The span of an explicit `rename`
+    rename: Option<Span>,
+    name: String,
+}
+
+pub(super) fn check_fields(
+    cx: &LateContext<'_>,
+    fields: &[(&FieldDef<'_>, SerdeMetaItems)],
+    rename_all: Option<(RenameRule, Span)>,
+) {
+    let names: Vec<_> = fields
+        .iter()
+        .filter(|(_, attrs)| !attrs.has_word(sym::skip))
+        .filter_map(|(field, attrs)| {
+            Some(Named {
+                hir_id: field.hir_id,
+                ident: field.ident,
+                rename: attrs.value(sym::rename).map(|(_, span)| span),
+                name: field_name(field, attrs, rename_all.map(|(rule, _)| rule))?,
+            })
+        })
+        .collect();
+    check_names(cx, "field", &names, rename_all.map(|(_, span)| span));
+}
+
+pub(super) fn check_variants(
+    cx: &LateContext<'_>,
+    variants: &[SerdeVariant<'_, '_>],
+    rename_all: Option<(RenameRule, Span)>,
+) {
+    let names: Vec<_> = variants
+        .iter()
+        .filter(|variant| !variant.attrs.has_word(sym::skip))
+        .filter_map(|&SerdeVariant { variant, ref attrs, .. }| {
+            let (name, rename) = if let Some((name, span)) = attrs.value(sym::rename) {
+                (name.to_string(), Some(span))
+            } else if attrs.get(sym::rename).is_some() {
+                return None;
+            } else {
+                let name = variant.ident.name.as_str();
+                let name = rename_all.map_or_else(|| name.to_owned(), |(rule, _)| rule.apply_to_variant(name));
+                (name, None)
+            };
+            Some(Named {
+                hir_id: variant.hir_id,
+                ident: variant.ident,
+                rename,
+                name,
+            })
+        })
+        .collect();
+    check_names(cx, "variant", &names, rename_all.map(|(_, span)| span));
+}
+
+fn check_names(cx: &LateContext<'_>, kind: &str, names: &[Named], rename_all: Option<Span>) {
+    for (i, named) in names.iter().enumerate() {
+        let Some(rename) = named.rename else {
+            continue;
+        };
+        // Only report the later of two explicit renames
+        if let Some(other) = names
+            .iter()
+            .enumerate()
+            .find(|&(j, other)| j != i && other.name == named.name && (other.rename.is_none() || j < i))
+            .map(|(_, other)| other)
+        {
+            span_lint_hir_and_then(
+                cx,
+                SERDE_CONFLICTING_RENAME,
+                named.hir_id,
+                rename,
+                format!(
+                    "{kind} `{}` is renamed to `{}`, which is also the name of {kind} `{}`",
+                    named.ident, named.name, other.ident
+                ),
+                |diag| {
+                    if let Some(other_rename) = other.rename {
+                        diag.span_note(other_rename, format!("`{}` is renamed here", other.ident));
+                    } else if let Some(rename_all) = rename_all
+                        && other.ident.name.as_str() != other.name
+                    {
+                        diag.span_note(
+                            rename_all,
+                            format!("`{}` is renamed to `{}` by `rename_all`", other.ident, other.name),
+                        );
+                    }
+                },
+            );
+        }
+    }
+}
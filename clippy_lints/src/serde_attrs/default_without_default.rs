@@ -0,0 +1,62 @@
+use clippy_utils::diagnostics::{span_lint_and_help, span_lint_hir_and_then};
+use clippy_utils::sym;
+use clippy_utils::ty::implements_trait;
+use rustc_ast::MetaItem;
+use rustc_hir::{FieldDef, Item};
+use rustc_lint::LateContext;
+use rustc_middle::ty::TypeVisitableExt as _;
+use rustc_span::symbol::kw;
+
+use super::SERDE_DEFAULT_WITHOUT_DEFAULT;
+use super::utils::SerdeMetaItems;
+
+/// Checks a container level `#[serde(default)]`, which fills in missing fields from the
+/// `Default` implementation of the type.
+pub(super) fn check_container(cx: &LateContext<'_>, item: &Item<'_>, container: &SerdeMetaItems) {
+    if let Some(meta) = container.get(kw::Default)
+        && meta.is_word()
+        && let Some(default_trait) = cx.tcx.get_diagnostic_item(sym::Default)
+    {
+        let ty = cx.tcx.type_of(item.owner_id).instantiate_identity().skip_norm_wip();
+        if !implements_trait(cx, ty, default_trait, &[]) {
+            span_lint_and_help(
+                cx,
+                SERDE_DEFAULT_WITHOUT_DEFAULT,
+                meta.span,
+                format!("`#[serde(default)]` on `{ty}`, which doesn't implement `Default`"),
+                None,
+                "implement `Default` for the type, or put `#[serde(default)]` on the individual fields",
+            );
+        }
+    }
+}
+
+pub(super) fn check(cx: &LateContext<'_>, fields: &[(&FieldDef<'_>, SerdeMetaItems)]) {
+    let Some(default_trait) = cx.tcx.get_diagnostic_item(sym::Default) else {
+        return;
+    };
+    for (field, attrs) in fields {
+        let Some(meta) = attrs.get(kw::Default).filter(|meta| MetaItem::is_word(meta)) else {
+            continue;
+        };
+        let ty = cx.tcx.type_of(field.def_id).instantiate_identity().skip_norm_wip();
+        if implements_trait(cx, ty, default_trait, &[]) {
+            continue;
+        }
+        span_lint_hir_and_then(
+            cx,
+            SERDE_DEFAULT_WITHOUT_DEFAULT,
+            field.hir_id,
+            meta.span,
+            format!("`#[serde(default)]` on a field of type `{ty}`, which doesn't implement `Default`"),
+            |diag| {
+                if ty.has_param() {
+                    diag.note(format!(
+                        "the derived `Deserialize` implementation silently requires `{ty}: Default`"
+                    ));
+                }
+                diag.help("use `#[serde(default = \"...\")]` with a function which creates the value");
+            },
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::sym;
+use rustc_hir::FieldDef;
+use rustc_lint::LateContext;
+
+use super::SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN;
+use super::utils::SerdeMetaItems;
+
+pub(super) fn check(cx: &LateContext<'_>, container: &SerdeMetaItems, fields: &[(&FieldDef<'_>, SerdeMetaItems)]) {
+    for (field, attrs) in fields {
+        let Some(flatten) = attrs.get(sym::flatten) else {
+            continue;
+        };
+
+        // Either the outer type or the flattened one can deny unknown fields
+        let deny = container
+            .get(sym::deny_unknown_fields)
+            .map(|meta| (meta.span, "here"))
+            .or_else(|| {
+                let adt = cx
+                    .tcx
+                    .type_of(field.def_id)
+                    .instantiate_identity()
+                    .skip_norm_wip()
+                    .ty_adt_def()?;
+                let local_id = adt.did().as_local()?;
+                let flattened = SerdeMetaItems::of(cx, cx.tcx.local_def_id_to_hir_id(local_id));
+                Some((flattened.get(sym::deny_unknown_fields)?.span, "by the flattened type"))
+            });
+
+        if let Some((deny_span, location)) = deny {
+            span_lint_hir_and_then(
+                cx,
+                SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+                field.hir_id,
+                flatten.span,
+                "`#[serde(flatten)]` is not supported together with `#[serde(deny_unknown_fields)]`",
+                |diag| {
+                    diag.span_note(deny_span, format!("unknown fields are denied {location}"));
+                },
+            );
+        }
+    }
+}
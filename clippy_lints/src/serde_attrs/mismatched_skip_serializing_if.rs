@@ -0,0 +1,65 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::source::snippet;
+use clippy_utils::sym;
+use clippy_utils::ty::deref_chain;
+use rustc_hir::FieldDef;
+use rustc_lint::LateContext;
+use rustc_middle::ty;
+use rustc_span::Symbol;
+
+use super::SERDE_MISMATCHED_SKIP_SERIALIZING_IF;
+use super::utils::SerdeMetaItems;
+
+/// Types commonly named by `skip_serializing_if`, e.g. `Option::is_none` or `Vec::is_empty`.
+const KNOWN_TYPES: [Symbol; 8] = [
+    sym::BTreeMap,
+    sym::BTreeSet,
+    sym::HashMap,
+    sym::HashSet,
+    sym::Option,
+    sym::String,
+    sym::Vec,
+    sym::VecDeque,
+];
+
+pub(super) fn check(cx: &LateContext<'_>, fields: &[(&FieldDef<'_>, SerdeMetaItems)]) {
+    for (field, attrs) in fields {
+        let Some((path, span)) = attrs.value(sym::skip_serializing_if) else {
+            continue;
+        };
+        let path = path.as_str();
+        let Some((ty_path, _)) = path.rsplit_once("::") else {
+            continue;
+        };
+        // `Vec::<u8>::is_empty`
+        let ty_path = ty_path.split("::<").next().unwrap_or(ty_path);
+        let ty_name = ty_path.rsplit("::").next().unwrap_or(ty_path);
+
+        if let Some(&name) = KNOWN_TYPES.iter().find(|name| name.as_str() == ty_name)
+            && let Some(expected) = cx.tcx.get_diagnostic_item(name)
+        {
+            let field_ty = cx.tcx.type_of(field.def_id).instantiate_identity().skip_norm_wip();
+            // The function is called with `&field`, which can deref-coerce to its parameter
+            let matches = deref_chain(cx, field_ty).any(|ty| match ty.kind() {
+                ty::Adt(adt, _) => adt.did() == expected,
+                ty::Param(_) | ty::Alias(..) => true,
+                _ => false,
+            });
+            if !matches {
+                span_lint_hir_and_then(
+                    cx,
+                    SERDE_MISMATCHED_SKIP_SERIALIZING_IF,
+                    field.hir_id,
+                    span,
+                    format!(
+                        "`skip_serializing_if` function `{path}` can't be called on a field of type `{}`",
+                        snippet(cx, field.ty.span, "_")
+                    ),
+                    |diag| {
+                        diag.note("the function is only type-checked when `Serialize` is derived");
+                    },
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+mod conflicting_rename;
+mod default_without_default;
+mod deny_unknown_fields_with_flatten;
+mod mismatched_skip_serializing_if;
+mod shadowed_untagged_variant;
+mod utils;
+
+use clippy_utils::sym;
+use rustc_hir::{FieldDef, Item, ItemKind, Variant};
+use rustc_lint::{LateContext, LateLintPass};
+use rustc_session::declare_lint_pass;
+use rustc_span::Span;
+use utils::{RenameRule, SerdeMetaItems};
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[serde(rename = "...")]` giving a field or variant the same name as another
+    /// one, including names produced by `rename_all`.
+    ///
+    /// ### Why is this bad?
+    /// Both fields are read from and written to the same key, so the serialized output contains
+    /// a duplicate key and only one of the fields can be deserialized.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// #[derive(Serialize, Deserialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct User {
+    ///     user_id: u64,
+    ///     #[serde(rename = "userId")]
+    ///     legacy_id: u64,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[derive(Serialize, Deserialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct User {
+    ///     user_id: u64,
+    ///     #[serde(rename = "legacyUserId")]
+    ///     legacy_id: u64,
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub SERDE_CONFLICTING_RENAME,
+    suspicious,
+    "`#[serde(rename)]` clashing with the name of another field or variant"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[serde(default)]` on fields whose type doesn't implement `Default`, and on
+    /// types which don't implement `Default` themselves.
+    ///
+    /// ### Why is this bad?
+    /// For a generic field, the derived `Deserialize` implementation silently gains a
+    /// `T: Default` bound, so the type can't be deserialized at all for other `T`s. Without a
+    /// derived `Deserialize` implementation the attribute isn't checked by the compiler and only
+    /// breaks once deserialization is derived.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// struct Page<T> {
+    ///     #[serde(default)]
+    ///     items: T,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// struct Page<T> {
+    ///     items: T,
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub SERDE_DEFAULT_WITHOUT_DEFAULT,
+    suspicious,
+    "`#[serde(default)]` on a field or type which doesn't implement `Default`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[serde(flatten)]` fields in a type with `#[serde(deny_unknown_fields)]`, or
+    /// whose own type has `#[serde(deny_unknown_fields)]`.
+    ///
+    /// ### Why is this bad?
+    /// serde doesn't support the combination: the outer type rejects the fields meant for the
+    /// flattened one, or the flattened type rejects the fields of the outer one.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// #[serde(deny_unknown_fields)]
+    /// struct Request {
+    ///     id: u64,
+    ///     #[serde(flatten)]
+    ///     extra: HashMap<String, Value>,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// struct Request {
+    ///     id: u64,
+    ///     #[serde(flatten)]
+    ///     extra: HashMap<String, Value>,
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+    suspicious,
+    "`#[serde(flatten)]` combined with `#[serde(deny_unknown_fields)]`"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for `#[serde(skip_serializing_if = "...")]` naming a method of a standard library
+    /// type, e.g. `Option::is_none`, on a field of a different type.
+    ///
+    /// ### Why is this bad?
+    /// The path is only type-checked when `Serialize` is derived for the type. When the derive is
+    /// missing or disabled, e.g. behind a `cfg_attr`, a mismatched path goes unnoticed until it
+    /// breaks the build.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// #[cfg_attr(feature = "serde", derive(Serialize))]
+    /// struct Tags {
+    ///     #[serde(skip_serializing_if = "Option::is_none")]
+    ///     names: Vec<String>,
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[cfg_attr(feature = "serde", derive(Serialize))]
+    /// struct Tags {
+    ///     #[serde(skip_serializing_if = "Vec::is_empty")]
+    ///     names: Vec<String>,
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub SERDE_MISMATCHED_SKIP_SERIALIZING_IF,
+    suspicious,
+    "`#[serde(skip_serializing_if)]` function which doesn't take the type of the field"
+}
+
+declare_clippy_lint! {
+    /// ### What it does
+    /// Checks for variants of a `#[serde(untagged)]` enum which can't be deserialized because an
+    /// earlier variant accepts the same input.
+    ///
+    /// ### Why is this bad?
+    /// serde tries the variants of an untagged enum in order and picks the first one that
+    /// deserializes. A struct variant ignores unknown fields and fills in missing `Option` fields
+    /// with `None`, so it can accept the input of a later, more specific variant.
+    ///
+    /// ### Example
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// #[serde(untagged)]
+    /// enum Event {
+    ///     Ping { id: u64 },
+    ///     Message { id: u64, text: String },
+    /// }
+    /// ```
+    /// Use instead:
+    /// ```rust,ignore
+    /// #[derive(Deserialize)]
+    /// #[serde(untagged)]
+    /// enum Event {
+    ///     Message { id: u64, text: String },
+    ///     Ping { id: u64 },
+    /// }
+    /// ```
+    #[clippy::version = "1.99.0"]
+    pub SERDE_SHADOWED_UNTAGGED_VARIANT,
+    suspicious,
+    "variant of an untagged enum which is shadowed by an earlier variant"
+}
+
+declare_lint_pass!(SerdeAttrs => [
+    SERDE_CONFLICTING_RENAME,
+    SERDE_DEFAULT_WITHOUT_DEFAULT,
+    SERDE_DENY_UNKNOWN_FIELDS_WITH_FLATTEN,
+    SERDE_MISMATCHED_SKIP_SERIALIZING_IF,
+    SERDE_SHADOWED_UNTAGGED_VARIANT,
+]);
+
+/// A variant of an enum along with the serde attributes of it and its fields
+struct SerdeVariant<'a, 'tcx> {
+    variant: &'a Variant<'tcx>,
+    attrs: SerdeMetaItems,
+    fields: Vec<(&'a FieldDef<'tcx>, SerdeMetaItems)>,
+    /// The `rename_all` rule which applies to the fields
+    rename_all: Option<(RenameRule, Span)>,
+}
+
+fn fields_with_attrs<'a, 'tcx>(
+    cx: &LateContext<'_>,
+    fields: &'a [FieldDef<'tcx>],
+) -> Vec<(&'a FieldDef<'tcx>, SerdeMetaItems)> {
+    fields
+        .iter()
+        .map(|field| (field, SerdeMetaItems::of(cx, field.hir_id)))
+        .collect()
+}
+
+impl<'tcx> LateLintPass<'tcx> for SerdeAttrs {
+    fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx Item<'_>) {
+        if item.span.from_expansion() {
+            return;
+        }
+        match item.kind {
+            ItemKind::Struct(_, _, ref data) => {
+                let container = SerdeMetaItems::of(cx, item.hir_id());
+                let fields = fields_with_attrs(cx, data.fields());
+                let rename_all = RenameRule::from_attr(&container, sym::rename_all);
+
+                default_without_default::check_container(cx, item, &container);
+                default_without_default::check(cx, &fields);
+                deny_unknown_fields_with_flatten::check(cx, &container, &fields);
+                mismatched_skip_serializing_if::check(cx, &fields);
+                conflicting_rename::check_fields(cx, &fields, rename_all);
+            },
+            ItemKind::Enum(_, _, ref def) => {
+                let container = SerdeMetaItems::of(cx, item.hir_id());
+                let rename_all_fields = RenameRule::from_attr(&container, sym::rename_all_fields);
+                let variants: Vec<_> = def
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let attrs = SerdeMetaItems::of(cx, variant.hir_id);
+                        let fields = fields_with_attrs(cx, variant.data.fields());
+                        let rename_all = RenameRule::from_attr(&attrs, sym::rename_all).or(rename_all_fields);
+
+                        default_without_default::check(cx, &fields);
+                        mismatched_skip_serializing_if::check(cx, &fields);
+                        conflicting_rename::check_fields(cx, &fields, rename_all);
+                        SerdeVariant {
+                            variant,
+                            attrs,
+                            fields,
+                            rename_all,
+                        }
+                    })
+                    .collect();
+
+                conflicting_rename::check_variants(cx, &variants, RenameRule::from_attr(&container, sym::rename_all));
+                if container.has_word(sym::untagged) {
+                    shadowed_untagged_variant::check(cx, &container, &variants);
+                }
+            },
+            _ => {},
+        }
+    }
+}
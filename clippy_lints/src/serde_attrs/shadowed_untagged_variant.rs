@@ -0,0 +1,119 @@
+use clippy_utils::diagnostics::span_lint_hir_and_then;
+use clippy_utils::res::MaybeDef as _;
+use clippy_utils::sym;
+use rustc_hir::VariantData;
+use rustc_lint::LateContext;
+use rustc_middle::ty::{self, Ty};
+use rustc_span::symbol::kw;
+
+use super::utils::{SerdeMetaItems, field_name};
+use super::{SERDE_SHADOWED_UNTAGGED_VARIANT, SerdeVariant};
+
+/// The input a variant of an untagged enum accepts
+enum Shape<'tcx> {
+    Unit,
+    Tuple(Vec<Ty<'tcx>>),
+    Struct(Vec<StructField<'tcx>>),
+}
+
+struct StructField<'tcx> {
+    name: String,
+    ty: Ty<'tcx>,
+    /// Whether the field may be missing from the input
+    optional: bool,
+}
+
+impl<'tcx> Shape<'tcx> {
+    /// Gets the shape of the variant, or `None` if custom deserialization is involved.
+    fn of(cx: &LateContext<'tcx>, variant: &SerdeVariant<'_, '_>) -> Option<Self> {
+        if variant.attrs.is_skipped() || variant.attrs.has_custom_with() {
+            return None;
+        }
+        let field_ty = |def_id| cx.tcx.type_of(def_id).instantiate_identity().skip_norm_wip();
+        match variant.variant.data {
+            VariantData::Unit(..) => Some(Self::Unit),
+            VariantData::Tuple(..) => variant
+                .fields
+                .iter()
+                .map(|(field, attrs)| (!attrs.is_skipped() && !attrs.has_custom_with()).then(|| field_ty(field.def_id)))
+                .collect::<Option<_>>()
+                .map(Self::Tuple),
+            VariantData::Struct { .. } => variant
+                .fields
+                .iter()
+                .filter(|(_, attrs)| !attrs.is_skipped())
+                .map(|(field, attrs)| {
+                    if attrs.has_custom_with() {
+                        return None;
+                    }
+                    let ty = field_ty(field.def_id);
+                    Some(StructField {
+                        name: field_name(field, attrs, variant.rename_all.map(|(rule, _)| rule))?,
+                        ty,
+                        optional: attrs.get(kw::Default).is_some() || ty.is_diag_item(cx, sym::Option),
+                    })
+                })
+                .collect::<Option<_>>()
+                .map(Self::Struct),
+        }
+    }
+
+    /// Checks whether any input accepted by `later` is also accepted by `self`.
+    fn covers(&self, cx: &LateContext<'_>, later: &Self, deny_unknown_fields: bool) -> bool {
+        match (self, later) {
+            (Self::Unit, Self::Unit) => true,
+            (Self::Tuple(tys), Self::Tuple(later_tys)) => tys == later_tys,
+            (Self::Struct(fields), Self::Struct(later_fields)) => {
+                fields.iter().all(|field| {
+                    match later_fields.iter().find(|later_field| later_field.name == field.name) {
+                        Some(later_field) => {
+                            field.ty == later_field.ty
+                                || (field.optional
+                                    && field.ty.is_diag_item(cx, sym::Option)
+                                    && matches!(field.ty.kind(), ty::Adt(_, args)
+                                        if args.types().next() == Some(later_field.ty)))
+                        },
+                        None => field.optional,
+                    }
+                }) && (!deny_unknown_fields
+                    || later_fields
+                        .iter()
+                        .all(|later_field| fields.iter().any(|field| field.name == later_field.name)))
+            },
+            _ => false,
+        }
+    }
+}
+
+pub(super) fn check(cx: &LateContext<'_>, container: &SerdeMetaItems, variants: &[SerdeVariant<'_, '_>]) {
+    let deny_unknown_fields = container.has_word(sym::deny_unknown_fields);
+    let shapes: Vec<_> = variants.iter().map(|variant| Shape::of(cx, variant)).collect();
+
+    for (i, (variant, shape)) in variants.iter().zip(&shapes).enumerate() {
+        let Some(shape) = shape else {
+            continue;
+        };
+        if let Some((earlier, _)) = variants[..i].iter().zip(&shapes[..i]).find(|(_, earlier)| {
+            earlier
+                .as_ref()
+                .is_some_and(|earlier| earlier.covers(cx, shape, deny_unknown_fields))
+        }) {
+            span_lint_hir_and_then(
+                cx,
+                SERDE_SHADOWED_UNTAGGED_VARIANT,
+                variant.variant.hir_id,
+                variant.variant.span,
+                format!(
+                    "variant `{}` of an untagged enum is shadowed by the earlier variant `{}`",
+                    variant.variant.ident, earlier.variant.ident
+                ),
+                |diag| {
+                    diag.span_note(
+                        earlier.variant.span,
+                        "variants are tried in order, and this one accepts the same input",
+                    );
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use clippy_utils::sym;
+use rustc_ast::{MetaItem, MetaItemInner};
+use rustc_hir::{Attribute, FieldDef, HirId};
+use rustc_lint::LateContext;
+use rustc_span::{Span, Symbol};
+
+/// The items of all `#[serde(...)]` attributes on a node.
+pub(super) struct SerdeMetaItems(Vec<MetaItem>);
+
+impl SerdeMetaItems {
+    pub(super) fn of(cx: &LateContext<'_>, hir_id: HirId) -> Self {
+        Self(
+            cx.tcx
+                .hir_attrs(hir_id)
+                .iter()
+                .filter(|attr| attr.has_name(sym::serde))
+                .filter_map(Attribute::meta_item_list)
+                .flatten()
+                .filter_map(|meta| match meta {
+                    MetaItemInner::MetaItem(meta) => Some(meta),
+                    MetaItemInner::Lit(_) => None,
+                })
+                .collect(),
+        )
+    }
+
+    pub(super) fn get(&self, name: Symbol) -> Option<&MetaItem> {
+        self.0.iter().find(|meta| meta.has_name(name))
+    }
+
+    /// Checks for a bare flag such as `#[serde(flatten)]`.
+    pub(super) fn has_word(&self, name: Symbol) -> bool {
+        self.get(name).is_some_and(MetaItem::is_word)
+    }
+
+    /// Gets the value of a `name = "value"` item along with the span of the item.
+    pub(super) fn value(&self, name: Symbol) -> Option<(Symbol, Span)> {
+        self.get(name).and_then(|meta| Some((meta.value_str()?, meta.span)))
+    }
+
+    /// Checks whether the (de)serialization of the field or variant is replaced by a custom
+    /// function, in which case its type says nothing about the accepted input.
+    pub(super) fn has_custom_with(&self) -> bool {
+        self.get(sym::with).is_some() || self.get(sym::deserialize_with).is_some()
+    }
+
+    /// Checks whether the field or variant is never deserialized.
+    pub(super) fn is_skipped(&self) -> bool {
+        self.has_word(sym::skip) || self.has_word(sym::skip_deserializing)
+    }
+}
+
+/// The case conversions supported by `rename_all`.
+#[derive(Clone, Copy)]
+pub(super) enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    pub(super) fn from_attr(attrs: &SerdeMetaItems, name: Symbol) -> Option<(Self, Span)> {
+        let (value, span) = attrs.value(name)?;
+        let rule = match value {
+            sym::lowercase => Self::Lower,
+            sym::UPPERCASE => Self::Upper,
+            sym::PascalCase => Self::Pascal,
+            sym::camelCase => Self::Camel,
+            sym::snake_case => Self::Snake,
+            sym::SCREAMING_SNAKE_CASE => Self::ScreamingSnake,
+            sym::kebab_case => Self::Kebab,
+            sym::SCREAMING_KEBAB_CASE => Self::ScreamingKebab,
+            _ => return None,
+        };
+        Some((rule, span))
+    }
+
+    /// Applies the rule to a `snake_case` field name the same way serde does.
+    pub(super) fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_owned(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::with_capacity(field.len());
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            },
+            Self::Camel => lower_first(&Self::Pascal.apply_to_field(field)),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => field.to_ascii_uppercase().replace('_', "-"),
+        }
+    }
+
+    /// Applies the rule to a `PascalCase` variant name the same way serde does.
+    pub(super) fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_owned(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => lower_first(variant),
+            Self::Snake => {
+                let mut snake = String::with_capacity(variant.len() + 4);
+                for (i, c) in variant.char_indices() {
+                    if i > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            },
+            Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply_to_variant(variant).replace('_', "-"),
+        }
+    }
+}
+
+fn lower_first(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// The name a field is deserialized from, if it has a single one.
+pub(super) fn field_name(
+    field: &FieldDef<'_>,
+    attrs: &SerdeMetaItems,
+    rename_all: Option<RenameRule>,
+) -> Option<String> {
+    if let Some((name, _)) = attrs.value(sym::rename) {
+        Some(name.to_string())
+    } else if attrs.get(sym::rename).is_some() || attrs.has_word(sym::flatten) || field.is_positional() {
+        None
+    } else {
+        let name = field.ident.name.as_str();
+        Some(rename_all.map_or_else(|| name.to_owned(), |rule| rule.apply_to_field(name)))
+    }
+}
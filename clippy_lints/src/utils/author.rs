@@ -4,10 +4,11 @@ use itertools::Itertools as _;
 use rustc_ast::LitIntType;
 use rustc_ast::ast::{LitFloatType, LitKind};
 use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::Res;
 use rustc_hir::def_id::DefId;
 use rustc_hir::{
-    self as hir, BindingMode, Body, CaptureBy, Closure, ClosureKind, ConstArg, ConstArgKind, CoroutineKind, ExprKind,
-    FnRetTy, HirId, Lit, PatExprKind, PatKind, QPath, StmtKind, StructTailExpr,
+    self as hir, BindingMode, Body, BorrowKind, CaptureBy, Closure, ClosureKind, ConstArg, ConstArgKind, CoroutineKind,
+    ExprKind, FnRetTy, HirId, Lit, PatExprKind, PatKind, QPath, StmtKind, StructTailExpr,
 };
 use rustc_lint::{LateContext, LateLintPass};
 use rustc_middle::ty::{FloatTy, IntTy, TypeckResults, UintTy};
@@ -48,6 +49,15 @@ declare_lint_pass!(
     ///     // report your lint here
     /// }
     /// ```
+    ///
+    /// With `#[clippy::author = "pattern"]`, a `clippy_utils::matcher` pattern is printed instead:
+    ///
+    /// ```rust,ignore
+    /// let pattern = binary(BinOpKind::Eq, bind("x"), lit_int(42));
+    /// if let Some(bindings) = pattern.matches(cx, expr) {
+    ///     // report your lint here
+    /// }
+    /// ```
     Author => []
 );
 
@@ -113,23 +123,25 @@ impl<'tcx> LateLintPass<'tcx> for Author {
     }
 
     fn check_arm(&mut self, cx: &LateContext<'tcx>, arm: &'tcx hir::Arm<'_>) {
-        check_node(cx, arm.hir_id, |v| {
+        check_node(cx, arm.hir_id, Some(arm.body), |v| {
             v.arm(&v.bind("arm", arm));
         });
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
-        check_node(cx, expr.hir_id, |v| {
+        check_node(cx, expr.hir_id, Some(expr), |v| {
             v.expr(&v.bind("expr", expr));
         });
     }
 
     fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx hir::Stmt<'_>) {
-        match stmt.kind {
+        let expr = match stmt.kind {
             StmtKind::Expr(e) | StmtKind::Semi(e) if has_attr(cx, e.hir_id) => return,
-            _ => {},
-        }
-        check_node(cx, stmt.hir_id, |v| {
+            StmtKind::Expr(e) | StmtKind::Semi(e) => Some(e),
+            StmtKind::Let(local) => local.init,
+            StmtKind::Item(_) => None,
+        };
+        check_node(cx, stmt.hir_id, expr, |v| {
             v.stmt(&v.bind("stmt", stmt));
         });
     }
@@ -140,6 +152,7 @@ fn check_item(cx: &LateContext<'_>, hir_id: HirId) {
         check_node_with_body(
             cx,
             hir_id,
+            Some(body.value),
             |v| {
                 v.expr(&v.bind("expr", body.value));
             },
@@ -148,24 +161,41 @@ fn check_item(cx: &LateContext<'_>, hir_id: HirId) {
     }
 }
 
-fn check_node(cx: &LateContext<'_>, hir_id: HirId, f: impl Fn(&PrintVisitor<'_, '_>)) {
-    check_node_with_body(cx, hir_id, f, None);
+fn check_node(cx: &LateContext<'_>, hir_id: HirId, expr: Option<&hir::Expr<'_>>, f: impl Fn(&PrintVisitor<'_, '_>)) {
+    check_node_with_body(cx, hir_id, expr, f, None);
 }
 
 /// Check the node at `hir_id`, in the context of `body` or the default from `cx` if none is given.
+/// `expr` is the expression printed by `#[clippy::author = "pattern"]`.
 fn check_node_with_body(
     cx: &LateContext<'_>,
     hir_id: HirId,
+    expr: Option<&hir::Expr<'_>>,
     f: impl Fn(&PrintVisitor<'_, '_>),
     body: Option<&Body<'_>>,
 ) {
-    if has_attr(cx, hir_id) {
-        let typeck_results = body.map_or_else(|| cx.typeck_results(), |body| cx.tcx.typeck_body(body.id()));
-        f(&PrintVisitor::new(cx, typeck_results));
-        println!("{{");
-        println!("    // report your lint here");
-        println!("}}");
+    let Some(mode) = author_mode(cx, hir_id) else {
+        return;
+    };
+    let typeck_results = body.map_or_else(|| cx.typeck_results(), |body| cx.tcx.typeck_body(body.id()));
+    match mode {
+        AuthorMode::Chain => {
+            f(&PrintVisitor::new(cx, typeck_results));
+            println!("{{");
+            println!("    // report your lint here");
+        },
+        AuthorMode::Pattern => {
+            let Some(expr) = expr else {
+                println!("// only expressions can be printed as a pattern");
+                return;
+            };
+            let pattern = PatternPrinter { cx, typeck_results }.expr(expr);
+            println!("let pattern = {};", pattern.render(0, "let pattern = ;".len()));
+            println!("if let Some(bindings) = pattern.matches(cx, expr) {{");
+            println!("    // report your lint here");
+        },
     }
+    println!("}}");
 }
 
 fn paths_static_name(cx: &LateContext<'_>, id: DefId) -> String {
@@ -879,7 +909,153 @@ impl<'a, 'tcx> PrintVisitor<'a, 'tcx> {
     }
 }
 
-fn has_attr(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+/// What `#[clippy::author]` prints
+enum AuthorMode {
+    /// A chain of `if let` conditions, the default
+    Chain,
+    /// A `clippy_utils::matcher` pattern, with `#[clippy::author = "pattern"]`
+    Pattern,
+}
+
+fn author_mode(cx: &LateContext<'_>, hir_id: HirId) -> Option<AuthorMode> {
     let attrs = cx.tcx.hir_attrs(hir_id);
-    get_builtin_attr(attrs, sym::author).count() > 0
+    get_builtin_attr(attrs, sym::author).next().map(|attr| {
+        if attr.value_str() == Some(sym::pattern) {
+            AuthorMode::Pattern
+        } else {
+            AuthorMode::Chain
+        }
+    })
+}
+
+fn has_attr(cx: &LateContext<'_>, hir_id: HirId) -> bool {
+    author_mode(cx, hir_id).is_some()
+}
+
+/// A `clippy_utils::matcher` combinator call, printed on one line if it's short enough
+enum PatternCode {
+    Atom(String),
+    Call(&'static str, Vec<PatternCode>),
+    List(Vec<PatternCode>),
+}
+
+impl PatternCode {
+    const MAX_WIDTH: usize = 100;
+
+    fn call(name: &'static str, args: impl IntoIterator<Item = Self>) -> Self {
+        Self::Call(name, args.into_iter().collect())
+    }
+
+    fn atom(code: impl Display) -> Self {
+        Self::Atom(code.to_string())
+    }
+
+    /// Renders the code at `indent`, with `used` columns of the first line already taken.
+    fn render(&self, indent: usize, used: usize) -> String {
+        let (open, close, items) = match self {
+            Self::Atom(code) => return code.clone(),
+            // Keep the brackets of a single list argument on the line of the call
+            Self::Call(name, args) if let [Self::List(items)] = &**args => (format!("{name}(["), "])", items),
+            Self::Call(name, args) => (format!("{name}("), ")", args),
+            Self::List(items) => ("[".to_owned(), "]", items),
+        };
+        let flat = format!("{open}{}{close}", items.iter().map(|item| item.render(0, 0)).join(", "));
+        if used + flat.len() <= Self::MAX_WIDTH || items.is_empty() {
+            return flat;
+        }
+        let inner = " ".repeat(indent + 4);
+        let mut out = open;
+        for item in items {
+            out.push('\n');
+            out.push_str(&inner);
+            // One more column for the comma
+            out.push_str(&item.render(indent + 4, indent + 5));
+            out.push(',');
+        }
+        out.push('\n');
+        out.push_str(&" ".repeat(indent));
+        out.push_str(close);
+        out
+    }
+}
+
+/// Prints the `clippy_utils::matcher` pattern of an expression. Local variables become bindings,
+/// and expressions the matcher doesn't support become `any()`.
+struct PatternPrinter<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: &'tcx TypeckResults<'tcx>,
+}
+
+impl PatternPrinter<'_, '_> {
+    fn exprs(&self, exprs: &[hir::Expr<'_>]) -> PatternCode {
+        PatternCode::List(exprs.iter().map(|e| self.expr(e)).collect())
+    }
+
+    fn expr(&self, expr: &hir::Expr<'_>) -> PatternCode {
+        match expr.kind {
+            ExprKind::DropTemps(inner) => self.expr(inner),
+            ExprKind::Path(_) => self.path(expr),
+            ExprKind::Lit(lit) => match lit.node {
+                LitKind::Int(value, _) => PatternCode::atom(format_args!("lit_int({value})")),
+                LitKind::Float(value, _) => PatternCode::atom(format_args!("lit_float({:?})", value.as_str())),
+                LitKind::Str(value, _) => PatternCode::atom(format_args!("lit_str({:?})", value.as_str())),
+                LitKind::Char(value) => PatternCode::atom(format_args!("lit_char({value:?})")),
+                LitKind::Bool(value) => PatternCode::atom(format_args!("lit_bool({value})")),
+                _ => PatternCode::atom("any()"),
+            },
+            ExprKind::Call(callee, args) => PatternCode::call("call", [self.expr(callee), self.exprs(args)]),
+            ExprKind::MethodCall(segment, receiver, args, _) => PatternCode::call(
+                "method_call",
+                [
+                    PatternCode::atom(format_args!("{:?}", segment.ident.as_str())),
+                    self.expr(receiver),
+                    self.exprs(args),
+                ],
+            ),
+            ExprKind::Field(base, ident) => PatternCode::call(
+                "field",
+                [self.expr(base), PatternCode::atom(format_args!("{:?}", ident.as_str()))],
+            ),
+            ExprKind::Unary(op, inner) => PatternCode::call(
+                "unary",
+                [PatternCode::atom(format_args!("UnOp::{op:?}")), self.expr(inner)],
+            ),
+            ExprKind::Binary(op, lhs, rhs) => PatternCode::call(
+                "binary",
+                [
+                    PatternCode::atom(format_args!("BinOpKind::{:?}", op.node)),
+                    self.expr(lhs),
+                    self.expr(rhs),
+                ],
+            ),
+            ExprKind::AddrOf(BorrowKind::Ref, mutbl, inner) => PatternCode::call(
+                "addr_of",
+                [
+                    PatternCode::atom(format_args!("Mutability::{mutbl:?}")),
+                    self.expr(inner),
+                ],
+            ),
+            ExprKind::Index(base, index, _) => PatternCode::call("index", [self.expr(base), self.expr(index)]),
+            ExprKind::Tup(exprs) => PatternCode::call("tup", [self.exprs(exprs)]),
+            ExprKind::Array(exprs) => PatternCode::call("array", [self.exprs(exprs)]),
+            _ => PatternCode::atom("any()"),
+        }
+    }
+
+    fn path(&self, expr: &hir::Expr<'_>) -> PatternCode {
+        match expr.res(self.typeck_results) {
+            Res::Local(id) => PatternCode::atom(format_args!("bind({:?})", self.cx.tcx.hir_name(id).as_str())),
+            Res::Def(_, id) if !id.is_local() => {
+                if let Some(lang) = self.cx.tcx.lang_items().from_def_id(id) {
+                    PatternCode::atom(format_args!("lang_item(LangItem::{})", lang.name()))
+                } else if let Some(name) = self.cx.tcx.get_diagnostic_name(id) {
+                    PatternCode::atom(format_args!("diag_item(sym::{name})"))
+                } else {
+                    // Add the path to `clippy_utils::paths` if needed
+                    PatternCode::atom(format_args!("path(&paths::{})", paths_static_name(self.cx, id)))
+                }
+            },
+            _ => PatternCode::atom("any()"),
+        }
+    }
 }
@@ -1,14 +1,34 @@
+use clippy_utils::res::MaybeQPath as _;
+use clippy_utils::source::snippet;
 use clippy_utils::{get_builtin_attr, sym};
 use hir::TraitItem;
+use itertools::Itertools as _;
 use rustc_hir as hir;
+use rustc_hir::def::Res;
+use rustc_hir::def_id::LocalDefId;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_hir::{BodyOwnerKind, ExprKind, Node, PatKind, StmtKind};
 use rustc_lint::{LateContext, LateLintPass};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::mir;
+use rustc_middle::ty::TypeckResults;
 use rustc_session::declare_lint_pass;
+use rustc_span::Span;
+use serde::Serialize;
 
 declare_lint_pass!(
     /// ### What it does
     /// It formats the attached node with `{:#?}` and writes the result to the
     /// standard output. This is intended for debugging.
     ///
+    /// With arguments, it writes a JSON object instead, holding the sections named by the
+    /// arguments:
+    /// - `hir`: the tree of the node, with the resolved paths
+    /// - `types`: the types of the expressions and bindings of the node, in the order of the tree
+    /// - `mir`: the optimized MIR of the enclosing body, or of the item itself
+    ///
+    /// The THIR isn't available, as it's consumed when building the MIR.
+    ///
     /// ### Examples
     /// ```rs
     /// #[clippy::dump]
@@ -18,42 +38,57 @@ declare_lint_pass!(
     /// fn foo(input: u32) -> u64 {
     ///     input as u64
     /// }
+    ///
+    /// #[clippy::dump(hir, types, mir)]
+    /// fn bar(input: u32) -> u64 {
+    ///     input as u64
+    /// }
     /// ```
     DumpHir => []
 );
 
 impl<'tcx> LateLintPass<'tcx> for DumpHir {
     fn check_item(&mut self, cx: &LateContext<'tcx>, item: &'tcx hir::Item<'_>) {
-        if has_attr(cx, item.hir_id()) {
-            println!("{item:#?}");
+        match dump_kind(cx, item.hir_id()) {
+            Some(DumpKind::Debug) => println!("{item:#?}"),
+            Some(DumpKind::Json(sections)) => dump_json(cx, item.hir_id(), sections),
+            None => {},
         }
     }
 
     fn check_expr(&mut self, cx: &LateContext<'tcx>, expr: &'tcx hir::Expr<'_>) {
-        if has_attr(cx, expr.hir_id) {
-            println!("{expr:#?}");
+        match dump_kind(cx, expr.hir_id) {
+            Some(DumpKind::Debug) => println!("{expr:#?}"),
+            Some(DumpKind::Json(sections)) => dump_json(cx, expr.hir_id, sections),
+            None => {},
         }
     }
 
     fn check_stmt(&mut self, cx: &LateContext<'tcx>, stmt: &'tcx hir::Stmt<'_>) {
         match stmt.kind {
-            hir::StmtKind::Expr(e) | hir::StmtKind::Semi(e) if has_attr(cx, e.hir_id) => return,
+            StmtKind::Expr(e) | StmtKind::Semi(e) if has_attr(cx, e.hir_id) => return,
             _ => {},
         }
-        if has_attr(cx, stmt.hir_id) {
-            println!("{stmt:#?}");
+        match dump_kind(cx, stmt.hir_id) {
+            Some(DumpKind::Debug) => println!("{stmt:#?}"),
+            Some(DumpKind::Json(sections)) => dump_json(cx, stmt.hir_id, sections),
+            None => {},
         }
     }
 
     fn check_trait_item(&mut self, cx: &LateContext<'_>, item: &TraitItem<'_>) {
-        if has_attr(cx, item.hir_id()) {
-            println!("{item:#?}");
+        match dump_kind(cx, item.hir_id()) {
+            Some(DumpKind::Debug) => println!("{item:#?}"),
+            Some(DumpKind::Json(sections)) => dump_json(cx, item.hir_id(), sections),
+            None => {},
         }
     }
 
     fn check_impl_item(&mut self, cx: &LateContext<'_>, item: &hir::ImplItem<'_>) {
-        if has_attr(cx, item.hir_id()) {
-            println!("{item:#?}");
+        match dump_kind(cx, item.hir_id()) {
+            Some(DumpKind::Debug) => println!("{item:#?}"),
+            Some(DumpKind::Json(sections)) => dump_json(cx, item.hir_id(), sections),
+            None => {},
         }
     }
 }
@@ -62,3 +97,391 @@ fn has_attr(cx: &LateContext<'_>, hir_id: hir::HirId) -> bool {
     let attrs = cx.tcx.hir_attrs(hir_id);
     get_builtin_attr(attrs, sym::dump).count() > 0
 }
+
+/// The sections written by `#[clippy::dump(..)]`
+#[derive(Clone, Copy, Default)]
+struct Sections {
+    hir: bool,
+    types: bool,
+    mir: bool,
+}
+
+enum DumpKind {
+    /// `#[clippy::dump]`
+    Debug,
+    /// `#[clippy::dump(..)]`
+    Json(Sections),
+}
+
+fn dump_kind(cx: &LateContext<'_>, hir_id: hir::HirId) -> Option<DumpKind> {
+    let attrs = cx.tcx.hir_attrs(hir_id);
+    let attr = get_builtin_attr(attrs, sym::dump).next()?;
+    let Some(args) = attr.meta_item_list() else {
+        return Some(DumpKind::Debug);
+    };
+    let mut sections = Sections::default();
+    for arg in args {
+        match arg.ident().map(|ident| ident.as_str().to_owned()).as_deref() {
+            Some("hir") => sections.hir = true,
+            Some("types") => sections.types = true,
+            Some("mir") => sections.mir = true,
+            _ => {
+                cx.tcx
+                    .sess
+                    .dcx()
+                    .span_err(arg.span(), "expected `hir`, `types` or `mir`");
+            },
+        }
+    }
+    Some(DumpKind::Json(sections))
+}
+
+#[derive(Serialize)]
+struct Dump {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hir: Option<HirNode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<Vec<TypeEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mir: Option<MirBody>,
+}
+
+/// A node of the HIR tree, e.g. an expression with `node: "expr"` and `kind: "Call"`
+#[derive(Serialize)]
+struct HirNode {
+    node: &'static str,
+    kind: &'static str,
+    /// The name of a binding, method or field, or the path of an item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// What the path refers to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    res: Option<String>,
+    /// The source of a literal, or the operator
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    children: Vec<HirNode>,
+}
+
+#[derive(Serialize)]
+struct TypeEntry {
+    node: &'static str,
+    src: String,
+    ty: String,
+    /// The type after the adjustments, if there are any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adjusted: Option<String>,
+}
+
+#[derive(Serialize)]
+struct MirBody {
+    def: String,
+    locals: Vec<String>,
+    vars: Vec<String>,
+    blocks: Vec<MirBlock>,
+}
+
+#[derive(Serialize)]
+struct MirBlock {
+    block: String,
+    statements: Vec<String>,
+    terminator: String,
+}
+
+fn dump_json(cx: &LateContext<'_>, hir_id: hir::HirId, sections: Sections) {
+    let node = cx.tcx.hir_node(hir_id);
+    // The body of the item, or the one enclosing the expression or statement
+    let item_body = node.associated_body().map(|(_, body_id)| body_id);
+    let body_id = item_body.or(cx.enclosing_body);
+    let typeck_results = body_id.map(|body_id| cx.tcx.typeck_body(body_id));
+
+    let mut dumper = Dumper {
+        cx,
+        typeck_results,
+        types: Vec::new(),
+    };
+    let hir = match node {
+        Node::Expr(expr) => dumper.expr(expr),
+        Node::Stmt(stmt) => dumper.stmt(stmt),
+        _ => {
+            let def_id = hir_id.expect_owner().def_id;
+            let mut item = HirNode::new("item", cx.tcx.def_descr(def_id.to_def_id()));
+            item.name = Some(cx.tcx.def_path_str(def_id));
+            if let Some(body_id) = item_body {
+                item.children = dumper.children(|v| v.visit_body(cx.tcx.hir_body(body_id)));
+            }
+            item
+        },
+    };
+
+    let dump = Dump {
+        hir: sections.hir.then_some(hir),
+        types: sections.types.then_some(dumper.types),
+        mir: if sections.mir {
+            body_id.and_then(|body_id| mir_body(cx, cx.tcx.hir_body_owner_def_id(body_id)))
+        } else {
+            None
+        },
+    };
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&dump).expect("the dump is serializable")
+    );
+}
+
+impl HirNode {
+    fn new(node: &'static str, kind: &'static str) -> Self {
+        Self {
+            node,
+            kind,
+            name: None,
+            res: None,
+            value: None,
+            children: Vec::new(),
+        }
+    }
+}
+
+struct Dumper<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    /// The types of the expressions and bindings, collected while building the tree
+    types: Vec<TypeEntry>,
+}
+
+impl<'tcx> Dumper<'_, 'tcx> {
+    /// The source of `span` on a single line, shortened to keep the dump compact
+    fn src(&self, span: Span) -> String {
+        let src = snippet(self.cx, span, "..").split_whitespace().join(" ");
+        if src.chars().count() > 40 {
+            format!("{}...", src.chars().take(37).collect::<String>())
+        } else {
+            src
+        }
+    }
+
+    fn res(&self, res: Res) -> String {
+        match res {
+            Res::Local(id) => format!("local `{}`", self.cx.tcx.hir_name(id)),
+            Res::Def(_, id) => format!("{} `{}`", self.cx.tcx.def_descr(id), self.cx.tcx.def_path_str(id)),
+            _ => res.descr().to_owned(),
+        }
+    }
+
+    fn children(&mut self, f: impl FnOnce(&mut ChildVisitor<'_, '_, 'tcx>)) -> Vec<HirNode> {
+        let mut visitor = ChildVisitor {
+            dumper: self,
+            children: Vec::new(),
+        };
+        f(&mut visitor);
+        visitor.children
+    }
+
+    fn expr(&mut self, expr: &'tcx hir::Expr<'tcx>) -> HirNode {
+        if let Some(typeck_results) = self.typeck_results
+            && let Some(ty) = typeck_results.expr_ty_opt(expr)
+        {
+            let entry = TypeEntry {
+                node: "expr",
+                src: self.src(expr.span),
+                ty: ty.to_string(),
+                adjusted: typeck_results
+                    .expr_adjustments(expr)
+                    .last()
+                    .map(|adjustment| adjustment.target.to_string()),
+            };
+            self.types.push(entry);
+        }
+
+        let mut node = HirNode::new("expr", expr_kind_name(&expr.kind));
+        match expr.kind {
+            ExprKind::Path(_) => node.res = self.typeck_results.map(|typeck| self.res(expr.res(typeck))),
+            ExprKind::Struct(qpath, ..) => {
+                node.res = self
+                    .typeck_results
+                    .map(|typeck| self.res(typeck.qpath_res(qpath, expr.hir_id)));
+            },
+            ExprKind::MethodCall(segment, ..) => {
+                node.name = Some(segment.ident.to_string());
+                node.res = self
+                    .typeck_results
+                    .and_then(|typeck| typeck.type_dependent_def(expr.hir_id))
+                    .map(|(kind, id)| self.res(Res::Def(kind, id)));
+            },
+            ExprKind::Field(_, ident) => node.name = Some(ident.to_string()),
+            ExprKind::Lit(lit) => node.value = Some(self.src(lit.span)),
+            ExprKind::Binary(op, ..) => node.value = Some(op.node.as_str().to_owned()),
+            ExprKind::AssignOp(op, ..) => node.value = Some(op.node.as_str().to_owned()),
+            ExprKind::Unary(op, _) => node.value = Some(op.as_str().to_owned()),
+            _ => {},
+        }
+        node.children = self.children(|v| intravisit::walk_expr(v, expr));
+        node
+    }
+
+    fn pat(&mut self, pat: &'tcx hir::Pat<'tcx>) -> HirNode {
+        let mut node = HirNode::new("pat", pat_kind_name(&pat.kind));
+        if let PatKind::Binding(_, _, ident, _) = pat.kind {
+            node.name = Some(ident.to_string());
+            if let Some(typeck_results) = self.typeck_results
+                && let Some(ty) = typeck_results.node_type_opt(pat.hir_id)
+            {
+                self.types.push(TypeEntry {
+                    node: "binding",
+                    src: ident.to_string(),
+                    ty: ty.to_string(),
+                    adjusted: None,
+                });
+            }
+        }
+        if let PatKind::Struct(ref qpath, ..) | PatKind::TupleStruct(ref qpath, ..) = pat.kind {
+            node.res = self
+                .typeck_results
+                .map(|typeck| self.res(typeck.qpath_res(qpath, pat.hir_id)));
+        }
+        node.children = self.children(|v| intravisit::walk_pat(v, pat));
+        node
+    }
+
+    fn stmt(&mut self, stmt: &'tcx hir::Stmt<'tcx>) -> HirNode {
+        let kind = match stmt.kind {
+            StmtKind::Let(_) => "Let",
+            StmtKind::Item(_) => "Item",
+            StmtKind::Expr(_) => "Expr",
+            StmtKind::Semi(_) => "Semi",
+        };
+        let mut node = HirNode::new("stmt", kind);
+        if let StmtKind::Item(item) = stmt.kind {
+            node.name = Some(self.cx.tcx.def_path_str(item.owner_id));
+        }
+        node.children = self.children(|v| intravisit::walk_stmt(v, stmt));
+        node
+    }
+}
+
+/// Collects the direct children of a node
+struct ChildVisitor<'a, 'b, 'tcx> {
+    dumper: &'a mut Dumper<'b, 'tcx>,
+    children: Vec<HirNode>,
+}
+
+impl<'tcx> Visitor<'tcx> for ChildVisitor<'_, '_, 'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        let node = self.dumper.expr(expr);
+        self.children.push(node);
+    }
+
+    fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+        let node = self.dumper.pat(pat);
+        self.children.push(node);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'tcx hir::Stmt<'tcx>) {
+        let node = self.dumper.stmt(stmt);
+        self.children.push(node);
+    }
+
+    fn visit_arm(&mut self, arm: &'tcx hir::Arm<'tcx>) {
+        let mut node = HirNode::new("arm", "Arm");
+        node.children = self.dumper.children(|v| intravisit::walk_arm(v, arm));
+        self.children.push(node);
+    }
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.dumper.cx.tcx
+    }
+}
+
+fn mir_body(cx: &LateContext<'_>, def_id: LocalDefId) -> Option<MirBody> {
+    let body = match cx.tcx.hir_body_owner_kind(def_id) {
+        BodyOwnerKind::Fn | BodyOwnerKind::Closure => cx.tcx.optimized_mir(def_id),
+        BodyOwnerKind::Const { .. } | BodyOwnerKind::Static(_) => cx.tcx.mir_for_ctfe(def_id),
+        BodyOwnerKind::GlobalAsm => return None,
+    };
+    Some(MirBody {
+        def: cx.tcx.def_path_str(def_id),
+        locals: body
+            .local_decls
+            .iter_enumerated()
+            .map(|(local, decl)| format!("{local:?}: {}", decl.ty))
+            .collect(),
+        vars: body
+            .var_debug_info
+            .iter()
+            .map(|info| format!("{} => {:?}", info.name, info.value))
+            .collect(),
+        blocks: body
+            .basic_blocks
+            .iter_enumerated()
+            .map(|(block, data): (mir::BasicBlock, &mir::BasicBlockData<'_>)| MirBlock {
+                block: format!("{block:?}"),
+                statements: data.statements.iter().map(|stmt| format!("{:?}", stmt.kind)).collect(),
+                terminator: format!("{:?}", data.terminator().kind),
+            })
+            .collect(),
+    })
+}
+
+fn expr_kind_name(kind: &ExprKind<'_>) -> &'static str {
+    match kind {
+        ExprKind::ConstBlock(_) => "ConstBlock",
+        ExprKind::Array(_) => "Array",
+        ExprKind::Call(..) => "Call",
+        ExprKind::MethodCall(..) => "MethodCall",
+        ExprKind::Use(..) => "Use",
+        ExprKind::Tup(_) => "Tup",
+        ExprKind::Binary(..) => "Binary",
+        ExprKind::Unary(..) => "Unary",
+        ExprKind::Lit(_) => "Lit",
+        ExprKind::Cast(..) => "Cast",
+        ExprKind::Type(..) => "Type",
+        ExprKind::DropTemps(_) => "DropTemps",
+        ExprKind::Let(_) => "Let",
+        ExprKind::If(..) => "If",
+        ExprKind::Loop(..) => "Loop",
+        ExprKind::Match(..) => "Match",
+        ExprKind::Closure(_) => "Closure",
+        ExprKind::Block(..) => "Block",
+        ExprKind::Assign(..) => "Assign",
+        ExprKind::AssignOp(..) => "AssignOp",
+        ExprKind::Field(..) => "Field",
+        ExprKind::Index(..) => "Index",
+        ExprKind::Path(_) => "Path",
+        ExprKind::AddrOf(..) => "AddrOf",
+        ExprKind::Break(..) => "Break",
+        ExprKind::Continue(_) => "Continue",
+        ExprKind::Ret(_) => "Ret",
+        ExprKind::Become(_) => "Become",
+        ExprKind::InlineAsm(_) => "InlineAsm",
+        ExprKind::OffsetOf(..) => "OffsetOf",
+        ExprKind::Struct(..) => "Struct",
+        ExprKind::Repeat(..) => "Repeat",
+        ExprKind::Yield(..) => "Yield",
+        ExprKind::UnsafeBinderCast(..) => "UnsafeBinderCast",
+        ExprKind::Err(_) => "Err",
+    }
+}
+
+fn pat_kind_name(kind: &PatKind<'_>) -> &'static str {
+    match kind {
+        PatKind::Missing => "Missing",
+        PatKind::Wild => "Wild",
+        PatKind::Binding(..) => "Binding",
+        PatKind::Struct(..) => "Struct",
+        PatKind::TupleStruct(..) => "TupleStruct",
+        PatKind::Or(_) => "Or",
+        PatKind::Never => "Never",
+        PatKind::Tuple(..) => "Tuple",
+        PatKind::Box(_) => "Box",
+        PatKind::Deref(_) => "Deref",
+        PatKind::Ref(..) => "Ref",
+        PatKind::Expr(_) => "Expr",
+        PatKind::Guard(..) => "Guard",
+        PatKind::Range(..) => "Range",
+        PatKind::Slice(..) => "Slice",
+        PatKind::Err(_) => "Err",
+    }
+}
@@ -39,7 +39,8 @@ pub fn check_clippy_attr<A: AttributeExt>(sess: &Session, attr: &A) {
             | sym::dump
             | sym::msrv
             | sym::has_significant_drop
-            | sym::format_args => {},
+            | sym::format_args
+            | sym::no_panic => {},
             _ => {
                 sess.dcx().span_err(path_span, "usage of unknown attribute");
             },
@@ -154,7 +155,11 @@ impl LimitStack {
         }
     }
     pub fn limit(&self) -> u64 {
-        self.stack.last().copied().unwrap_or(self.default)
+        self.limit_or(self.default)
+    }
+    /// Gets the limit set by the innermost attribute, or `default` if there is none
+    pub fn limit_or(&self, default: u64) -> u64 {
+        self.stack.last().copied().unwrap_or(default)
     }
     pub fn push_attrs(&mut self, sess: &Session, attrs: &[impl AttributeExt], name: Symbol) {
         let stack = &mut self.stack;
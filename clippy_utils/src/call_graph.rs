@@ -0,0 +1,70 @@
+//! Summaries of the functions of the crate which depend on the functions they call, e.g. whether
+//! a function may reach a panic.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::LocalDefId;
+use rustc_middle::ty::TyCtxt;
+
+/// Computes the summary of a single function of the crate.
+pub trait Summarize {
+    type Summary: Clone;
+
+    /// Computes the summary of `def_id`, getting the ones of the functions it calls from
+    /// `summaries`. `None` means there's nothing to report for the function.
+    fn summarize(
+        &self,
+        tcx: TyCtxt<'_>,
+        summaries: &mut CallSummaries<Self::Summary>,
+        def_id: LocalDefId,
+    ) -> Option<Self::Summary>;
+}
+
+/// The memoized summaries of the functions of the crate.
+///
+/// Recursive calls get no summary while the function they reach is being summarized. A missing
+/// summary which relied on this isn't cached until that function is done, as the function may
+/// still turn out to have one.
+pub struct CallSummaries<T> {
+    summaries: FxHashMap<LocalDefId, Option<T>>,
+    /// The functions being summarized, innermost last
+    visiting: Vec<LocalDefId>,
+    /// The index into `visiting` of the outermost function reached again by a recursive call
+    /// while summarizing the current function
+    cycle_start: Option<usize>,
+}
+
+impl<T> Default for CallSummaries<T> {
+    fn default() -> Self {
+        Self {
+            summaries: FxHashMap::default(),
+            visiting: Vec::new(),
+            cycle_start: None,
+        }
+    }
+}
+
+impl<T: Clone> CallSummaries<T> {
+    /// Gets the summary of `def_id`, computing it with `summarizer` if it isn't known yet.
+    pub fn get<S: Summarize<Summary = T>>(&mut self, tcx: TyCtxt<'_>, summarizer: &S, def_id: LocalDefId) -> Option<T> {
+        if let Some(summary) = self.summaries.get(&def_id) {
+            return summary.clone();
+        }
+        if let Some(i) = self.visiting.iter().position(|&visiting| visiting == def_id) {
+            self.cycle_start = Some(self.cycle_start.map_or(i, |start| start.min(i)));
+            return None;
+        }
+
+        let depth = self.visiting.len();
+        self.visiting.push(def_id);
+        let outer_cycle_start = self.cycle_start.take();
+        let summary = summarizer.summarize(tcx, self, def_id);
+        self.visiting.pop();
+
+        let cycle_start = self.cycle_start.filter(|&start| summary.is_none() && start < depth);
+        if cycle_start.is_none() {
+            self.summaries.insert(def_id, summary.clone());
+        }
+        self.cycle_start = outer_cycle_start.into_iter().chain(cycle_start).min();
+        summary
+    }
+}
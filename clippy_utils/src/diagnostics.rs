@@ -12,10 +12,48 @@ use rustc_errors::{Applicability, Diag, DiagCtxtHandle, DiagMessage, Diagnostic,
 #[cfg(debug_assertions)]
 use rustc_errors::{EmissionGuarantee, SubstitutionPart, Suggestions};
 use rustc_hir::HirId;
-use rustc_lint::{LateContext, Lint, LintContext};
+use rustc_lint::builtin::WARNINGS;
+use rustc_lint::{LateContext, Lint, LintContext, LintId};
+use rustc_middle::lint::{LevelSpec, emit_lint_base};
 use rustc_span::Span;
+use std::cell::Cell;
 use std::env;
 
+thread_local! {
+    static EMITTING_LINT: Cell<Option<&'static Lint>> = const { Cell::new(None) };
+}
+
+/// Gets the Clippy lint whose diagnostic is being emitted by one of the functions in this module.
+///
+/// Lint diagnostics are passed to `rustc_errors::TRACK_DIAGNOSTIC` while they're emitted, so this
+/// lets the driver tell which lint a diagnostic belongs to.
+pub fn emitting_lint() -> Option<&'static Lint> {
+    EMITTING_LINT.get()
+}
+
+/// Runs `f` with [`emitting_lint`] returning `lint`. Use this to emit a diagnostic of `lint`
+/// again after it was held back.
+pub fn with_emitting_lint<R>(lint: &'static Lint, f: impl FnOnce() -> R) -> R {
+    let _guard = EmittingLintGuard::new(lint);
+    f()
+}
+
+/// Restores the previous value of [`emitting_lint`] when dropped. The lint functions use this
+/// instead of a closure so `#[track_caller]` still points to the lint's implementation.
+struct EmittingLintGuard(Option<&'static Lint>);
+
+impl EmittingLintGuard {
+    fn new(lint: &'static Lint) -> Self {
+        Self(EMITTING_LINT.replace(Some(lint)))
+    }
+}
+
+impl Drop for EmittingLintGuard {
+    fn drop(&mut self) {
+        EMITTING_LINT.set(self.0);
+    }
+}
+
 fn docs_link(diag: &mut Diag<'_, ()>, lint: &'static Lint) {
     if env::var("CLIPPY_DISABLE_DOCS_LINKS").is_err()
         && let Some(lint) = lint.name_lower().strip_prefix("clippy::")
@@ -251,6 +289,7 @@ where
     }
 
     let sp = sp.into();
+    let _guard = EmittingLintGuard::new(lint);
     #[expect(clippy::disallowed_methods)]
     cx.emit_span_lint(
         lint,
@@ -329,6 +368,7 @@ pub fn span_lint_hir_and_then(
     msg: impl Into<DiagMessage>,
     f: impl FnOnce(&mut Diag<'_, ()>),
 ) {
+    let _guard = EmittingLintGuard::new(lint);
     #[expect(clippy::disallowed_methods)]
     cx.tcx.emit_node_span_lint(
         lint,
@@ -345,6 +385,48 @@ pub fn span_lint_hir_and_then(
     );
 }
 
+/// Like [`span_lint_hir_and_then`], but emits the lint at `level` instead of the level it has at
+/// the node identified by `hir_id`, unless the lint is allowed, expected or forbidden there.
+#[track_caller]
+pub fn span_lint_hir_at_level_and_then(
+    cx: &LateContext<'_>,
+    lint: &'static Lint,
+    level: rustc_lint::Level,
+    hir_id: HirId,
+    sp: impl Into<MultiSpan>,
+    msg: impl Into<DiagMessage>,
+    f: impl FnOnce(&mut Diag<'_, ()>),
+) {
+    let mut level_spec = cx.tcx.lint_level_spec_at_node(lint, hir_id);
+    if matches!(level_spec.level(), rustc_lint::Level::Warn | rustc_lint::Level::Deny) {
+        let warnings = cx.tcx.lint_level_spec_at_node(WARNINGS, hir_id);
+        level_spec = if level == rustc_lint::Level::Warn && !matches!(warnings.level(), rustc_lint::Level::Warn) {
+            // Respect `-D warnings` and `#[allow(warnings)]` like the compiler does
+            warnings
+        } else {
+            let cap = cx.tcx.sess.opts.lint_cap.unwrap_or(rustc_lint::Level::Forbid);
+            let driver_cap = cx.tcx.sess.driver_lint_caps.get(&LintId::of(lint)).copied();
+            let level = driver_cap.into_iter().fold(level.min(cap), Ord::min);
+            LevelSpec::new(level, None, level_spec.src)
+        };
+    }
+    let _guard = EmittingLintGuard::new(lint);
+    emit_lint_base(
+        cx.tcx.sess,
+        lint,
+        level_spec,
+        Some(sp.into()),
+        rustc_errors::DiagDecorator(|diag| {
+            diag.primary_message(msg);
+            f(diag);
+            docs_link(diag, lint);
+
+            #[cfg(debug_assertions)]
+            validate_diag(diag);
+        }),
+    );
+}
+
 /// Add a span lint with a suggestion on how to fix it.
 ///
 /// These suggestions can be parsed by rustfix to allow it to automatically fix your code.
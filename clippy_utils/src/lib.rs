@@ -42,6 +42,7 @@ extern crate rustc_trait_selection;
 pub mod ast_utils;
 #[deny(missing_docs)]
 pub mod attrs;
+pub mod call_graph;
 mod check_proc_macro;
 pub mod comparisons;
 pub mod consts;
@@ -50,6 +51,7 @@ pub mod eager_or_lazy;
 pub mod higher;
 mod hir_utils;
 pub mod macros;
+pub mod matcher;
 pub mod mir;
 pub mod msrvs;
 pub mod numeric_literal;
@@ -2141,6 +2143,40 @@ pub fn fn_def_id_with_node_args<'tcx>(
     }
 }
 
+/// Returns the `DefId` of the function called by a call or method call expression in any body,
+/// given the typeck results of that body. Calls of trait methods are resolved to the method of the
+/// implementation when it's known.
+///
+/// Returns `None` for calls of closures and through function pointers or `dyn Fn`.
+pub fn resolved_callee<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typing_env: rustc_ty::TypingEnv<'tcx>,
+    typeck: &TypeckResults<'tcx>,
+    expr: &Expr<'_>,
+) -> Option<DefId> {
+    let (def_id, args) = match expr.kind {
+        ExprKind::MethodCall(..) => (
+            typeck.type_dependent_def_id(expr.hir_id)?,
+            typeck.node_args(expr.hir_id),
+        ),
+        ExprKind::Call(callee, _) => match *typeck.expr_ty(callee).kind() {
+            rustc_ty::FnDef(def_id, args) => match args.no_bound_vars() {
+                Some(args) => (def_id, args),
+                None => return Some(def_id),
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if tcx.trait_of_assoc(def_id).is_some()
+        && let Ok(Some(instance)) = rustc_ty::Instance::try_resolve(tcx, typing_env, def_id, args)
+    {
+        Some(instance.def_id())
+    } else {
+        Some(def_id)
+    }
+}
+
 /// Returns `Option<String>` where String is a textual representation of the type encapsulated in
 /// the slice iff the given expression is a slice of primitives.
 ///
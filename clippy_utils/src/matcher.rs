@@ -0,0 +1,298 @@
+//! Patterns matching HIR expressions, built from the combinators of this module.
+//!
+//! ```rust,ignore
+//! use clippy_utils::matcher::{ExprPat, bind, call, diag_item, lit_int};
+//!
+//! // `std::cmp::min(0, x)`, binding `x`
+//! let pattern = call(diag_item(sym::cmp_min), [lit_int(0), bind("x")]);
+//! if let Some(bindings) = pattern.matches(cx, expr) {
+//!     let x = &bindings["x"];
+//! }
+//! ```
+//!
+//! `#[clippy::author = "pattern"]` prints the pattern matching the annotated expression.
+
+use crate::SpanlessEq;
+use crate::paths::PathLookup;
+use crate::res::{MaybeDef as _, MaybeQPath as _};
+use rustc_ast::ast::{BorrowKind, LitFloatType, LitIntType, LitKind, StrStyle};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::DefId;
+use rustc_hir::{BinOpKind, Expr, ExprKind, LangItem, Mutability, UnOp};
+use rustc_lint::LateContext;
+use rustc_span::Symbol;
+use std::ops::Index;
+
+/// A pattern matching an expression
+pub enum ExprPat {
+    /// Any expression
+    Any,
+    /// The expression matched by the inner pattern, bound to the name. A name bound more than once
+    /// has to match equal expressions each time.
+    Bind(Symbol, Box<ExprPat>),
+    /// A path resolving to the item
+    Path(PathPat),
+    /// A literal, regardless of its suffix
+    Lit(LitKind),
+    Call(Box<ExprPat>, Vec<ExprPat>),
+    /// A method call with the name, the receiver and the arguments
+    MethodCall(Symbol, Box<ExprPat>, Vec<ExprPat>),
+    Field(Box<ExprPat>, Symbol),
+    Unary(UnOp, Box<ExprPat>),
+    Binary(BinOpKind, Box<ExprPat>, Box<ExprPat>),
+    AddrOf(Mutability, Box<ExprPat>),
+    Index(Box<ExprPat>, Box<ExprPat>),
+    Tup(Vec<ExprPat>),
+    Array(Vec<ExprPat>),
+}
+
+/// The item a path has to resolve to
+pub enum PathPat {
+    DiagItem(Symbol),
+    LangItem(LangItem),
+    Lookup(&'static PathLookup),
+    DefIds(Vec<DefId>),
+}
+
+/// Matches any expression.
+pub fn any() -> ExprPat {
+    ExprPat::Any
+}
+
+/// Matches any expression and binds it to `name`.
+pub fn bind(name: &str) -> ExprPat {
+    any().bind(name)
+}
+
+/// Matches a path to the diagnostic item `name`.
+pub fn diag_item(name: Symbol) -> ExprPat {
+    ExprPat::Path(PathPat::DiagItem(name))
+}
+
+/// Matches a path to the lang item `item`.
+pub fn lang_item(item: LangItem) -> ExprPat {
+    ExprPat::Path(PathPat::LangItem(item))
+}
+
+/// Matches a path resolving to one of the items of `path`, which are usually in
+/// [`crate::paths`].
+pub fn path(path: &'static PathLookup) -> ExprPat {
+    ExprPat::Path(PathPat::Lookup(path))
+}
+
+/// Matches a path resolving to one of `def_ids`.
+pub fn def_ids(def_ids: Vec<DefId>) -> ExprPat {
+    ExprPat::Path(PathPat::DefIds(def_ids))
+}
+
+/// Matches the literal `kind`, regardless of the suffix.
+pub fn lit(kind: LitKind) -> ExprPat {
+    ExprPat::Lit(kind)
+}
+
+/// Matches an integer literal.
+pub fn lit_int(value: u128) -> ExprPat {
+    lit(LitKind::Int(value.into(), LitIntType::Unsuffixed))
+}
+
+/// Matches a float literal, written as `value`.
+pub fn lit_float(value: &str) -> ExprPat {
+    lit(LitKind::Float(Symbol::intern(value), LitFloatType::Unsuffixed))
+}
+
+/// Matches a string literal.
+pub fn lit_str(value: &str) -> ExprPat {
+    lit(LitKind::Str(Symbol::intern(value), StrStyle::Cooked))
+}
+
+/// Matches a character literal.
+pub fn lit_char(value: char) -> ExprPat {
+    lit(LitKind::Char(value))
+}
+
+/// Matches a boolean literal.
+pub fn lit_bool(value: bool) -> ExprPat {
+    lit(LitKind::Bool(value))
+}
+
+/// Matches a call of `callee` with `args`.
+pub fn call(callee: ExprPat, args: impl IntoIterator<Item = ExprPat>) -> ExprPat {
+    ExprPat::Call(Box::new(callee), args.into_iter().collect())
+}
+
+/// Matches a call of the method `name` on `receiver` with `args`.
+pub fn method_call(name: &str, receiver: ExprPat, args: impl IntoIterator<Item = ExprPat>) -> ExprPat {
+    ExprPat::MethodCall(Symbol::intern(name), Box::new(receiver), args.into_iter().collect())
+}
+
+/// Matches an access to the field `name` of `base`.
+pub fn field(base: ExprPat, name: &str) -> ExprPat {
+    ExprPat::Field(Box::new(base), Symbol::intern(name))
+}
+
+pub fn unary(op: UnOp, operand: ExprPat) -> ExprPat {
+    ExprPat::Unary(op, Box::new(operand))
+}
+
+pub fn binary(op: BinOpKind, lhs: ExprPat, rhs: ExprPat) -> ExprPat {
+    ExprPat::Binary(op, Box::new(lhs), Box::new(rhs))
+}
+
+/// Matches `&expr` or `&mut expr`, but not a raw borrow.
+pub fn addr_of(mutbl: Mutability, expr: ExprPat) -> ExprPat {
+    ExprPat::AddrOf(mutbl, Box::new(expr))
+}
+
+pub fn index(base: ExprPat, index: ExprPat) -> ExprPat {
+    ExprPat::Index(Box::new(base), Box::new(index))
+}
+
+pub fn tup(exprs: impl IntoIterator<Item = ExprPat>) -> ExprPat {
+    ExprPat::Tup(exprs.into_iter().collect())
+}
+
+pub fn array(exprs: impl IntoIterator<Item = ExprPat>) -> ExprPat {
+    ExprPat::Array(exprs.into_iter().collect())
+}
+
+impl ExprPat {
+    /// Binds the expression matched by this pattern to `name`.
+    #[must_use]
+    pub fn bind(self, name: &str) -> Self {
+        Self::Bind(Symbol::intern(name), Box::new(self))
+    }
+
+    /// Matches `expr` against the pattern, returning the expressions bound to its names on success.
+    pub fn matches<'tcx>(&self, cx: &LateContext<'tcx>, expr: &'tcx Expr<'tcx>) -> Option<Bindings<'tcx>> {
+        let mut matcher = Matcher {
+            cx,
+            bindings: Bindings::default(),
+        };
+        matcher.matches(self, expr).then_some(matcher.bindings)
+    }
+
+    /// Calls `f` with the names bound by the pattern, in order of appearance.
+    pub fn for_each_binding(&self, f: &mut impl FnMut(Symbol)) {
+        match self {
+            Self::Bind(name, inner) => {
+                f(*name);
+                inner.for_each_binding(f);
+            },
+            Self::Any | Self::Path(_) | Self::Lit(_) => {},
+            Self::Call(callee, args) | Self::MethodCall(_, callee, args) => {
+                callee.for_each_binding(f);
+                for arg in args {
+                    arg.for_each_binding(f);
+                }
+            },
+            Self::Field(inner, _) | Self::Unary(_, inner) | Self::AddrOf(_, inner) => inner.for_each_binding(f),
+            Self::Binary(_, lhs, rhs) | Self::Index(lhs, rhs) => {
+                lhs.for_each_binding(f);
+                rhs.for_each_binding(f);
+            },
+            Self::Tup(exprs) | Self::Array(exprs) => {
+                for expr in exprs {
+                    expr.for_each_binding(f);
+                }
+            },
+        }
+    }
+}
+
+/// The expressions bound by a successful match
+#[derive(Debug, Default)]
+pub struct Bindings<'tcx> {
+    map: FxHashMap<Symbol, &'tcx Expr<'tcx>>,
+}
+
+impl<'tcx> Bindings<'tcx> {
+    /// Gets the expression bound to `name`.
+    pub fn get(&self, name: &str) -> Option<&'tcx Expr<'tcx>> {
+        self.get_sym(Symbol::intern(name))
+    }
+
+    /// Gets the expression bound to `name`.
+    pub fn get_sym(&self, name: Symbol) -> Option<&'tcx Expr<'tcx>> {
+        self.map.get(&name).copied()
+    }
+}
+
+impl<'tcx> Index<&str> for Bindings<'tcx> {
+    type Output = Expr<'tcx>;
+
+    /// Gets the expression bound to `name`, which has to be bound by the pattern.
+    fn index(&self, name: &str) -> &Self::Output {
+        self.get(name)
+            .unwrap_or_else(|| panic!("`{name}` isn't bound by the pattern"))
+    }
+}
+
+struct Matcher<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    bindings: Bindings<'tcx>,
+}
+
+impl<'tcx> Matcher<'_, 'tcx> {
+    fn matches(&mut self, pattern: &ExprPat, expr: &'tcx Expr<'tcx>) -> bool {
+        match (pattern, expr.kind) {
+            (ExprPat::Any, _) => true,
+            (&ExprPat::Bind(name, ref inner), _) => {
+                if !self.matches(inner, expr) {
+                    return false;
+                }
+                if let Some(&bound) = self.bindings.map.get(&name) {
+                    SpanlessEq::new(self.cx).eq_expr(expr.span.ctxt(), bound, expr)
+                } else {
+                    self.bindings.map.insert(name, expr);
+                    true
+                }
+            },
+            (ExprPat::Path(path), ExprKind::Path(_)) => {
+                let res = expr.res(self.cx);
+                match *path {
+                    PathPat::DiagItem(name) => res.is_diag_item(self.cx, name),
+                    PathPat::LangItem(item) => res.is_lang_item(self.cx, item),
+                    PathPat::Lookup(lookup) => res.opt_def_id().is_some_and(|id| lookup.matches(self.cx, id)),
+                    PathPat::DefIds(ref def_ids) => res.opt_def_id().is_some_and(|id| def_ids.contains(&id)),
+                }
+            },
+            (ExprPat::Lit(kind), ExprKind::Lit(lit)) => match (kind, lit.node) {
+                (LitKind::Int(a, _), LitKind::Int(b, _)) => *a == b,
+                (LitKind::Float(a, _), LitKind::Float(b, _)) | (LitKind::Str(a, _), LitKind::Str(b, _)) => *a == b,
+                (a, b) => *a == b,
+            },
+            (ExprPat::Call(callee, args), ExprKind::Call(expr_callee, expr_args)) => {
+                self.matches(callee, expr_callee) && self.matches_all(args, expr_args)
+            },
+            (ExprPat::MethodCall(name, receiver, args), ExprKind::MethodCall(segment, expr_receiver, expr_args, _)) => {
+                segment.ident.name == *name
+                    && self.matches(receiver, expr_receiver)
+                    && self.matches_all(args, expr_args)
+            },
+            (ExprPat::Field(base, name), ExprKind::Field(expr_base, ident)) => {
+                ident.name == *name && self.matches(base, expr_base)
+            },
+            (ExprPat::Unary(op, inner), ExprKind::Unary(expr_op, expr_inner)) => {
+                *op == expr_op && self.matches(inner, expr_inner)
+            },
+            (ExprPat::Binary(op, lhs, rhs), ExprKind::Binary(expr_op, expr_lhs, expr_rhs)) => {
+                *op == expr_op.node && self.matches(lhs, expr_lhs) && self.matches(rhs, expr_rhs)
+            },
+            (ExprPat::AddrOf(mutbl, inner), ExprKind::AddrOf(BorrowKind::Ref, expr_mutbl, expr_inner)) => {
+                *mutbl == expr_mutbl && self.matches(inner, expr_inner)
+            },
+            (ExprPat::Index(base, index), ExprKind::Index(expr_base, expr_index, _)) => {
+                self.matches(base, expr_base) && self.matches(index, expr_index)
+            },
+            (ExprPat::Tup(patterns), ExprKind::Tup(exprs)) | (ExprPat::Array(patterns), ExprKind::Array(exprs)) => {
+                self.matches_all(patterns, exprs)
+            },
+            (_, ExprKind::DropTemps(inner)) => self.matches(pattern, inner),
+            _ => false,
+        }
+    }
+
+    fn matches_all(&mut self, patterns: &[ExprPat], exprs: &'tcx [Expr<'tcx>]) -> bool {
+        patterns.len() == exprs.len() && patterns.iter().zip(exprs).all(|(p, e)| self.matches(p, e))
+    }
+}
@@ -9,6 +9,9 @@ use rustc_middle::mir::{
 };
 use rustc_middle::ty::TyCtxt;
 
+mod param_usage;
+pub use param_usage::{ParamUsage, ParamUsages};
+
 mod possible_borrower;
 pub use possible_borrower::PossibleBorrowerMap;
 
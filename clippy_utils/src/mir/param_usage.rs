@@ -0,0 +1,308 @@
+//! Summaries of how crate-local functions use their parameters, computed from their MIR.
+
+use crate::fn_has_unsatisfiable_clauses;
+use crate::ty::InteriorMut;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::DefKind;
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_index::IndexVec;
+use rustc_lint::LateContext;
+use rustc_middle::mir::visit::{PlaceContext, Visitor};
+use rustc_middle::mir::{
+    Body, Local, Location, Mutability, Operand, Place, RETURN_PLACE, Rvalue, StatementKind, Terminator, TerminatorKind,
+};
+use rustc_middle::ty::{self, GenericArgKind, Ty};
+
+/// How a function uses one of its parameters. The variants are ordered from the most to the
+/// least restrictive, so that the usage of a parameter is the maximum of its uses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ParamUsage {
+    /// The parameter is only read or borrowed immutably. A parameter taken by value could be
+    /// taken by reference instead.
+    Borrowed,
+    /// The parameter, or what it refers to, is mutated or borrowed mutably, or it has interior
+    /// mutability.
+    Mutated,
+    /// The parameter, or a part of it, is moved into another function.
+    MovedOut,
+    /// The parameter, or a reference to it, is returned or stored where it can't be followed.
+    Escapes,
+}
+
+/// The summaries of the crate-local functions, computed on demand and cached.
+///
+/// Calls of other crate-local functions are followed, using their own summary. Recursive calls,
+/// and calls of functions without a summary, are treated conservatively.
+#[derive(Default)]
+pub struct ParamUsages {
+    /// `None` while the summary is being computed
+    summaries: FxHashMap<LocalDefId, Option<Vec<ParamUsage>>>,
+}
+
+impl ParamUsages {
+    /// Returns how the function `def_id` uses its parameter at `index`, if it's a crate-local
+    /// function with a summary.
+    pub fn get(&mut self, cx: &LateContext<'_>, def_id: DefId, index: usize) -> Option<ParamUsage> {
+        self.summary(cx, def_id.as_local()?)?.get(index).copied()
+    }
+
+    /// Returns how the function `def_id` uses each of its parameters. Returns `None` for
+    /// functions without a body, trait methods, and functions whose summary is being computed.
+    pub fn summary(&mut self, cx: &LateContext<'_>, def_id: LocalDefId) -> Option<&[ParamUsage]> {
+        if !self.summaries.contains_key(&def_id) {
+            let tcx = cx.tcx;
+            if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+                || tcx.hir_maybe_body_owned_by(def_id).is_none()
+                || tcx.trait_of_assoc(def_id.to_def_id()).is_some()
+                // Building MIR for `fn`s with unsatisfiable clauses results in ICE.
+                || fn_has_unsatisfiable_clauses(cx, def_id.to_def_id())
+            {
+                return None;
+            }
+            self.summaries.insert(def_id, None);
+            let mir = tcx.optimized_mir(def_id);
+            let summary = mir
+                .args_iter()
+                .map(|param| {
+                    let mut v = UsageVisitor::new(cx, mir, self, param);
+                    v.run();
+                    v.usage
+                })
+                .collect();
+            self.summaries.insert(def_id, Some(summary));
+        }
+        self.summaries[&def_id].as_deref()
+    }
+}
+
+/// What a local holds of the parameter
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Holds {
+    /// A shared reference to it, or to a part of it
+    Shared,
+    /// A mutable reference to it, or to a part of it
+    Mut,
+    /// The parameter, or a part of it
+    Value,
+}
+
+/// Follows the values derived from a parameter through the body, until a fixpoint is reached.
+struct UsageVisitor<'a, 'tcx> {
+    cx: &'a LateContext<'tcx>,
+    mir: &'tcx Body<'tcx>,
+    usages: &'a mut ParamUsages,
+    param: Local,
+    holds: IndexVec<Local, Option<Holds>>,
+    usage: ParamUsage,
+    changed: bool,
+}
+
+impl<'a, 'tcx> UsageVisitor<'a, 'tcx> {
+    fn new(cx: &'a LateContext<'tcx>, mir: &'tcx Body<'tcx>, usages: &'a mut ParamUsages, param: Local) -> Self {
+        let ty = mir.local_decls[param].ty;
+        let mut holds = IndexVec::from_elem(None, &mir.local_decls);
+        holds[param] = Some(match ty.ref_mutability() {
+            Some(Mutability::Not) => Holds::Shared,
+            Some(Mutability::Mut) => Holds::Mut,
+            None => Holds::Value,
+        });
+        // Interior mutability allows mutating it through shared references, which isn't tracked
+        let usage = if ty.peel_refs().is_freeze(cx.tcx, mir.typing_env(cx.tcx))
+            && !InteriorMut::new(cx.tcx, &[]).is_interior_mut_ty(cx, ty)
+        {
+            ParamUsage::Borrowed
+        } else {
+            ParamUsage::Mutated
+        };
+        Self {
+            cx,
+            mir,
+            usages,
+            param,
+            holds,
+            usage,
+            changed: false,
+        }
+    }
+
+    fn run(&mut self) {
+        loop {
+            self.changed = false;
+            for (block, data) in self.mir.basic_blocks.iter_enumerated() {
+                for (statement_index, statement) in data.statements.iter().enumerate() {
+                    self.visit_statement(statement, Location { block, statement_index });
+                }
+                self.visit_terminator_kind(data.terminator());
+            }
+            if !self.changed || self.usage == ParamUsage::Escapes {
+                break;
+            }
+        }
+    }
+
+    fn add_usage(&mut self, usage: ParamUsage) {
+        self.usage = self.usage.max(usage);
+    }
+
+    /// Records that `place` now holds `holds` of the parameter.
+    fn assign(&mut self, place: Place<'tcx>, holds: Option<Holds>) {
+        let Some(holds) = holds else {
+            return;
+        };
+        if let Some(local) = place.as_local()
+            && local != RETURN_PLACE
+        {
+            let slot = &mut self.holds[local];
+            if slot.is_none_or(|old| old < holds) {
+                *slot = Some(holds);
+                self.changed = true;
+            }
+        } else {
+            self.add_usage(ParamUsage::Escapes);
+        }
+    }
+
+    /// Returns what a value of type `ty`, read from `place`, holds of the parameter.
+    fn read(&self, place: Place<'tcx>, moved: bool, ty: Ty<'tcx>) -> Option<Holds> {
+        let holds = self.holds[place.local]?;
+        if moved || (!place.is_indirect() && holds != Holds::Value) {
+            Some(holds)
+        } else {
+            // A copy of data inside of it, which may hold a reference to it
+            may_borrow(ty).then_some(Holds::Shared)
+        }
+    }
+
+    fn read_operand(&self, operand: &Operand<'tcx>, ty: Ty<'tcx>) -> Option<Holds> {
+        match *operand {
+            Operand::Copy(place) => self.read(place, false, ty),
+            Operand::Move(place) => self.read(place, true, ty),
+            Operand::Constant(_) | Operand::RuntimeChecks(_) => None,
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &rustc_middle::mir::Statement<'tcx>, location: Location) {
+        match &statement.kind {
+            StatementKind::Assign((dest, rvalue)) => {
+                if self.holds[dest.local].is_some_and(|holds| holds != Holds::Shared)
+                    && (!dest.projection.is_empty() || dest.local == self.param)
+                {
+                    // Writing into it, or reassigning the parameter
+                    self.add_usage(ParamUsage::Mutated);
+                }
+                let tcx = self.cx.tcx;
+                let dest_ty = dest.ty(self.mir, tcx).ty;
+                let holds = match rvalue {
+                    Rvalue::Use(operand, _)
+                    | Rvalue::Repeat(operand, _)
+                    | Rvalue::Cast(_, operand, _)
+                    | Rvalue::UnaryOp(_, operand)
+                    | Rvalue::WrapUnsafeBinder(operand, _) => self.read_operand(operand, dest_ty),
+                    Rvalue::BinaryOp(_, operands) => self
+                        .read_operand(&operands.0, dest_ty)
+                        .max(self.read_operand(&operands.1, dest_ty)),
+                    Rvalue::CopyForDeref(place) => self.read(*place, false, dest_ty),
+                    Rvalue::Ref(_, kind, place) => self.holds[place.local].map(|_| match kind.mutability() {
+                        Mutability::Not => Holds::Shared,
+                        Mutability::Mut => Holds::Mut,
+                    }),
+                    Rvalue::Reborrow(_, mutbl, place) => self.holds[place.local].map(|_| match mutbl {
+                        Mutability::Not => Holds::Shared,
+                        Mutability::Mut => Holds::Mut,
+                    }),
+                    Rvalue::Aggregate(_, operands) => operands
+                        .iter()
+                        .filter_map(|operand| self.read_operand(operand, operand.ty(self.mir, tcx)))
+                        .max(),
+                    Rvalue::RawPtr(_, place) => {
+                        if self.holds[place.local].is_some() {
+                            self.add_usage(ParamUsage::Escapes);
+                        }
+                        None
+                    },
+                    Rvalue::ThreadLocalRef(_) | Rvalue::Discriminant(_) => None,
+                };
+                self.assign(*dest, holds);
+            },
+            StatementKind::SetDiscriminant { place, .. }
+                if self.holds[place.local].is_some_and(|holds| holds != Holds::Shared) =>
+            {
+                self.add_usage(ParamUsage::Mutated);
+            },
+            StatementKind::Intrinsic(_) if self.mentions_param(|v| v.visit_statement(statement, location)) => {
+                self.add_usage(ParamUsage::Escapes);
+            },
+            _ => {},
+        }
+    }
+
+    fn visit_terminator_kind(&mut self, terminator: &Terminator<'tcx>) {
+        match &terminator.kind {
+            TerminatorKind::Call { func, args, .. } | TerminatorKind::TailCall { func, args, .. } => {
+                let tcx = self.cx.tcx;
+                let callee = match *func.ty(self.mir, tcx).kind() {
+                    ty::FnDef(def_id, _) => Some(def_id),
+                    _ => None,
+                };
+                let mut returned = None;
+                for (index, arg) in args.iter().enumerate() {
+                    let Some(holds) = self.read_operand(&arg.node, arg.node.ty(self.mir, tcx)) else {
+                        continue;
+                    };
+                    let callee_usage = callee.and_then(|callee| self.usages.get(self.cx, callee, index));
+                    self.add_usage(match holds {
+                        Holds::Shared => ParamUsage::Borrowed,
+                        Holds::Mut if callee_usage == Some(ParamUsage::Borrowed) => ParamUsage::Borrowed,
+                        Holds::Mut => ParamUsage::Mutated,
+                        Holds::Value => callee_usage.unwrap_or(ParamUsage::MovedOut),
+                    });
+                    if holds != Holds::Value {
+                        returned = returned.max(Some(holds));
+                    }
+                }
+                if let TerminatorKind::Call { destination, .. } = terminator.kind
+                    && may_borrow(destination.ty(self.mir, tcx).ty)
+                {
+                    // The result may borrow from the arguments
+                    self.assign(destination, returned);
+                }
+            },
+            TerminatorKind::Yield { .. } | TerminatorKind::InlineAsm { .. }
+                if self.mentions_param(|v| v.visit_terminator(terminator, Location::START)) =>
+            {
+                self.add_usage(ParamUsage::Escapes);
+            },
+            _ => {},
+        }
+    }
+
+    /// Checks whether the places visited by `f` include a local holding some of the parameter.
+    fn mentions_param(&self, f: impl FnOnce(&mut MentionsVisitor<'_>)) -> bool {
+        let mut v = MentionsVisitor {
+            holds: &self.holds,
+            found: false,
+        };
+        f(&mut v);
+        v.found
+    }
+}
+
+struct MentionsVisitor<'a> {
+    holds: &'a IndexVec<Local, Option<Holds>>,
+    found: bool,
+}
+
+impl Visitor<'_> for MentionsVisitor<'_> {
+    fn visit_local(&mut self, local: Local, _: PlaceContext, _: Location) {
+        self.found |= self.holds[local].is_some();
+    }
+}
+
+/// Checks whether a value of type `ty` may hold a reference or a pointer.
+fn may_borrow(ty: Ty<'_>) -> bool {
+    ty.walk().any(|arg| match arg.kind() {
+        GenericArgKind::Lifetime(_) => true,
+        GenericArgKind::Type(ty) => matches!(ty.kind(), ty::RawPtr(..) | ty::Param(_) | ty::Alias(..)),
+        GenericArgKind::Const(_) => false,
+    })
+}
@@ -136,6 +136,8 @@ path_macros! {
 // Paths in the standard library missing a diagnostic item
 
 // Paths in external crates
+pub static FANCY_REGEX_BUILDER_NEW: PathLookup = value_path!(fancy_regex::RegexBuilder::new);
+pub static FANCY_REGEX_NEW: PathLookup = value_path!(fancy_regex::Regex::new);
 pub static FUTURES_IO_ASYNCREADEXT: PathLookup = type_path!(futures_util::AsyncReadExt);
 pub static FUTURES_IO_ASYNCWRITEEXT: PathLookup = type_path!(futures_util::AsyncWriteExt);
 pub static ITERTOOLS_NEXT_TUPLE: PathLookup = value_path!(itertools::Itertools::next_tuple);
@@ -148,6 +150,8 @@ pub static REGEX_BUILDER_NEW: PathLookup = value_path!(regex::RegexBuilder::new)
 pub static REGEX_BYTES_BUILDER_NEW: PathLookup = value_path!(regex::bytes::RegexBuilder::new);
 pub static REGEX_BYTES_NEW: PathLookup = value_path!(regex::bytes::Regex::new);
 pub static REGEX_BYTES_SET_NEW: PathLookup = value_path!(regex::bytes::RegexSet::new);
+pub static REGEX_LITE_BUILDER_NEW: PathLookup = value_path!(regex_lite::RegexBuilder::new);
+pub static REGEX_LITE_NEW: PathLookup = value_path!(regex_lite::Regex::new);
 pub static REGEX_NEW: PathLookup = value_path!(regex::Regex::new);
 pub static REGEX_SET_NEW: PathLookup = value_path!(regex::RegexSet::new);
 pub static SERDE_DESERIALIZE: PathLookup = type_path!(serde::de::Deserialize);
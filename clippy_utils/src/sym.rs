@@ -47,6 +47,9 @@ generate! {
     BinaryHeap,
     CLIPPY_ARGS,
     CLIPPY_CONF_DIR,
+    CLIPPY_PASS_TIMINGS,
+    CLIPPY_SARIF_OUTPUT,
+    CLIPPY_VERIFY_SUGGESTIONS,
     CRLF: "\r\n",
     Cargo_toml: "Cargo.toml",
     Child,
@@ -98,6 +101,7 @@ generate! {
     OpenOptions,
     OsStr,
     OsString,
+    PascalCase,
     Path,
     PathBuf,
     PathLookup,
@@ -108,6 +112,8 @@ generate! {
     Regex,
     RegexBuilder,
     RegexSet,
+    SCREAMING_KEBAB_CASE: "SCREAMING-KEBAB-CASE",
+    SCREAMING_SNAKE_CASE,
     Saturating,
     SeekFrom,
     SliceIter,
@@ -118,6 +124,7 @@ generate! {
     TBD,
     ToOwned,
     ToString,
+    UPPERCASE,
     UpperExp,
     UpperHex,
     V4,
@@ -156,8 +163,10 @@ generate! {
     build_hasher,
     by_ref,
     bytes,
+    camelCase,
     capacity,
     cargo_clippy: "cargo-clippy",
+    case_insensitive,
     cast,
     cast_const,
     cast_mut,
@@ -202,6 +211,7 @@ generate! {
     count_ones,
     create,
     create_new,
+    crlf,
     cstring_as_c_str,
     cx,
     cycle,
@@ -209,10 +219,13 @@ generate! {
     dbg_macro,
     de,
     debug_struct,
+    deny_unknown_fields,
     deprecated_in_future,
     deref_mut_method,
+    deserialize_with,
     diagnostics,
     disallowed_types,
+    dot_matches_new_line,
     drain,
     dump,
     duration_constructors,
@@ -264,6 +277,7 @@ generate! {
     f64_legacy_const_nan,
     f64_legacy_const_neg_infinity,
     f64_legacy_const_radix,
+    fancy_regex,
     file_options,
     filter,
     filter_map,
@@ -352,6 +366,7 @@ generate! {
     i8_legacy_fn_max_value,
     i8_legacy_fn_min_value,
     i8_legacy_mod,
+    ignore_whitespace,
     ilog,
     include_bytes_macro,
     include_str_macro,
@@ -399,6 +414,7 @@ generate! {
     iter_repeat,
     itertools,
     join,
+    kebab_case: "kebab-case",
     kw,
     lazy_static,
     leading_zeros,
@@ -409,6 +425,7 @@ generate! {
     log,
     log10,
     log2,
+    lowercase,
     macro_concat,
     macro_use_imports,
     map_break,
@@ -437,6 +454,7 @@ generate! {
     module_name_repetitions,
     msrv,
     msrvs,
+    multi_line,
     mut_ptr,
     mutex,
     needless_return,
@@ -445,6 +463,7 @@ generate! {
     next_if_eq,
     next_multiple_of,
     next_tuple,
+    no_panic,
     nth,
     ok,
     ok_or,
@@ -467,6 +486,7 @@ generate! {
     path_to_pathbuf,
     pathbuf_as_path,
     paths,
+    pattern,
     peek,
     peek_mut,
     peekable,
@@ -508,7 +528,11 @@ generate! {
     redundant_imports,
     redundant_pub_crate,
     regex,
+    regex_lite,
     rem_euclid,
+    rename,
+    rename_all,
+    rename_all_fields,
     repeat,
     replace,
     replacen,
@@ -547,11 +571,14 @@ generate! {
     signum,
     single_component_path_imports,
     skip,
+    skip_deserializing,
+    skip_serializing_if,
     skip_while,
     slice_from_ref,
     slice_iter,
     slice_mut_unchecked,
     slice_unchecked,
+    snake_case,
     sort,
     sort_by,
     sort_unstable_by,
@@ -593,6 +620,7 @@ generate! {
     subsec_micros,
     subsec_nanos,
     sum,
+    swap_greed,
     symbol,
     take,
     take_while,
@@ -623,9 +651,11 @@ generate! {
     try_fold,
     try_for_each,
     try_from_fn,
+    unicode,
     unimplemented_macro,
     unreachable_pub,
     unsafe_removed_from_name,
+    untagged,
     unused,
     unused_braces,
     unused_extern_crates,
@@ -652,6 +682,7 @@ generate! {
     wake,
     warnings,
     wildcard_imports,
+    with,
     with_capacity,
     wrapping_neg,
     wrapping_offset,
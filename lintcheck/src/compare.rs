@@ -0,0 +1,197 @@
+//! Comparison of the warnings emitted by two different Clippy builds on the same crates.
+
+use std::env::consts::EXE_SUFFIX;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::{env, fs, iter};
+
+use crate::config::LintcheckConfig;
+use crate::input::RecursiveOptions;
+use crate::output::ClippyCheckOutput;
+use crate::{Crate, build_clippy, json, run_clippy, target_dir};
+
+/// A `clippy-driver` binary to run on the crates.
+pub(crate) struct ClippyBuild {
+    pub driver: PathBuf,
+    /// The output of `clippy-driver --version`
+    pub version: String,
+    /// The directory containing the `rustc_driver` library the driver was built with. Only set
+    /// when it may differ from the one used by lintcheck's toolchain.
+    lib_dir: Option<PathBuf>,
+}
+
+impl ClippyBuild {
+    /// Builds clippy inside the repo.
+    pub fn current(release_build: bool, pass_timings: bool) -> Self {
+        let version = build_clippy(release_build, pass_timings);
+        let profile = if release_build { "release" } else { "debug" };
+        let driver = fs::canonicalize(format!("{}/{profile}/clippy-driver{EXE_SUFFIX}", target_dir())).unwrap();
+
+        // assert that clippy is found
+        assert!(
+            driver.is_file(),
+            "{}/{profile}/clippy-driver binary not found! {}",
+            target_dir(),
+            driver.display()
+        );
+
+        Self {
+            driver,
+            version,
+            lib_dir: None,
+        }
+    }
+
+    /// Uses either an existing `cargo-clippy` or `clippy-driver` binary, or builds clippy at the
+    /// given git revision.
+    pub fn new(spec: &str, config: &LintcheckConfig) -> Self {
+        let path = Path::new(spec);
+        if path.is_file() {
+            Self::from_binary(path)
+        } else {
+            Self::from_revision(spec, config)
+        }
+    }
+
+    fn from_binary(path: &Path) -> Self {
+        // `clippy-driver` is always installed next to `cargo-clippy`
+        let driver = if path.file_stem().is_some_and(|stem| stem == "cargo-clippy") {
+            path.with_file_name(format!("clippy-driver{EXE_SUFFIX}"))
+        } else {
+            path.to_owned()
+        };
+        let driver = fs::canonicalize(&driver).unwrap_or_else(|e| panic!("failed to find {}: {e}", driver.display()));
+        let version = Command::new(&driver)
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map_or_else(
+                || driver.display().to_string(),
+                |output| String::from_utf8_lossy(&output.stdout).into_owned(),
+            );
+
+        Self {
+            driver,
+            version,
+            lib_dir: None,
+        }
+    }
+
+    /// Builds clippy at a revision of the repo. The revision is checked out in a separate
+    /// worktree which is kept around to avoid rebuilding it every time.
+    fn from_revision(rev: &str, config: &LintcheckConfig) -> Self {
+        let output = Command::new("git")
+            .args(["rev-parse", "--verify", "--quiet", &format!("{rev}^{{commit}}")])
+            .output()
+            .expect("failed to run git");
+        if !output.status.success() {
+            eprintln!("Error: `{rev}` is neither a Clippy binary nor a git revision");
+            std::process::exit(1);
+        }
+        let commit = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+
+        let worktree = PathBuf::from(format!("{}/lintcheck/clippy-builds/{commit}", target_dir()));
+        if !worktree.is_dir() {
+            println!("Checking out {rev} ({commit}) to {}", worktree.display());
+            let status = Command::new("git")
+                .args(["worktree", "add", "--detach", "--force"])
+                .arg(&worktree)
+                .arg(&commit)
+                .status()
+                .expect("failed to run git");
+            assert!(status.success(), "failed to check out {rev}");
+        }
+
+        // The revision may use a different toolchain. Removing `RUSTUP_TOOLCHAIN`, which is set
+        // by `cargo run`, lets rustup pick it up from the worktree's `rust-toolchain.toml`.
+        println!("Building Clippy at {rev}");
+        let mut build_cmd = Command::new("cargo");
+        build_cmd.args(["run", "--bin=clippy-driver"]);
+        if config.offline {
+            build_cmd.arg("--offline");
+        }
+        let output = build_cmd
+            .args(["--", "--version"])
+            .current_dir(&worktree)
+            .env_remove("RUSTUP_TOOLCHAIN")
+            .env_remove("CARGO_TARGET_DIR")
+            .stderr(Stdio::inherit())
+            .output()
+            .unwrap();
+        if !output.status.success() {
+            eprintln!("Error: Failed to compile Clippy at {rev}!");
+            std::process::exit(1);
+        }
+
+        let sysroot = Command::new("rustc")
+            .args(["--print", "sysroot"])
+            .current_dir(&worktree)
+            .env_remove("RUSTUP_TOOLCHAIN")
+            .output()
+            .unwrap();
+        let sysroot = PathBuf::from(String::from_utf8_lossy(&sysroot.stdout).trim());
+
+        Self {
+            driver: fs::canonicalize(worktree.join(format!("target/debug/clippy-driver{EXE_SUFFIX}"))).unwrap(),
+            version: String::from_utf8_lossy(&output.stdout).into_owned(),
+            lib_dir: Some(sysroot.join(if cfg!(windows) { "bin" } else { "lib" })),
+        }
+    }
+
+    /// Sets up the environment needed to run the driver.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        if let Some(lib_dir) = &self.lib_dir {
+            let var = if cfg!(windows) {
+                "PATH"
+            } else if cfg!(target_os = "macos") {
+                "DYLD_FALLBACK_LIBRARY_PATH"
+            } else {
+                "LD_LIBRARY_PATH"
+            };
+            let existing = env::var_os(var).unwrap_or_default();
+            let paths = iter::once(lib_dir.clone()).chain(env::split_paths(&existing));
+            cmd.env(var, env::join_paths(paths).unwrap());
+        }
+    }
+}
+
+/// Runs both builds on the crates, saves their warnings as JSON logs and prints the diff between
+/// them.
+pub(crate) fn compare(
+    config: &LintcheckConfig,
+    crates: &[Crate],
+    recursive_options: &RecursiveOptions,
+    old: &ClippyBuild,
+    new: &ClippyBuild,
+) {
+    let stem = config.sources_toml_path.file_stem().unwrap().to_string_lossy();
+    let old_path = config
+        .lintcheck_results_path
+        .with_file_name(format!("{stem}_old_logs.json"));
+    let new_path = config
+        .lintcheck_results_path
+        .with_file_name(format!("{stem}_new_logs.json"));
+    fs::create_dir_all(old_path.parent().unwrap()).unwrap();
+
+    for (clippy, path) in [(old, &old_path), (new, &new_path)] {
+        println!("Linting with {}", clippy.version.trim());
+        let mut warnings = Vec::new();
+        let mut ices = Vec::new();
+        for entry in run_clippy(config, clippy, crates, recursive_options) {
+            match entry {
+                ClippyCheckOutput::ClippyWarning(warning) => warnings.push(warning),
+                ClippyCheckOutput::RustcIce(ice) => {
+                    println!("{ice}");
+                    ices.push(ice);
+                },
+                // `--fix` can't be used when comparing builds
+                ClippyCheckOutput::FixFailure(_) => {},
+            }
+        }
+        println!("Writing logs to {}", path.display());
+        fs::write(path, json::output(warnings, ices, Vec::new())).unwrap();
+    }
+
+    json::diff(&old_path, &new_path, config.truncate, None);
+}
@@ -34,7 +34,8 @@ pub(crate) struct LintcheckConfig {
     /// Only process a single crate on the list
     #[clap(long, value_name = "CRATE")]
     pub only: Option<String>,
-    /// Runs cargo clippy --fix and checks if all suggestions apply
+    /// Runs cargo clippy --fix and checks if all suggestions apply. When they don't, each lint's
+    /// suggestions are applied separately to find the ones that break the code
     #[clap(long, conflicts_with("max_jobs"))]
     pub fix: bool,
     /// Apply a filter to only collect specified lints
@@ -55,6 +56,24 @@ pub(crate) struct LintcheckConfig {
     /// `target/lintcheck/sources/<package>-<version>/perf.data`
     #[clap(long)]
     pub perf: bool,
+    /// Time each of Clippy's lint passes and add a ranked table to the logs. The timings are also
+    /// saved to `lintcheck-logs/<name>_logs.timings.json` and compared with the previous run
+    #[clap(long, conflicts_with_all = ["fix", "perf"])]
+    pub pass_timings: bool,
+    /// Compare the warnings of two Clippy builds and print the diff. Each build is either a git
+    /// revision of this repo, which will be built in a separate worktree, or the path to a
+    /// `cargo-clippy` or `clippy-driver` binary
+    #[clap(long, value_name = "REV|PATH", conflicts_with_all = ["fix", "perf", "pass_timings"])]
+    pub old_clippy: Option<String>,
+    /// The Clippy build to compare against `--old-clippy`, defaults to the current build
+    #[clap(long, value_name = "REV|PATH", requires = "old_clippy")]
+    pub new_clippy: Option<String>,
+    /// Limit the number of warnings printed for each lint when comparing builds
+    #[clap(long, requires = "old_clippy")]
+    pub truncate: bool,
+    /// Run cargo without accessing the network
+    #[clap(long)]
+    pub offline: bool,
     #[command(subcommand)]
     pub subcommand: Option<Commands>,
 }
@@ -106,9 +125,11 @@ impl LintcheckConfig {
         // for the path where we save the lint results, get the filename without extension (so for
         // wasd.toml, use "wasd"...)
         let filename: PathBuf = config.sources_toml_path.file_stem().unwrap().into();
+        // --fix only records fix failures and ICEs, so it doesn't overwrite the warnings
         config.lintcheck_results_path = PathBuf::from(format!(
-            "lintcheck-logs/{}_logs.{}",
+            "lintcheck-logs/{}_{}logs.{}",
             filename.display(),
+            if config.fix { "fix_" } else { "" },
             config.format.file_extension(),
         ));
 
@@ -0,0 +1,266 @@
+//! Finds the lints whose suggestions break a crate when running with `--fix`.
+//!
+//! When `cargo fix` fails to apply Clippy's suggestions to a crate, the suggestions of each lint
+//! are applied on their own to a scratch copy of the crate. Every lint whose fixed code no longer
+//! compiles is reported along with the lines it changed nearest to the first error.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::{fmt, fs};
+
+use cargo_metadata::Message;
+use cargo_metadata::diagnostic::{Applicability, Diagnostic, DiagnosticLevel};
+use serde::{Deserialize, Serialize};
+
+use crate::compare::ClippyBuild;
+use crate::config::LintcheckConfig;
+use crate::input::copy_dir;
+use crate::{Crate, shared_target_dir, target_dir};
+
+/// The number of unchanged lines to show around the changed lines
+const CONTEXT_LINES: usize = 1;
+
+/// A suggestion that produced code which no longer compiles.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FixFailure {
+    pub crate_name: String,
+    /// The lint whose suggestions were applied e.g. `clippy::needless_return`, or `None` if the
+    /// code only broke with the suggestions of all lints applied together
+    pub lint: Option<String>,
+    /// The first error in the fixed code e.g. ``error[E0425]: cannot find value `x` in this scope``
+    pub error: String,
+    /// The file and line of the error e.g. `src/lib.rs:12`
+    pub location: String,
+    /// The lines changed by the fix closest to the error, before applying it
+    pub before: String,
+    /// The same lines after applying the fix
+    pub after: String,
+}
+
+impl FixFailure {
+    pub fn lint_name(&self) -> &str {
+        self.lint.as_deref().unwrap_or("all lints")
+    }
+}
+
+impl fmt::Display for FixFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: the suggestions of `{}` produced code that does not compile",
+            self.crate_name,
+            self.lint_name()
+        )?;
+        writeln!(f, "{} at {}", self.error, self.location)?;
+        writeln!(f, "before:\n{}", self.before)?;
+        writeln!(f, "after:\n{}", self.after)?;
+        writeln!(f, "========================================")
+    }
+}
+
+/// Applies the suggestions of each lint to a copy of the crate to find the ones that break it.
+pub(crate) fn find_failures(
+    krate: &Crate,
+    clippy: &ClippyBuild,
+    config: &LintcheckConfig,
+    lint_levels_args: &[String],
+    thread_index: usize,
+) -> Vec<FixFailure> {
+    let scratch = PathBuf::from(format!(
+        "{}/lintcheck/fix-failures/{}-{}",
+        target_dir(),
+        krate.name,
+        krate.version
+    ));
+    let target_dir = shared_target_dir(&format!("_{thread_index:?}_fix"));
+    let runner = Runner {
+        krate,
+        clippy,
+        config,
+        scratch: &scratch,
+        target_dir: &target_dir,
+    };
+
+    runner.reset();
+    let lints = runner.fixable_lints(lint_levels_args);
+    println!(
+        "Applying the suggestions of {} lints to {} {} one at a time",
+        lints.len(),
+        krate.name,
+        krate.version
+    );
+
+    let mut failures = Vec::new();
+    for lint in lints {
+        runner.reset();
+        runner.fix(&["--cap-lints=allow".into(), format!("--force-warn={lint}")]);
+        failures.extend(runner.check(Some(lint)));
+    }
+
+    // The suggestions may only conflict when applied together
+    if failures.is_empty() {
+        runner.reset();
+        runner.fix(lint_levels_args);
+        failures.extend(runner.check(None));
+    }
+
+    let _ = fs::remove_dir_all(&scratch);
+    failures
+}
+
+struct Runner<'a> {
+    krate: &'a Crate,
+    clippy: &'a ClippyBuild,
+    config: &'a LintcheckConfig,
+    /// The copy of the crate the suggestions are applied to
+    scratch: &'a Path,
+    target_dir: &'a Path,
+}
+
+impl Runner<'_> {
+    /// Replaces the scratch copy with the crate's sources.
+    fn reset(&self) {
+        let _ = fs::remove_dir_all(self.scratch);
+        fs::create_dir_all(self.scratch.parent().unwrap()).unwrap();
+        copy_dir(&self.krate.path, self.scratch);
+    }
+
+    fn cargo(&self, subcommand: &str, lint_args: &[String]) -> Command {
+        let mut clippy_args: Vec<&str> = self.krate.options.iter().flatten().map(String::as_str).collect();
+        clippy_args.extend(lint_args.iter().map(String::as_str));
+
+        let mut cmd = Command::new("cargo");
+        self.clippy.apply_env(&mut cmd);
+        cmd.arg(subcommand)
+            .arg("--quiet")
+            .args(self.config.offline.then_some("--offline"))
+            .current_dir(self.scratch)
+            .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
+            .env("CLIPPY_DISABLE_DOCS_LINKS", "1")
+            .env("CARGO_TARGET_DIR", self.target_dir)
+            .env("RUSTC_WORKSPACE_WRAPPER", &self.clippy.driver);
+        cmd
+    }
+
+    /// Runs `cargo check` and returns the diagnostics.
+    fn diagnostics(&self, lint_args: &[String]) -> Vec<Diagnostic> {
+        let output = self
+            .cargo("check", lint_args)
+            .arg("--message-format=json")
+            .output()
+            .unwrap();
+        Message::parse_stream(output.stdout.as_slice())
+            .filter_map(|msg| match msg {
+                Ok(Message::CompilerMessage(message)) => Some(message.message),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Finds the lints with suggestions that `cargo fix` applies.
+    fn fixable_lints(&self, lint_levels_args: &[String]) -> BTreeSet<String> {
+        self.diagnostics(lint_levels_args)
+            .into_iter()
+            .filter(|diag| {
+                diag.children.iter().chain([diag]).any(|diag| {
+                    diag.spans
+                        .iter()
+                        .any(|span| span.suggestion_applicability == Some(Applicability::MachineApplicable))
+                })
+            })
+            .filter_map(|diag| diag.code.map(|code| code.code))
+            .filter(|lint| lint.starts_with("clippy::"))
+            .collect()
+    }
+
+    fn fix(&self, lint_args: &[String]) {
+        let status = self
+            .cargo("fix", lint_args)
+            .args(["--broken-code", "--allow-dirty", "--allow-no-vcs"])
+            .output()
+            .unwrap()
+            .status;
+        if !status.success() {
+            println!(
+                "`cargo fix` exited with {status} when fixing {} {}",
+                self.krate.name, self.krate.version
+            );
+        }
+    }
+
+    /// Checks whether the fixed code still compiles.
+    fn check(&self, lint: Option<String>) -> Option<FixFailure> {
+        let error = self
+            .diagnostics(&["--cap-lints=allow".into()])
+            .into_iter()
+            .find(|diag| diag.level == DiagnosticLevel::Error && !diag.spans.is_empty())?;
+        let span = error
+            .spans
+            .iter()
+            .find(|span| span.is_primary)
+            .unwrap_or(&error.spans[0]);
+
+        let original = fs::read_to_string(self.krate.path.join(&span.file_name)).unwrap_or_default();
+        let fixed = fs::read_to_string(self.scratch.join(&span.file_name)).unwrap_or_default();
+        let (before, after) = changed_lines(&original, &fixed, span.line_start);
+
+        Some(FixFailure {
+            crate_name: self.krate.name.clone(),
+            lint,
+            error: match &error.code {
+                Some(code) => format!("error[{}]: {}", code.code, error.message),
+                None => format!("error: {}", error.message),
+            },
+            location: format!("{}:{}", span.file_name, span.line_start),
+            before,
+            after,
+        })
+    }
+}
+
+/// Finds the lines changed between `old` and `new` which are closest to `line` in `new`, and
+/// returns them before and after the change with some surrounding context.
+fn changed_lines(old: &str, new: &str, line: usize) -> (String, String) {
+    use diff::Result::{Both, Left, Right};
+
+    let changes = diff::lines(old, new);
+
+    // The ranges of changed lines in `changes` with the first line they cover in `new`
+    let mut hunks: Vec<(usize, usize, usize)> = Vec::new();
+    let mut new_line = 1;
+    for (i, change) in changes.iter().enumerate() {
+        match change {
+            Both(..) => new_line += 1,
+            Left(_) | Right(_) => {
+                match hunks.last_mut() {
+                    Some((_, end, _)) if *end == i => *end = i + 1,
+                    _ => hunks.push((i, i + 1, new_line)),
+                }
+                if let Right(_) = change {
+                    new_line += 1;
+                }
+            },
+        }
+    }
+
+    let Some(&(start, end, _)) = hunks.iter().min_by_key(|(_, _, first_line)| first_line.abs_diff(line)) else {
+        return (String::new(), String::new());
+    };
+    let start = start.saturating_sub(CONTEXT_LINES);
+    let end = (end + CONTEXT_LINES).min(changes.len());
+
+    let mut before = Vec::new();
+    let mut after = Vec::new();
+    for change in &changes[start..end] {
+        match change {
+            Both(line, _) => {
+                before.push(*line);
+                after.push(*line);
+            },
+            Left(line) => before.push(*line),
+            Right(line) => after.push(*line),
+        }
+    }
+    (before.join("\n"), after.join("\n"))
+}
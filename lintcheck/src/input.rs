@@ -13,6 +13,7 @@ use crate::{Crate, lintcheck_sources, target_dir};
 const DEFAULT_DOCS_LINK: &str = "https://docs.rs/{krate}/{version}/src/{krate_}/{file}.html#{line}";
 const DEFAULT_GITHUB_LINK: &str = "{url}/blob/{hash}/src/{file}#L{line}";
 const DEFAULT_PATH_LINK: &str = "{path}/src/{file}:{line}";
+const DEFAULT_TARBALL_LINK: &str = "{tarball}/src/{file}:{line}";
 
 /// List of sources to check, loaded from a .toml file
 #[derive(Debug, Deserialize)]
@@ -22,7 +23,7 @@ pub struct SourceList {
     recursive: RecursiveOptions,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Clone, Debug, Deserialize, Default)]
 pub struct RecursiveOptions {
     pub ignore: HashSet<String>,
 }
@@ -36,6 +37,8 @@ struct TomlCrate {
     git_url: Option<String>,
     git_hash: Option<String>,
     path: Option<String>,
+    /// A local `.crate`, `.tar.gz` or `.tar` archive
+    tarball: Option<String>,
     options: Option<Vec<String>>,
     /// Magic values:
     /// * `{krate}` will be replaced by `self.name`
@@ -44,6 +47,7 @@ struct TomlCrate {
     /// * `{url}` will be replaced with `self.git_url`
     /// * `{hash}` will be replaced with `self.git_hash`
     /// * `{path}` will be replaced with `self.path`
+    /// * `{tarball}` will be replaced with `self.tarball`
     /// * `{file}` will be replaced by the path after `src/`
     /// * `{line}` will be replaced by the line
     ///
@@ -70,6 +74,9 @@ impl TomlCrate {
         if let Some(path) = &self.path {
             link = link.replace("{path}", path);
         }
+        if let Some(tarball) = &self.tarball {
+            link = link.replace("{tarball}", tarball);
+        }
         link
     }
 }
@@ -89,6 +96,7 @@ pub enum CrateSource {
     CratesIo { version: String },
     Git { url: String, commit: String },
     Path { path: PathBuf },
+    Tarball { path: PathBuf },
 }
 
 /// Read a `lintcheck_crates.toml` file
@@ -113,6 +121,15 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 file_link: tk.file_link(DEFAULT_PATH_LINK),
                 options: tk.options.clone(),
             });
+        } else if let Some(ref tarball) = tk.tarball {
+            crate_sources.push(CrateWithSource {
+                name: tk.name.clone(),
+                source: CrateSource::Tarball {
+                    path: PathBuf::from(tarball),
+                },
+                file_link: tk.file_link(DEFAULT_TARBALL_LINK),
+                options: tk.options.clone(),
+            });
         } else if let Some(ref version) = tk.version {
             crate_sources.push(CrateWithSource {
                 name: tk.name.clone(),
@@ -140,6 +157,7 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
         // if we have a version as well as a git data OR only one git data, something is funky
         if tk.version.is_some() && (tk.git_url.is_some() || tk.git_hash.is_some())
             || tk.git_hash.is_some() != tk.git_url.is_some()
+            || tk.path.is_some() && tk.tarball.is_some()
         {
             eprintln!("tomlkrate: {tk:?}");
             assert_eq!(
@@ -148,8 +166,12 @@ pub fn read_crates(toml_path: &Path) -> (Vec<CrateWithSource>, RecursiveOptions)
                 "Error: Encountered TomlCrate with only one of git_hash and git_url!"
             );
             assert!(
-                tk.path.is_none() || (tk.git_hash.is_none() && tk.version.is_none()),
-                "Error: TomlCrate can only have one of 'git_.*', 'version' or 'path' fields"
+                (tk.path.is_none() && tk.tarball.is_none()) || (tk.git_hash.is_none() && tk.version.is_none()),
+                "Error: TomlCrate can only have one of 'git_.*', 'version', 'path' or 'tarball' fields"
+            );
+            assert!(
+                tk.path.is_none() || tk.tarball.is_none(),
+                "Error: TomlCrate can only have one of 'git_.*', 'version', 'path' or 'tarball' fields"
             );
             unreachable!("Failed to translate TomlCrate into CrateSource!");
         }
@@ -279,14 +301,6 @@ impl CrateWithSource {
                 }
             },
             CrateSource::Path { path } => {
-                fn is_cache_dir(entry: &DirEntry) -> bool {
-                    fs::read(entry.path().join("CACHEDIR.TAG"))
-                        .is_ok_and(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
-                }
-
-                // copy path into the dest_crate_root but skip directories that contain a CACHEDIR.TAG file.
-                // The target/ directory contains a CACHEDIR.TAG file so it is the most commonly skipped directory
-                // as a result of this filter.
                 let dest_crate_root = PathBuf::from(lintcheck_sources()).join(name);
                 if dest_crate_root.exists() {
                     println!("Deleting existing directory at `{}`", dest_crate_root.display());
@@ -294,25 +308,42 @@ impl CrateWithSource {
                 }
 
                 println!("Copying `{}` to `{}`", path.display(), dest_crate_root.display());
+                copy_dir(path, &dest_crate_root);
 
-                for entry in WalkDir::new(path).into_iter().filter_entry(|e| !is_cache_dir(e)) {
-                    let entry = entry.unwrap();
-                    let entry_path = entry.path();
-                    let relative_entry_path = entry_path.strip_prefix(path).unwrap();
-                    let dest_path = dest_crate_root.join(relative_entry_path);
-                    let metadata = entry_path.symlink_metadata().unwrap();
+                Crate {
+                    version: String::from("local"),
+                    name: name.clone(),
+                    path: dest_crate_root,
+                    options: options.clone(),
+                    base_url: file_link.clone(),
+                }
+            },
+            CrateSource::Tarball { path } => {
+                let extract_dir = PathBuf::from(lintcheck_sources()).join(format!("{name}-tarball"));
+                if extract_dir.exists() {
+                    fs::remove_dir_all(&extract_dir).unwrap();
+                }
 
-                    if metadata.is_dir() {
-                        fs::create_dir(dest_path).unwrap();
-                    } else if metadata.is_file() {
-                        fs::copy(entry_path, dest_path).unwrap();
-                    }
+                println!("Extracting `{}` to `{}`", path.display(), extract_dir.display());
+                let tarball = fs::File::open(path).unwrap_or_else(|e| panic!("Failed to open {}: {e}", path.display()));
+                if path.extension().is_some_and(|ext| ext == "tar") {
+                    tar::Archive::new(tarball).unpack(&extract_dir)
+                } else {
+                    tar::Archive::new(flate2::read::GzDecoder::new(tarball)).unpack(&extract_dir)
                 }
+                .expect("Failed to extract!");
+
+                // archives usually contain a single directory, e.g. `crate-1.2.3/` in `.crate` files
+                let mut entries = fs::read_dir(&extract_dir).unwrap().map(|entry| entry.unwrap().path());
+                let crate_root = match (entries.next(), entries.next()) {
+                    (Some(dir), None) if dir.is_dir() => dir,
+                    _ => extract_dir,
+                };
 
                 Crate {
                     version: String::from("local"),
                     name: name.clone(),
-                    path: dest_crate_root,
+                    path: crate_root,
                     options: options.clone(),
                     base_url: file_link.clone(),
                 }
@@ -321,6 +352,30 @@ impl CrateWithSource {
     }
 }
 
+/// Copies the directory `src` to `dest`, skipping directories that contain a `CACHEDIR.TAG` file.
+/// The target/ directory contains a `CACHEDIR.TAG` file so it is the most commonly skipped
+/// directory as a result of this filter.
+pub(crate) fn copy_dir(src: &Path, dest: &Path) {
+    fn is_cache_dir(entry: &DirEntry) -> bool {
+        fs::read(entry.path().join("CACHEDIR.TAG"))
+            .is_ok_and(|x| x.starts_with(b"Signature: 8a477f597d28d172789f06886806bc55"))
+    }
+
+    for entry in WalkDir::new(src).into_iter().filter_entry(|e| !is_cache_dir(e)) {
+        let entry = entry.unwrap();
+        let entry_path = entry.path();
+        let relative_entry_path = entry_path.strip_prefix(src).unwrap();
+        let dest_path = dest.join(relative_entry_path);
+        let metadata = entry_path.symlink_metadata().unwrap();
+
+        if metadata.is_dir() {
+            fs::create_dir(dest_path).unwrap();
+        } else if metadata.is_file() {
+            fs::copy(entry_path, dest_path).unwrap();
+        }
+    }
+}
+
 /// Create necessary directories to run the lintcheck tool.
 ///
 /// # Panics
@@ -1,9 +1,11 @@
 //! JSON output and comparison functionality for Clippy warnings.
 //!
-//! This module handles serialization of Clippy warnings to JSON format,
-//! loading warnings from JSON files, and generating human-readable diffs
-//! between different linting runs.
+//! This module handles serialization of Clippy warnings, ICEs and fix failures to JSON format,
+//! loading them from JSON files, and generating human-readable diffs between different linting
+//! runs.
 
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::{fmt, fs};
 
@@ -11,6 +13,8 @@ use itertools::{EitherOrBoth, Itertools as _};
 use serde::{Deserialize, Serialize};
 
 use crate::ClippyWarning;
+use crate::fix::FixFailure;
+use crate::output::RustcIce;
 
 /// This is the total number. 300 warnings results in 100 messages per section.
 const DEFAULT_LIMIT_PER_LINT: usize = 300;
@@ -38,12 +42,34 @@ impl LintJson {
     }
 }
 
+/// The contents of a log file in the JSON format
+#[derive(Debug, Deserialize, Serialize)]
+struct LogJson {
+    warnings: Vec<LintJson>,
+    #[serde(default)]
+    ices: Vec<RustcIce>,
+    #[serde(default)]
+    fix_failures: Vec<FixFailure>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyLogJson {
+    Log(LogJson),
+    /// Logs written before ICEs and fix failures were recorded only contain the warnings
+    Warnings(Vec<LintJson>),
+}
+
 #[derive(Debug, Serialize)]
 struct SummaryRow {
     name: String,
     added: usize,
     removed: usize,
     changed: usize,
+    /// The number of new ICEs in the lint's code
+    ices: usize,
+    /// The number of new fix failures caused by the lint's suggestions
+    fix_failures: usize,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,22 +77,39 @@ struct Summary(Vec<SummaryRow>);
 
 impl fmt::Display for Summary {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(
-            "\
+        // Only show the ICE and fix failure columns when there are any
+        let show_failures = self.0.iter().any(|row| row.ices != 0 || row.fix_failures != 0);
+        if show_failures {
+            f.write_str(
+                "\
+| Lint | Added | Removed | Changed | ICEs | Fix failures |
+| ---- | ----: | ------: | ------: | ---: | -----------: |
+",
+            )?;
+        } else {
+            f.write_str(
+                "\
 | Lint | Added | Removed | Changed |
 | ---- | ----: | ------: | ------: |
 ",
-        )?;
+            )?;
+        }
 
         for SummaryRow {
             name,
             added,
             changed,
             removed,
+            ices,
+            fix_failures,
         } in &self.0
         {
             let html_id = to_html_id(name);
-            writeln!(f, "| [`{name}`](#{html_id}) | {added} | {removed} | {changed} |")?;
+            write!(f, "| [`{name}`](#{html_id}) | {added} | {removed} | {changed} |")?;
+            if show_failures {
+                write!(f, " {ices} | {fix_failures} |")?;
+            }
+            writeln!(f)?;
         }
 
         Ok(())
@@ -74,23 +117,55 @@ impl fmt::Display for Summary {
 }
 
 impl Summary {
-    fn new(lints: &[LintWarnings]) -> Self {
-        Summary(
-            lints
-                .iter()
-                .map(|lint| SummaryRow {
-                    name: lint.name.clone(),
-                    added: lint.added.len(),
-                    removed: lint.removed.len(),
-                    changed: lint.changed.len(),
-                })
-                .collect(),
-        )
+    fn new(lints: &[LintWarnings], failures: &FailureDiff) -> Self {
+        let mut rows: BTreeMap<String, SummaryRow> = lints
+            .iter()
+            .map(|lint| {
+                (
+                    lint.name.clone(),
+                    SummaryRow {
+                        name: lint.name.clone(),
+                        added: lint.added.len(),
+                        removed: lint.removed.len(),
+                        changed: lint.changed.len(),
+                        ices: 0,
+                        fix_failures: 0,
+                    },
+                )
+            })
+            .collect();
+        let ices = failures.new_ices.iter().map(|ice| (ice.lint.as_deref(), true));
+        let fix_failures = failures
+            .new_fix_failures
+            .iter()
+            .map(|failure| (failure.lint.as_deref(), false));
+        for (lint, is_ice) in ices.chain(fix_failures) {
+            // Names not from a lint must still be valid for the PR comment
+            let name = lint.filter(|lint| lint.starts_with("clippy::")).unwrap_or("unknown");
+            let row = rows.entry(name.to_owned()).or_insert_with(|| SummaryRow {
+                name: name.to_owned(),
+                added: 0,
+                removed: 0,
+                changed: 0,
+                ices: 0,
+                fix_failures: 0,
+            });
+            if is_ice {
+                row.ices += 1;
+            } else {
+                row.fix_failures += 1;
+            }
+        }
+        Summary(rows.into_values().collect())
     }
 }
 
 /// Creates the log file output for [`crate::config::OutputFormat::Json`]
-pub(crate) fn output(clippy_warnings: Vec<ClippyWarning>) -> String {
+pub(crate) fn output(
+    clippy_warnings: Vec<ClippyWarning>,
+    mut ices: Vec<RustcIce>,
+    mut fix_failures: Vec<FixFailure>,
+) -> String {
     let mut lints: Vec<LintJson> = clippy_warnings
         .into_iter()
         .map(|warning| {
@@ -109,14 +184,89 @@ pub(crate) fn output(clippy_warnings: Vec<ClippyWarning>) -> String {
         })
         .collect();
     lints.sort_by(|a, b| a.key().cmp(&b.key()));
-    serde_json::to_string(&lints).unwrap()
+    ices.sort_by(|a, b| a.signature().cmp(&b.signature()));
+    fix_failures.sort_by(|a, b| fix_failure_key(a).cmp(&fix_failure_key(b)));
+    serde_json::to_string(&LogJson {
+        warnings: lints,
+        ices,
+        fix_failures,
+    })
+    .unwrap()
 }
 
-/// Loads lint warnings from a JSON file at the given path.
-fn load_warnings(path: &Path) -> Vec<LintJson> {
+/// Loads lint warnings, ICEs and fix failures from a JSON file at the given path.
+fn load_log(path: &Path) -> LogJson {
     let file = fs::read(path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
 
-    serde_json::from_slice(&file).unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", path.display()))
+    match serde_json::from_slice(&file) {
+        Ok(AnyLogJson::Log(log)) => log,
+        Ok(AnyLogJson::Warnings(warnings)) => LogJson {
+            warnings,
+            ices: Vec::new(),
+            fix_failures: Vec::new(),
+        },
+        Err(e) => panic!("failed to deserialize {}: {e}", path.display()),
+    }
+}
+
+fn fix_failure_key(failure: &FixFailure) -> impl Ord + '_ {
+    (
+        failure.crate_name.as_str(),
+        failure.lint.as_deref(),
+        failure.error.as_str(),
+        failure.before.as_str(),
+    )
+}
+
+/// The ICEs and fix failures that only occur in one of the logs.
+struct FailureDiff {
+    new_ices: Vec<RustcIce>,
+    fixed_ices: Vec<RustcIce>,
+    new_fix_failures: Vec<FixFailure>,
+    fixed_fix_failures: Vec<FixFailure>,
+}
+
+impl FailureDiff {
+    fn new(old: &mut LogJson, new: &mut LogJson) -> Self {
+        let (fixed_ices, new_ices) =
+            only_in_one(std::mem::take(&mut old.ices), std::mem::take(&mut new.ices), |a, b| {
+                a.signature().cmp(&b.signature())
+            });
+        let (fixed_fix_failures, new_fix_failures) = only_in_one(
+            std::mem::take(&mut old.fix_failures),
+            std::mem::take(&mut new.fix_failures),
+            |a, b| fix_failure_key(a).cmp(&fix_failure_key(b)),
+        );
+        Self {
+            new_ices,
+            fixed_ices,
+            new_fix_failures,
+            fixed_fix_failures,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.new_ices.is_empty()
+            && self.fixed_ices.is_empty()
+            && self.new_fix_failures.is_empty()
+            && self.fixed_fix_failures.is_empty()
+    }
+}
+
+/// Splits two lists into the items only in `old` and the items only in `new`.
+fn only_in_one<T>(mut old: Vec<T>, mut new: Vec<T>, cmp: impl Fn(&T, &T) -> Ordering) -> (Vec<T>, Vec<T>) {
+    old.sort_by(&cmp);
+    new.sort_by(&cmp);
+    let mut only_old = Vec::new();
+    let mut only_new = Vec::new();
+    for item in itertools::merge_join_by(old, new, &cmp) {
+        match item {
+            EitherOrBoth::Left(old) => only_old.push(old),
+            EitherOrBoth::Right(new) => only_new.push(new),
+            EitherOrBoth::Both(..) => {},
+        }
+    }
+    (only_old, only_new)
 }
 
 /// Generates and prints a diff between two sets of lint warnings.
@@ -124,8 +274,11 @@ fn load_warnings(path: &Path) -> Vec<LintJson> {
 /// Compares warnings from `old_path` and `new_path`, then displays a summary table
 /// and detailed information about added, removed, and changed warnings.
 pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summary: Option<PathBuf>) {
-    let old_warnings = load_warnings(old_path);
-    let new_warnings = load_warnings(new_path);
+    let mut old_log = load_log(old_path);
+    let mut new_log = load_log(new_path);
+    let failures = FailureDiff::new(&mut old_log, &mut new_log);
+    let old_warnings = old_log.warnings;
+    let new_warnings = new_log.warnings;
 
     let mut lint_warnings = vec![];
 
@@ -157,11 +310,11 @@ pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summa
         }
     }
 
-    if lint_warnings.is_empty() {
+    if lint_warnings.is_empty() && failures.is_empty() {
         return;
     }
 
-    let summary = Summary::new(&lint_warnings);
+    let summary = Summary::new(&lint_warnings, &failures);
     if let Some(path) = write_summary {
         let json = serde_json::to_string(&summary).unwrap();
         fs::write(path, json).unwrap();
@@ -170,7 +323,7 @@ pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summa
     let truncate_after = if truncate {
         // Max 15 ensures that we at least have five messages per lint
         DEFAULT_LIMIT_PER_LINT
-            .min(TRUNCATION_TOTAL_TARGET / lint_warnings.len())
+            .min(TRUNCATION_TOTAL_TARGET / lint_warnings.len().max(1))
             .max(15)
     } else {
         // No lint should ever each this number of lint emissions, so this is equivialent to
@@ -179,6 +332,7 @@ pub(crate) fn diff(old_path: &Path, new_path: &Path, truncate: bool, write_summa
     };
 
     println!("{summary}");
+    print_failures(&failures);
     for lint in lint_warnings {
         print_lint_warnings(&lint, truncate_after);
     }
@@ -213,6 +367,59 @@ fn print_lint_warnings(lint: &LintWarnings, truncate_after: usize) {
     print_changed_diff(&lint.changed, truncate_after / 3);
 }
 
+/// Prints the ICEs and fix failures which were added or fixed.
+fn print_failures(failures: &FailureDiff) {
+    for (title, ices) in [("New ICEs", &failures.new_ices), ("Fixed ICEs", &failures.fixed_ices)] {
+        if ices.is_empty() {
+            continue;
+        }
+        println!("<h2>{title}</h2>");
+        println!();
+        for ice in ices {
+            let lint = ice.lint.as_deref().unwrap_or("unknown lint");
+            println!("`{}` in `{lint}`: {}", ice.crate_name, ice.message);
+            println!();
+            println!("```");
+            for frame in &ice.frames {
+                println!("{frame}");
+            }
+            println!("```");
+            println!();
+        }
+    }
+
+    for (title, fix_failures) in [
+        ("New fix failures", &failures.new_fix_failures),
+        ("Fixed fix failures", &failures.fixed_fix_failures),
+    ] {
+        if fix_failures.is_empty() {
+            continue;
+        }
+        println!("<h2>{title}</h2>");
+        println!();
+        for failure in fix_failures {
+            println!(
+                "The suggestions of `{}` in `{}` caused `{}` at `{}`",
+                failure.lint_name(),
+                failure.crate_name,
+                failure.error,
+                failure.location
+            );
+            println!();
+            println!("```diff");
+            for change in diff::lines(&failure.before, &failure.after) {
+                match change {
+                    diff::Result::Both(unchanged, _) => println!(" {unchanged}"),
+                    diff::Result::Left(removed) => println!("-{removed}"),
+                    diff::Result::Right(added) => println!("+{added}"),
+                }
+            }
+            println!("```");
+            println!();
+        }
+    }
+}
+
 /// Prints a section of warnings with a header and formatted code blocks.
 fn print_warnings(title: &str, warnings: &[LintJson], truncate_after: usize) {
     if warnings.is_empty() {
@@ -18,18 +18,23 @@
     clippy::needless_borrows_for_generic_args
 )]
 
+mod compare;
 mod config;
 mod driver;
+mod fix;
 mod input;
 mod json;
 mod output;
 mod popular_crates;
 mod recursive;
+mod timings;
 
+use crate::compare::ClippyBuild;
 use crate::config::{Commands, LintcheckConfig, OutputFormat};
+use crate::fix::FixFailure;
+use crate::input::RecursiveOptions;
 use crate::recursive::LintcheckServer;
 
-use std::env::consts::EXE_SUFFIX;
 use std::io::{self};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -67,7 +72,7 @@ impl Crate {
     #[expect(clippy::too_many_lines)]
     fn run_clippy_lints(
         &self,
-        clippy_driver_path: &Path,
+        clippy: &ClippyBuild,
         target_dir_index: &AtomicUsize,
         total_crates_to_lint: usize,
         config: &LintcheckConfig,
@@ -141,11 +146,21 @@ impl Crate {
             cmd = Command::new("cargo");
         }
 
+        if config.pass_timings {
+            let dir = timings::crate_dir(&self.name, &self.version);
+            let _ = fs::remove_dir_all(&dir);
+            cmd.env("CLIPPY_PASS_TIMINGS", dir);
+        }
+
+        clippy.apply_env(&mut cmd);
         cmd.arg(if config.fix { "fix" } else { "check" })
             .arg("--quiet")
+            .args(config.offline.then_some("--offline"))
             .current_dir(&self.path)
             .env("CLIPPY_ARGS", clippy_args.join("__CLIPPY_HACKERY__"))
-            .env("CLIPPY_DISABLE_DOCS_LINKS", "1");
+            .env("CLIPPY_DISABLE_DOCS_LINKS", "1")
+            // Used to identify ICEs, see `RustcIce`
+            .env("RUST_BACKTRACE", "1");
 
         if let Some(server) = server {
             // `cargo clippy` is a wrapper around `cargo check` that mainly sets `RUSTC_WORKSPACE_WRAPPER` to
@@ -161,7 +176,7 @@ impl Crate {
                 .env("RUSTC_WRAPPER", env::current_exe().unwrap())
                 // Pass the absolute path so `crate::driver` can find `clippy-driver`, as it's executed in various
                 // different working directories
-                .env("CLIPPY_DRIVER", clippy_driver_path)
+                .env("CLIPPY_DRIVER", &clippy.driver)
                 .env("LINTCHECK_SERVER", server.local_addr.to_string())
                 .status()
                 .expect("failed to run cargo");
@@ -171,7 +186,10 @@ impl Crate {
             return Vec::new();
         }
 
-        if !config.fix && !config.perf {
+        if config.fix {
+            // The sources are inside Clippy's repository, where cargo considers them to be uncommitted
+            cmd.args(["--allow-dirty", "--allow-no-vcs"]);
+        } else if !config.perf {
             cmd.arg("--message-format=json");
         }
 
@@ -180,7 +198,7 @@ impl Crate {
             // use the looping index to create individual target dirs
             .env("CARGO_TARGET_DIR", shared_target_dir.as_os_str())
             // Roughly equivalent to `cargo clippy`/`cargo clippy --fix`
-            .env("RUSTC_WORKSPACE_WRAPPER", clippy_driver_path)
+            .env("RUSTC_WORKSPACE_WRAPPER", &clippy.driver)
             .output()
             .unwrap();
         let stdout = String::from_utf8_lossy(&all_output.stdout);
@@ -195,6 +213,9 @@ impl Crate {
         }
 
         if config.fix {
+            if let Some(ice) = RustcIce::from_stderr_and_status(&self.name, *status, &stderr) {
+                return vec![ClippyCheckOutput::RustcIce(ice)];
+            }
             if let Some(stderr) = stderr
                 .lines()
                 .find(|line| line.contains("failed to automatically apply fixes suggested by rustc to crate"))
@@ -204,13 +225,17 @@ impl Crate {
                     "ERROR: failed to apply some suggestion to {} / to (sub)crate {subcrate}",
                     self.name
                 );
+                return fix::find_failures(self, clippy, config, lint_levels_args, thread_index)
+                    .into_iter()
+                    .map(ClippyCheckOutput::FixFailure)
+                    .collect();
             }
             // fast path, we don't need the warnings anyway
             return Vec::new();
         }
 
         // We don't want to keep target directories if benchmarking
-        if config.perf {
+        if config.perf || config.pass_timings {
             let _ = fs::remove_dir_all(&shared_target_dir);
         }
 
@@ -263,12 +288,13 @@ fn normalize_diag(
 }
 
 /// Builds clippy inside the repo to make sure we have a clippy executable we can use.
-fn build_clippy(release_build: bool) -> String {
+fn build_clippy(release_build: bool, pass_timings: bool) -> String {
     let mut build_cmd = Command::new("cargo");
     build_cmd.args([
         "run",
         "--bin=clippy-driver",
         if release_build { "-r" } else { "" },
+        if pass_timings { "--features=pass-timings" } else { "" },
         "--",
         "--version",
     ]);
@@ -306,66 +332,26 @@ fn main() {
             new,
             truncate,
             write_summary,
-        }) => json::diff(&old, &new, truncate, write_summary),
+        }) => {
+            json::diff(&old, &new, truncate, write_summary);
+            if let (Some(old), Some(new)) = (
+                timings::load(&timings::path_for_logs(&old)),
+                timings::load(&timings::path_for_logs(&new)),
+            ) {
+                timings::print_diff(&old, &new);
+            }
+        },
         Some(Commands::Popular { output, number }) => popular_crates::fetch(output, number).unwrap(),
         None => lintcheck(config),
     }
 }
 
-#[expect(clippy::too_many_lines)]
 fn lintcheck(config: LintcheckConfig) {
-    let clippy_ver = build_clippy(config.perf);
-    let clippy_driver_path = fs::canonicalize(format!(
-        "{}/{}/clippy-driver{EXE_SUFFIX}",
-        target_dir(),
-        if config.perf { "release" } else { "debug" }
-    ))
-    .unwrap();
-
-    // assert that clippy is found
-    assert!(
-        clippy_driver_path.is_file(),
-        "{}/{}/clippy-driver binary not found! {}",
-        target_dir(),
-        if config.perf { "release" } else { "debug" },
-        clippy_driver_path.display()
-    );
-
     // download and extract the crates, then run clippy on them and collect clippy's warnings
     // flatten into one big list of warnings
 
     let (crates, recursive_options) = read_crates(&config.sources_toml_path);
 
-    let counter = AtomicUsize::new(1);
-    let mut lint_level_args: Vec<String> = vec!["--cap-lints=allow".into()];
-    if config.lint_filter.is_empty() {
-        let groups = if config.all_lints {
-            &[
-                "clippy::all",
-                "clippy::cargo",
-                "clippy::nursery",
-                "clippy::pedantic",
-                "clippy::restriction",
-            ][..]
-        } else {
-            &["clippy::all", "clippy::pedantic"]
-        };
-        groups
-            .iter()
-            .map(|group| format!("--force-warn={group}"))
-            .collect_into(&mut lint_level_args);
-    } else {
-        config
-            .lint_filter
-            .iter()
-            .map(|filter| {
-                let mut filter = filter.clone();
-                filter.insert_str(0, "--force-warn=");
-                filter
-            })
-            .collect_into(&mut lint_level_args);
-    }
-
     let crates: Vec<Crate> = crates
         .into_iter()
         .filter(|krate| {
@@ -396,20 +382,120 @@ fn lintcheck(config: LintcheckConfig) {
         .build_global()
         .unwrap();
 
+    if let Some(old) = &config.old_clippy {
+        let old = ClippyBuild::new(old, &config);
+        let new = match &config.new_clippy {
+            Some(new) => ClippyBuild::new(new, &config),
+            None => ClippyBuild::current(false, false),
+        };
+        compare::compare(&config, &crates, &recursive_options, &old, &new);
+        return;
+    }
+
+    // Timings of a debug build aren't representative
+    let clippy = ClippyBuild::current(config.perf || config.pass_timings, config.pass_timings);
+    let clippy_entries = run_clippy(&config, &clippy, &crates, &recursive_options);
+    let timings = config.pass_timings.then(|| timings::collect(&crates));
+
+    // split up warnings, ices and fix failures
+    let mut warnings: Vec<ClippyWarning> = vec![];
+    let mut raw_ices: Vec<RustcIce> = vec![];
+    let mut fix_failures: Vec<FixFailure> = vec![];
+    for entry in clippy_entries {
+        match entry {
+            ClippyCheckOutput::ClippyWarning(x) => warnings.push(x),
+            ClippyCheckOutput::RustcIce(x) => raw_ices.push(x),
+            ClippyCheckOutput::FixFailure(x) => fix_failures.push(x),
+        }
+    }
+
+    // The ICEs are included in the JSON output, but CI still has to fail because of them.
+    let fail_on_ices = matches!(config.format, OutputFormat::Json) && !raw_ices.is_empty();
+    let text = match config.format {
+        OutputFormat::Text | OutputFormat::Markdown => {
+            let mut text =
+                output::summarize_and_print_changes(&warnings, &raw_ices, &fix_failures, clippy.version, &config);
+            if let Some(timings) = &timings {
+                text.push_str("\n\n### Pass timings:\n\n");
+                text.push_str(&timings.to_string());
+            }
+            text
+        },
+        OutputFormat::Json => {
+            for ice in &raw_ices {
+                println!("{ice}");
+            }
+            json::output(warnings, raw_ices, fix_failures)
+        },
+    };
+
+    println!("Writing logs to {}", config.lintcheck_results_path.display());
+    fs::create_dir_all(config.lintcheck_results_path.parent().unwrap()).unwrap();
+    fs::write(&config.lintcheck_results_path, text).unwrap();
+
+    if let Some(timings) = timings {
+        let path = timings::path_for_logs(&config.lintcheck_results_path);
+        if let Some(old) = timings::load(&path) {
+            timings::print_diff(&old, &timings);
+        }
+        println!("Writing pass timings to {}", path.display());
+        fs::write(&path, serde_json::to_string(&timings).unwrap()).unwrap();
+    }
+
+    assert!(!fail_on_ices, "Some crates ICEd");
+}
+
+/// Runs the given clippy build on all the crates and collects its warnings and ICEs.
+fn run_clippy(
+    config: &LintcheckConfig,
+    clippy: &ClippyBuild,
+    crates: &[Crate],
+    recursive_options: &RecursiveOptions,
+) -> Vec<ClippyCheckOutput> {
+    let counter = AtomicUsize::new(1);
+    let mut lint_level_args: Vec<String> = vec!["--cap-lints=allow".into()];
+    if config.lint_filter.is_empty() {
+        let groups = if config.all_lints {
+            &[
+                "clippy::all",
+                "clippy::cargo",
+                "clippy::nursery",
+                "clippy::pedantic",
+                "clippy::restriction",
+            ][..]
+        } else {
+            &["clippy::all", "clippy::pedantic"]
+        };
+        groups
+            .iter()
+            .map(|group| format!("--force-warn={group}"))
+            .collect_into(&mut lint_level_args);
+    } else {
+        config
+            .lint_filter
+            .iter()
+            .map(|filter| {
+                let mut filter = filter.clone();
+                filter.insert_str(0, "--force-warn=");
+                filter
+            })
+            .collect_into(&mut lint_level_args);
+    }
+
     let server = config.recursive.then(|| {
         let _: io::Result<()> = fs::remove_dir_all(format!("{}/lintcheck/shared_target_dir/recursive", target_dir()));
 
-        LintcheckServer::spawn(recursive_options)
+        LintcheckServer::spawn(recursive_options.clone())
     });
 
     let mut clippy_entries: Vec<ClippyCheckOutput> = crates
         .par_iter()
         .flat_map(|krate| {
             krate.run_clippy_lints(
-                &clippy_driver_path,
+                clippy,
                 &counter,
                 crates.len(),
-                &config,
+                config,
                 &lint_level_args,
                 server.as_ref(),
             )
@@ -422,41 +508,7 @@ fn lintcheck(config: LintcheckConfig) {
         clippy_entries.extend(server_clippy_entries);
     }
 
-    // if we are in --fix mode, don't change the log files, terminate here
-    if config.fix {
-        return;
-    }
-
-    // split up warnings and ices
-    let mut warnings: Vec<ClippyWarning> = vec![];
-    let mut raw_ices: Vec<RustcIce> = vec![];
-    for entry in clippy_entries {
-        if let ClippyCheckOutput::ClippyWarning(x) = entry {
-            warnings.push(x);
-        } else if let ClippyCheckOutput::RustcIce(x) = entry {
-            raw_ices.push(x);
-        }
-    }
-
-    let text = match config.format {
-        OutputFormat::Text | OutputFormat::Markdown => {
-            output::summarize_and_print_changes(&warnings, &raw_ices, clippy_ver, &config)
-        },
-        OutputFormat::Json => {
-            if !raw_ices.is_empty() {
-                for ice in raw_ices {
-                    println!("{ice}");
-                }
-                panic!("Some crates ICEd");
-            }
-
-            json::output(warnings)
-        },
-    };
-
-    println!("Writing logs to {}", config.lintcheck_results_path.display());
-    fs::create_dir_all(config.lintcheck_results_path.parent().unwrap()).unwrap();
-    fs::write(&config.lintcheck_results_path, text).unwrap();
+    clippy_entries
 }
 
 /// Traverse a directory looking for `perf.data.<number>` files, and adds one
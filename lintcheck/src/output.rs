@@ -7,29 +7,49 @@ use std::path::Path;
 use std::process::ExitStatus;
 
 use crate::config::{LintcheckConfig, OutputFormat};
+use crate::fix::FixFailure;
 
 /// A single emitted output from clippy being executed on a crate. It may either be a
-/// `ClippyWarning`, or a `RustcIce` caused by a panic within clippy. A crate may have many
-/// `ClippyWarning`s but a maximum of one `RustcIce` (at which point clippy halts execution).
+/// `ClippyWarning`, a `RustcIce` caused by a panic within clippy, or a `FixFailure` when running
+/// with `--fix`. A crate may have many `ClippyWarning`s but a maximum of one `RustcIce` (at which
+/// point clippy halts execution).
 #[derive(Debug)]
 pub enum ClippyCheckOutput {
     ClippyWarning(ClippyWarning),
     RustcIce(RustcIce),
+    FixFailure(FixFailure),
 }
 
-#[derive(Debug)]
+/// The number of frames from Clippy's crates used to identify an ICE
+const SIGNATURE_FRAMES: usize = 3;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct RustcIce {
     pub crate_name: String,
+    /// The panic message, without the location of `bug!`s in the compiler
+    pub message: String,
+    /// The innermost functions of the backtrace that are part of Clippy e.g.
+    /// `clippy_lints::methods::unwrap_used::check`
+    pub frames: Vec<String>,
+    /// The lint whose code panicked, guessed from the file of the innermost frame in `clippy_lints`
+    pub lint: Option<String>,
+    #[serde(skip)]
     pub ice_content: String,
 }
 
 impl fmt::Display for RustcIce {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
+        writeln!(
             f,
-            "{}:\n{}\n========================================\n",
-            self.crate_name, self.ice_content
-        )
+            "{} ({}): {}",
+            self.crate_name,
+            self.lint.as_deref().unwrap_or("unknown lint"),
+            self.message
+        )?;
+        for frame in &self.frames {
+            writeln!(f, "    at {frame}")?;
+        }
+        write!(f, "{}\n========================================\n", self.ice_content)
     }
 }
 
@@ -38,14 +58,99 @@ impl RustcIce {
         if status.code().unwrap_or(0) == 101
         /* ice exit status */
         {
+            let (frames, locations): (Vec<_>, Vec<_>) = backtrace(stderr).into_iter().unzip();
+            let panic_location = stderr
+                .lines()
+                .find_map(|line| line.split_once(" panicked at ").map(|(_, location)| location));
+            let lint = locations
+                .into_iter()
+                .flatten()
+                .chain(panic_location)
+                .find_map(lint_from_path);
             Some(Self {
                 crate_name: crate_name.to_owned(),
+                message: panic_message(stderr),
+                frames: frames.into_iter().take(SIGNATURE_FRAMES).collect(),
+                lint,
                 ice_content: stderr.to_owned(),
             })
         } else {
             None
         }
     }
+
+    /// Identifies the same ICE across runs.
+    pub fn signature(&self) -> (&str, &str, &[String]) {
+        (&self.crate_name, &self.message, &self.frames)
+    }
+}
+
+/// Gets the message of the first `bug!` or panic.
+fn panic_message(stderr: &str) -> String {
+    let mut lines = stderr.lines();
+    if let Some(message) = stderr
+        .lines()
+        .find_map(|line| line.strip_prefix("error: internal compiler error: "))
+    {
+        // Remove the location, e.g. `compiler/rustc_middle/src/ty/mod.rs:12:34: `
+        return message
+            .split_once(".rs:")
+            .and_then(|(_, rest)| rest.split_once(": "))
+            .map_or(message, |(_, message)| message)
+            .to_owned();
+    }
+    lines
+        .find(|line| line.contains(" panicked at "))
+        .and_then(|_| lines.next())
+        .unwrap_or_default()
+        .trim()
+        .to_owned()
+}
+
+/// Gets the functions of Clippy's crates in the backtrace, innermost first, along with their
+/// source locations.
+fn backtrace(stderr: &str) -> Vec<(String, Option<&str>)> {
+    let mut frames: Vec<(String, Option<&str>)> = Vec::new();
+    let mut lines = stderr.lines().skip_while(|line| !line.starts_with("stack backtrace:"));
+    while let Some(line) = lines.next() {
+        let line = line.trim();
+        if let Some(location) = line.strip_prefix("at ") {
+            if let Some((_, frame_location)) = frames.last_mut()
+                && frame_location.is_none()
+            {
+                *frame_location = Some(location);
+            }
+        } else if let Some((index, function)) = line.split_once(": ")
+            && index.bytes().all(|b| b.is_ascii_digit())
+        {
+            // Remove the symbol hash, e.g. `::h0123456789abcdef`
+            let function = match function.rsplit_once("::h") {
+                Some((path, hash)) if hash.len() == 16 && hash.bytes().all(|b| b.is_ascii_hexdigit()) => path,
+                _ => function,
+            };
+            if function.trim_start_matches('<').starts_with("clippy") {
+                frames.push((function.to_owned(), None));
+            } else {
+                // Skip the location of frames outside of Clippy
+                while lines.clone().next().is_some_and(|line| line.trim().starts_with("at ")) {
+                    lines.next();
+                }
+            }
+        } else if line.starts_with("note:") {
+            break;
+        }
+    }
+    frames
+}
+
+/// Turns the path of a file in `clippy_lints` into the name of the lint it likely implements e.g.
+/// `clippy_lints/src/methods/unwrap_used.rs:12:5` -> `clippy::unwrap_used`
+fn lint_from_path(path: &str) -> Option<String> {
+    let (_, path) = path.split_once("clippy_lints/src/")?;
+    let (module, _) = path.split_once(".rs")?;
+    let module = module.strip_suffix("/mod").unwrap_or(module);
+    let name = module.rsplit('/').next()?;
+    (name != "lib").then(|| format!("clippy::{name}"))
 }
 
 /// A single warning that clippy issued while checking a `Crate`
@@ -129,6 +234,7 @@ impl ClippyWarning {
 pub fn summarize_and_print_changes(
     warnings: &[ClippyWarning],
     ices: &[RustcIce],
+    fix_failures: &[FixFailure],
     clippy_ver: String,
     config: &LintcheckConfig,
 ) -> String {
@@ -152,6 +258,12 @@ pub fn summarize_and_print_changes(
     for ice in ices {
         writeln!(text, "{ice}").unwrap();
     }
+    if !fix_failures.is_empty() {
+        text.push_str("\n\n### Fix failures:\n");
+        for failure in fix_failures {
+            writeln!(text, "{failure}").unwrap();
+        }
+    }
 
     print_stats(old_stats, new_stats, &config.lint_filter);
 
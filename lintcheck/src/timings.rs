@@ -0,0 +1,177 @@
+//! Time spent in each of Clippy's lint passes, collected with `--pass-timings`.
+//!
+//! `clippy-driver` writes the timings of every compilation to `CLIPPY_PASS_TIMINGS`, which is set
+//! to a separate directory for each crate. These are summed into one entry per pass, ranked by the
+//! total time across all crates.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fmt, fs};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Crate, clippy_project_root, target_dir};
+
+/// An entry of the files written by `clippy-driver`
+#[derive(Deserialize)]
+struct DriverTiming {
+    kind: String,
+    pass: String,
+    nanos: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct PassTiming {
+    /// Either `early` or `late`
+    kind: String,
+    /// The name of the pass e.g. `Methods`
+    pass: String,
+    /// The total time across all crates
+    nanos: u64,
+    /// The time spent in each crate
+    crates: BTreeMap<String, u64>,
+}
+
+impl PassTiming {
+    fn slowest_crate(&self) -> Option<(&String, &u64)> {
+        self.crates.iter().max_by_key(|(_, nanos)| **nanos)
+    }
+}
+
+/// The timings of all passes, slowest first.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub(crate) struct Timings(Vec<PassTiming>);
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total: u64 = self.0.iter().map(|timing| timing.nanos).sum();
+        f.write_str(
+            "\
+| Rank | Pass | Kind | Time (ms) | Share | Slowest crate |
+| ---: | ---- | ---- | --------: | ----: | ------------- |
+",
+        )?;
+        for (rank, timing) in self.0.iter().enumerate() {
+            let slowest = timing.slowest_crate().map_or_else(String::new, |(name, nanos)| {
+                format!("{name} ({:.1} ms)", millis(*nanos))
+            });
+            writeln!(
+                f,
+                "| {} | `{}` | {} | {:.1} | {:.1}% | {slowest} |",
+                rank + 1,
+                timing.pass,
+                timing.kind,
+                millis(timing.nanos),
+                percent(timing.nanos, total),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The directory `clippy-driver` writes the timings of a crate to.
+pub(crate) fn crate_dir(name: &str, version: &str) -> PathBuf {
+    clippy_project_root().join(format!("{}/lintcheck/pass-timings/{name}-{version}", target_dir()))
+}
+
+/// The file the timings are saved to alongside the given log file, e.g.
+/// `lintcheck-logs/lintcheck_crates_logs.timings.json`
+pub(crate) fn path_for_logs(logs: &Path) -> PathBuf {
+    logs.with_extension("timings.json")
+}
+
+/// Reads and ranks the timings written by `clippy-driver` for the crates.
+pub(crate) fn collect(crates: &[Crate]) -> Timings {
+    let mut passes: BTreeMap<(String, String), PassTiming> = BTreeMap::new();
+    for krate in crates {
+        let crate_name = format!("{}-{}", krate.name, krate.version);
+        let Ok(entries) = fs::read_dir(crate_dir(&krate.name, &krate.version)) else {
+            continue;
+        };
+        for entry in entries {
+            let path = entry.unwrap().path();
+            let contents = fs::read(&path).unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+            let timings: Vec<DriverTiming> = serde_json::from_slice(&contents)
+                .unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", path.display()));
+            for DriverTiming { kind, pass, nanos } in timings {
+                let timing = passes
+                    .entry((kind.clone(), pass.clone()))
+                    .or_insert_with(|| PassTiming {
+                        kind,
+                        pass,
+                        nanos: 0,
+                        crates: BTreeMap::new(),
+                    });
+                timing.nanos += nanos;
+                *timing.crates.entry(crate_name.clone()).or_default() += nanos;
+            }
+        }
+    }
+
+    let mut timings: Vec<PassTiming> = passes.into_values().collect();
+    timings.sort_by(|a, b| b.nanos.cmp(&a.nanos).then_with(|| a.pass.cmp(&b.pass)));
+    Timings(timings)
+}
+
+/// Loads the timings saved by a previous run, if there are any.
+pub(crate) fn load(path: &Path) -> Option<Timings> {
+    let file = fs::read(path).ok()?;
+    Some(serde_json::from_slice(&file).unwrap_or_else(|e| panic!("failed to deserialize {}: {e}", path.display())))
+}
+
+/// Prints a table of the passes whose time changed, largest change first.
+pub(crate) fn print_diff(old: &Timings, new: &Timings) {
+    let key = |timing: &PassTiming| (timing.kind.clone(), timing.pass.clone());
+    let mut changes: BTreeMap<(String, String), (u64, u64)> = BTreeMap::new();
+    for timing in &old.0 {
+        changes.entry(key(timing)).or_default().0 = timing.nanos;
+    }
+    for timing in &new.0 {
+        changes.entry(key(timing)).or_default().1 = timing.nanos;
+    }
+    let mut changes: Vec<_> = changes.into_iter().filter(|(_, (old, new))| old != new).collect();
+    if changes.is_empty() {
+        return;
+    }
+    changes.sort_by_key(|(_, (old, new))| std::cmp::Reverse(old.abs_diff(*new)));
+
+    let mut table = String::from(
+        "\
+| Pass | Kind | Old (ms) | New (ms) | Change |
+| ---- | ---- | -------: | -------: | -----: |
+",
+    );
+    for ((kind, pass), (old, new)) in changes {
+        let change = if old == 0 {
+            "new".to_owned()
+        } else {
+            let sign = if new >= old { "+" } else { "-" };
+            format!("{sign}{:.1}%", percent(old.abs_diff(new), old))
+        };
+        writeln!(
+            table,
+            "| `{pass}` | {kind} | {:.1} | {:.1} | {change} |",
+            millis(old),
+            millis(new)
+        )
+        .unwrap();
+    }
+
+    println!("### Pass timings\n");
+    println!("{table}");
+}
+
+fn millis(nanos: u64) -> f64 {
+    Duration::from_nanos(nanos).as_secs_f64() * 1000.0
+}
+
+fn percent(part: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        Duration::from_nanos(part).as_secs_f64() / Duration::from_nanos(total).as_secs_f64() * 100.0
+    }
+}
@@ -0,0 +1,281 @@
+//! Baseline files record the diagnostics of Clippy's lints in a crate so that later runs only
+//! report new occurrences.
+//!
+//! Diagnostics are identified by the lint, the file, the path of the enclosing item and the
+//! whitespace-normalized source snippet. Line numbers aren't used so that edits elsewhere in a file
+//! don't cause recorded diagnostics to be reported again.
+//!
+//! Filtering happens in `rustc_errors::TRACK_DIAGNOSTIC` so suppressed diagnostics are not counted
+//! towards the number of warnings or errors. Lints emitted before HIR is available are held back
+//! until the enclosing items can be determined.
+
+use clippy_utils::diagnostics::{emitting_lint, with_emitting_lint};
+use rustc_errors::{DiagInner, ErrorGuaranteed, TRACK_DIAGNOSTIC};
+use rustc_lint_defs::Lint;
+use rustc_middle::ty::print::with_no_trimmed_paths;
+use rustc_middle::ty::{TyCtxt, tls};
+use rustc_session::Session;
+use rustc_span::def_id::CRATE_DEF_ID;
+use rustc_span::source_map::SourceMap;
+use rustc_span::{BytePos, Span};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read as _, Seek as _, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+pub type TrackDiagnostic =
+    fn(DiagInner, &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>) -> Option<ErrorGuaranteed>;
+
+pub enum Mode {
+    /// Suppress the diagnostics recorded in the baseline file.
+    Check(PathBuf),
+    /// Record the emitted diagnostics in the baseline file.
+    Write(PathBuf),
+}
+
+impl Mode {
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Check(path) | Self::Write(path) => path,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+struct Fingerprint {
+    /// The lint name e.g. `clippy::bytes_nth`
+    lint: String,
+    file: String,
+    /// The path of the innermost item containing the diagnostic, or an empty string for the crate
+    /// root.
+    item: String,
+    /// The source of the diagnostic's primary span with all whitespace replaced by a single space.
+    snippet: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    #[serde(flatten)]
+    fingerprint: Fingerprint,
+    count: usize,
+}
+
+struct ItemSpan {
+    lo: BytePos,
+    hi: BytePos,
+    path: String,
+}
+
+struct State {
+    mode: Mode,
+    /// The number of occurrences of each fingerprint. When checking this is the number left to
+    /// suppress, when writing it's the number emitted so far.
+    counts: BTreeMap<Fingerprint, usize>,
+    /// The spans of all items in the crate. `None` until HIR is available.
+    items: Option<Vec<ItemSpan>>,
+    /// Lint diagnostics emitted before HIR was available.
+    pending: Vec<(&'static Lint, DiagInner)>,
+}
+
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+static PREV_TRACK_DIAGNOSTIC: OnceLock<TrackDiagnostic> = OnceLock::new();
+
+/// Starts filtering or recording lint diagnostics. This must be called after the compiler's own
+/// `TRACK_DIAGNOSTIC` callback has been installed.
+pub fn install(sess: &Session, mode: Mode) {
+    let counts = match &mode {
+        Mode::Check(path) => match read_entries(path) {
+            Ok(entries) => entries.into_iter().map(|e| (e.fingerprint, e.count)).collect(),
+            Err(e) => sess
+                .dcx()
+                .fatal(format!("failed to read baseline file `{}`: {e}", path.display())),
+        },
+        Mode::Write(_) => BTreeMap::new(),
+    };
+    *STATE.lock().unwrap() = Some(State {
+        mode,
+        counts,
+        items: None,
+        pending: Vec::new(),
+    });
+    let prev = TRACK_DIAGNOSTIC.swap(&(track_diagnostic as TrackDiagnostic));
+    let _ = PREV_TRACK_DIAGNOSTIC.set(*prev);
+}
+
+/// Collects the spans of all items in the crate and emits any diagnostics which were held back.
+pub fn after_expansion(tcx: TyCtxt<'_>) {
+    // The lock isn't held while collecting the items since a diagnostic would take it again.
+    if STATE.lock().unwrap().is_none() {
+        return;
+    }
+    let items = tcx
+        .hir_crate_items(())
+        .owners()
+        // The crate root's span can be the same as its only item.
+        .filter(|owner| owner.def_id != CRATE_DEF_ID)
+        .filter_map(|owner| {
+            let span = tcx.hir_span_with_body(owner.into());
+            (!span.from_expansion()).then(|| ItemSpan {
+                lo: span.lo(),
+                hi: span.hi(),
+                path: with_no_trimmed_paths!(tcx.def_path_str(owner.to_def_id())),
+            })
+        })
+        .collect();
+
+    let pending = {
+        let mut state = STATE.lock().unwrap();
+        let Some(state) = state.as_mut() else {
+            return;
+        };
+        state.items = Some(items);
+        std::mem::take(&mut state.pending)
+    };
+    for (lint, diag) in pending {
+        with_emitting_lint(lint, || tcx.dcx().emit_diagnostic(diag));
+    }
+}
+
+/// Adds the recorded diagnostics to the baseline file when writing a baseline. Multiple crates can
+/// share the same file, so the file is locked while being updated.
+pub fn finish(sess: &Session) {
+    let Some(State {
+        mode: Mode::Write(path),
+        counts,
+        ..
+    }) = STATE.lock().unwrap().take()
+    else {
+        return;
+    };
+    if let Err(e) = write_entries(&path, counts) {
+        sess.dcx()
+            .err(format!("failed to write baseline file `{}`: {e}", path.display()));
+    }
+}
+
+fn track_diagnostic(
+    diag: DiagInner,
+    f: &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>,
+) -> Option<ErrorGuaranteed> {
+    let prev = PREV_TRACK_DIAGNOSTIC.get().copied().unwrap_or(|diag, f| f(diag));
+    let Some(lint) = clippy_lint(&diag) else {
+        return prev(diag, f);
+    };
+
+    let suppress = {
+        let mut state = STATE.lock().unwrap();
+        let Some(state) = state.as_mut() else {
+            return prev(diag, f);
+        };
+        let fingerprint = match &state.items {
+            Some(items) => tls::with_opt(|tcx| tcx.map(|tcx| fingerprint(tcx.sess.source_map(), items, lint, &diag))),
+            None => None,
+        };
+        let Some(fingerprint) = fingerprint else {
+            state.pending.push((lint, diag));
+            return None;
+        };
+        match state.mode {
+            Mode::Check(_) => state.counts.get_mut(&fingerprint).is_some_and(|count| {
+                let suppress = *count > 0;
+                *count = count.saturating_sub(1);
+                suppress
+            }),
+            Mode::Write(_) => {
+                *state.counts.entry(fingerprint).or_default() += 1;
+                false
+            },
+        }
+    };
+
+    if suppress {
+        // Still let the compiler track the diagnostic so it's replayed by incremental compilation.
+        prev(diag, &mut |_| None)
+    } else {
+        prev(diag, f)
+    }
+}
+
+/// Gets the Clippy lint which emitted the diagnostic.
+pub fn clippy_lint(diag: &DiagInner) -> Option<&'static Lint> {
+    diag.is_lint.as_ref().and(emitting_lint())
+}
+
+fn fingerprint(source_map: &SourceMap, items: &[ItemSpan], lint: &Lint, diag: &DiagInner) -> Fingerprint {
+    let lint = lint.name_lower();
+    let Some(span) = diag.span.primary_span().map(Span::source_callsite) else {
+        return Fingerprint {
+            lint,
+            file: String::new(),
+            item: String::new(),
+            snippet: String::new(),
+        };
+    };
+    let item = items
+        .iter()
+        .filter(|item| item.lo <= span.lo() && span.hi() <= item.hi)
+        .min_by_key(|item| item.hi - item.lo)
+        .map_or_else(String::new, |item| item.path.clone());
+    let snippet = source_map
+        .span_to_snippet(span)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Fingerprint {
+        lint,
+        file: source_map
+            .span_to_filename(span)
+            .prefer_local_unconditionally()
+            .to_string()
+            .replace('\\', "/"),
+        item,
+        snippet,
+    }
+}
+
+fn read_entries(path: &Path) -> io::Result<Vec<Entry>> {
+    let contents = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// Merges the counts into the file. When a fingerprint is already present the larger count is kept
+/// since the same source is often compiled more than once, e.g. both as a library and as tests.
+fn write_entries(path: &Path, counts: BTreeMap<Fingerprint, usize>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    file.lock()?;
+
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)?;
+    let mut merged: BTreeMap<Fingerprint, usize> = if contents.trim().is_empty() {
+        BTreeMap::new()
+    } else {
+        serde_json::from_str::<Vec<Entry>>(&contents)?
+            .into_iter()
+            .map(|e| (e.fingerprint, e.count))
+            .collect()
+    };
+    for (fingerprint, count) in counts {
+        let entry = merged.entry(fingerprint).or_default();
+        *entry = (*entry).max(count);
+    }
+    let entries: Vec<_> = merged
+        .into_iter()
+        .map(|(fingerprint, count)| Entry { fingerprint, count })
+        .collect();
+
+    file.set_len(0)?;
+    file.rewind()?;
+    serde_json::to_writer_pretty(&mut file, &entries)?;
+    file.write_all(b"\n")
+}
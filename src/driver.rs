@@ -7,25 +7,37 @@
 
 // FIXME: switch to something more ergonomic here, once available.
 // (Currently there is no way to opt into sysroot crates without `extern crate`.)
+extern crate rustc_ast;
 extern crate rustc_driver;
+extern crate rustc_errors;
 extern crate rustc_interface;
+extern crate rustc_lint_defs;
+extern crate rustc_middle;
 extern crate rustc_session;
 extern crate rustc_span;
 
+mod baseline;
+mod sarif;
+mod verify_suggestions;
+
 // Override the C allocator in the same way that the `rustc` binary would do.
 rustc_driver::override_c_allocator_in_binary!();
 
+use clippy_config::ConfFormat;
 use clippy_utils::sym;
 use declare_clippy_lint::LintListBuilder;
+use rustc_driver::Compilation;
 use rustc_interface::interface;
-use rustc_session::config::ErrorOutputType;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::{ErrorOutputType, Input};
 use rustc_session::{EarlyDiagCtxt, Session};
+use rustc_span::FileName;
 use rustc_span::symbol::Symbol;
 
 use std::env;
 use std::fs::read_to_string;
 use std::io::Write as _;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 /// If a command-line option matches `find_arg`, then apply the predicate `pred` on its value. If
@@ -133,6 +145,43 @@ impl rustc_driver::Callbacks for RustcCallbacks {
 
 struct ClippyCallbacks {
     clippy_args_var: Option<String>,
+    /// The directory to write a SARIF log to.
+    sarif_output: Option<PathBuf>,
+    baseline: Option<baseline::Mode>,
+    /// The directory to write the time spent in each lint pass to.
+    pass_timings: Option<PathBuf>,
+    /// Whether to check that machine-applicable suggestions compile, and the compiler arguments to
+    /// do so with.
+    verify_suggestions: Option<Vec<String>>,
+}
+
+impl ClippyCallbacks {
+    fn new(clippy_args_var: Option<String>) -> Self {
+        Self {
+            clippy_args_var,
+            sarif_output: env::var_os("CLIPPY_SARIF_OUTPUT").map(PathBuf::from),
+            baseline: None,
+            pass_timings: env::var_os("CLIPPY_PASS_TIMINGS").map(PathBuf::from),
+            verify_suggestions: env::var_os("CLIPPY_VERIFY_SUGGESTIONS").map(|_| Vec::new()),
+        }
+    }
+
+    /// Handles the arguments in `CLIPPY_ARGS` which are used by the driver rather than passed on
+    /// to rustc. Returns whether the argument was handled.
+    fn parse_arg(&mut self, arg: &str) -> bool {
+        if let Some(dir) = arg.strip_prefix("--sarif-output=") {
+            self.sarif_output = Some(dir.into());
+        } else if let Some(path) = arg.strip_prefix("--baseline=") {
+            self.baseline = Some(baseline::Mode::Check(path.into()));
+        } else if let Some(path) = arg.strip_prefix("--write-baseline=") {
+            self.baseline = Some(baseline::Mode::Write(path.into()));
+        } else if arg == "--verify-suggestions" {
+            self.verify_suggestions = Some(Vec::new());
+        } else {
+            return false;
+        }
+        true
+    }
 }
 
 impl rustc_driver::Callbacks for ClippyCallbacks {
@@ -140,17 +189,46 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
     fn config(&mut self, config: &mut interface::Config) {
         let previous = config.register_lints.take();
         let clippy_args_var = self.clippy_args_var.take();
+        let baseline_path = self
+            .baseline
+            .as_ref()
+            .and_then(|mode| mode.path().to_str())
+            .map(String::from);
         config.track_state = Some(Box::new(move |sess| {
             track_clippy_args(sess, clippy_args_var.as_deref());
             track_files(sess);
+            // Rerun clippy when the baseline changes.
+            if let Some(path) = &baseline_path {
+                sess.file_depinfo.borrow_mut().insert(Symbol::intern(path));
+            }
 
-            // Trigger a rebuild if CLIPPY_CONF_DIR changes. The value must be a valid string so
-            // changes between dirs that are invalid UTF-8 will not trigger rebuilds
-            sess.env_depinfo.borrow_mut().insert((
+            // Trigger a rebuild if CLIPPY_CONF_DIR, CLIPPY_SARIF_OUTPUT, CLIPPY_PASS_TIMINGS or
+            // CLIPPY_VERIFY_SUGGESTIONS change. The value must be a valid string so changes between dirs that
+            // are invalid UTF-8 will not trigger rebuilds
+            for var in [
                 sym::CLIPPY_CONF_DIR,
-                env::var("CLIPPY_CONF_DIR").ok().map(|dir| Symbol::intern(&dir)),
-            ));
+                sym::CLIPPY_SARIF_OUTPUT,
+                sym::CLIPPY_PASS_TIMINGS,
+                sym::CLIPPY_VERIFY_SUGGESTIONS,
+            ] {
+                sess.env_depinfo
+                    .borrow_mut()
+                    .insert((var, env::var(var.as_str()).ok().map(|dir| Symbol::intern(&dir))));
+            }
         }));
+        if let Some(dir) = self.sarif_output.take() {
+            let crate_name = config
+                .opts
+                .crate_name
+                .clone()
+                .unwrap_or_else(|| config.input.filestem().to_owned());
+            let output = sarif::output_path(&dir, &crate_name, &config.opts.cg.extra_filename);
+            let error_format = config.opts.error_format;
+            config.psess_created = Some(Box::new(move |psess| {
+                let emitter = sarif::SarifEmitter::new(psess.clone_source_map(), error_format, output);
+                psess.dcx().set_emitter(Box::new(emitter));
+            }));
+        }
         config.register_lints = Some(Box::new(move |sess, lint_store| {
             // technically we're ~guaranteed that this is none but might as well call anything that
             // is there already. Certainly it can't hurt.
@@ -181,6 +259,98 @@ impl rustc_driver::Callbacks for ClippyCallbacks {
         // Disable flattening and inlining of format_args!(), so the HIR matches with the AST.
         config.opts.unstable_opts.flatten_format_args = false;
     }
+
+    fn after_crate_root_parsing(&mut self, compiler: &interface::Compiler, _: &mut rustc_ast::Crate) -> Compilation {
+        // Installed first so suggestions are also verified when writing a baseline.
+        if let Some(args) = self.verify_suggestions.take() {
+            verify_suggestions::install(args);
+        }
+        if let Some(mode) = self.baseline.take() {
+            baseline::install(&compiler.sess, mode);
+        }
+        if self.pass_timings.is_some() && !cfg!(feature = "pass-timings") {
+            compiler
+                .sess
+                .dcx()
+                .fatal("`CLIPPY_PASS_TIMINGS` requires Clippy to be built with the `pass-timings` feature");
+        }
+        Compilation::Continue
+    }
+
+    fn after_expansion(&mut self, _: &interface::Compiler, tcx: TyCtxt<'_>) -> Compilation {
+        baseline::after_expansion(tcx);
+        Compilation::Continue
+    }
+
+    fn after_analysis(&mut self, compiler: &interface::Compiler, tcx: TyCtxt<'_>) -> Compilation {
+        baseline::finish(&compiler.sess);
+        #[cfg(feature = "pass-timings")]
+        if let Some(dir) = &self.pass_timings {
+            write_pass_timings(tcx, dir);
+        }
+        #[cfg(not(feature = "pass-timings"))]
+        let _ = tcx;
+        Compilation::Continue
+    }
+}
+
+/// Writes the time spent in each lint pass to `<dir>/<crate>-<pid>.json`. The process ID keeps the
+/// files of different targets of the same crate apart.
+#[cfg(feature = "pass-timings")]
+fn write_pass_timings(tcx: TyCtxt<'_>, dir: &Path) {
+    let timings: Vec<_> = clippy_lints::pass_timings::take()
+        .into_iter()
+        .map(|timing| {
+            serde_json::json!({
+                "kind": timing.kind,
+                "pass": timing.pass,
+                "nanos": u64::try_from(timing.time.as_nanos()).unwrap_or(u64::MAX),
+            })
+        })
+        .collect();
+    let path = dir.join(format!(
+        "{}-{}.json",
+        tcx.crate_name(rustc_span::def_id::LOCAL_CRATE),
+        std::process::id()
+    ));
+    let write = || -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(&path, serde_json::to_string(&timings)?)
+    };
+    if let Err(e) = write() {
+        tcx.dcx()
+            .err(format!("failed to write pass timings to `{}`: {e}", path.display()));
+    }
+}
+
+/// Prints the resolved configuration rather than compiling a crate.
+struct PrintConfigCallbacks {
+    format: ConfFormat,
+    failed: bool,
+}
+
+impl rustc_driver::Callbacks for PrintConfigCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        // Loading the configuration needs a session, but there's no crate to compile.
+        config.input = Input::Str {
+            name: FileName::Custom("print-config".into()),
+            input: String::new(),
+        };
+        config.extra_symbols = sym::EXTRA_SYMBOLS.into();
+    }
+
+    fn after_crate_root_parsing(&mut self, compiler: &interface::Compiler, _: &mut rustc_ast::Crate) -> Compilation {
+        let conf = clippy_config::Conf::format_resolved(&compiler.sess, self.format);
+        if let Err(e) = writeln!(&mut anstream::stdout().lock(), "{conf}") {
+            compiler
+                .sess
+                .dcx()
+                .err(format!("failed to print the configuration: {e}"));
+        }
+        // Errors in the configuration files are reported while loading them.
+        self.failed = compiler.sess.dcx().has_errors().is_some();
+        Compilation::Stop
+    }
 }
 
 fn display_help() -> ExitCode {
@@ -191,6 +361,24 @@ fn display_help() -> ExitCode {
     }
 }
 
+fn print_config(early_dcx: &EarlyDiagCtxt, arg: &str, args: &[String]) -> ExitCode {
+    let format = match arg.split_once('=') {
+        None => ConfFormat::Toml,
+        Some((_, format)) => ConfFormat::parse(format).unwrap_or_else(|| {
+            early_dcx.early_fatal(format!(
+                "unknown format `{format}` for `--print-config`, expected `toml` or `json`"
+            ))
+        }),
+    };
+    let mut callbacks = PrintConfigCallbacks { format, failed: false };
+    rustc_driver::run_compiler(args, &mut callbacks);
+    if callbacks.failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
 const BUG_REPORT_URL: &str = "https://github.com/rust-lang/rust-clippy/issues/new?template=ice.yml";
 
 fn main() -> ExitCode {
@@ -256,6 +444,13 @@ fn main() -> ExitCode {
             };
         }
 
+        if let Some(arg) = orig_args.iter().find(|arg| is_arg(arg, "--print-config")) {
+            // The input file is replaced by `PrintConfigCallbacks` so it's never read.
+            let mut args = vec![orig_args[0].clone(), "print-config.rs".into()];
+            pass_sysroot_env_if_given(&mut args, sys_root_env);
+            return print_config(&early_dcx, arg, &args);
+        }
+
         // Setting RUSTC_WRAPPER causes Cargo to pass 'rustc' as the first argument.
         // We're invoking the compiler programmatically, so we ignore this/
         let wrapper_mode = orig_args.get(1).map(Path::new).and_then(Path::file_stem) == Some("rustc".as_ref());
@@ -274,6 +469,7 @@ fn main() -> ExitCode {
 
         let mut no_deps = false;
         let clippy_args_var = env::var("CLIPPY_ARGS").ok();
+        let mut callbacks = ClippyCallbacks::new(clippy_args_var.clone());
         let clippy_args = clippy_args_var
             .as_deref()
             .unwrap_or_default()
@@ -284,6 +480,7 @@ fn main() -> ExitCode {
                     no_deps = true;
                     None
                 },
+                _ if callbacks.parse_arg(s) => None,
                 _ => Some(s.to_string()),
             })
             .chain(vec!["--cfg".into(), "clippy".into()])
@@ -304,7 +501,10 @@ fn main() -> ExitCode {
         let clippy_enabled = !cap_lints_allow && relevant_package && !info_query;
         if clippy_enabled {
             args.extend(clippy_args);
-            rustc_driver::run_compiler(&args, &mut ClippyCallbacks { clippy_args_var });
+            if let Some(verify_args) = &mut callbacks.verify_suggestions {
+                verify_args.clone_from(&args);
+            }
+            rustc_driver::run_compiler(&args, &mut callbacks);
         } else {
             rustc_driver::run_compiler(&args, &mut RustcCallbacks { clippy_args_var });
         }
@@ -325,6 +525,11 @@ Run <cyan>clippy-driver</> with the same arguments you use for <cyan>rustc</>
     <cyan,bold>-h</>, <cyan,bold>--help</>               Print this message
     <cyan,bold>-V</>, <cyan,bold>--version</>            Print version info and exit
     <cyan,bold>--rustc</>                  Pass all arguments to <cyan>rustc</>
+    <cyan,bold>--print-config[=FORMAT]</>  Print the resolved configuration as <cyan>toml</> (default) or <cyan>json</>
+
+<green,bold>Environment variables:</>
+    <cyan,bold>CLIPPY_SARIF_OUTPUT</>      Also write the diagnostics as a SARIF log to this directory
+    <cyan,bold>CLIPPY_VERIFY_SUGGESTIONS</> Check that each machine-applicable suggestion compiles before emitting it
 
 <green,bold>Allowing / Denying lints</>
 You can use tool lints to allow or deny lints from your code, e.g.:
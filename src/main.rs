@@ -7,6 +7,7 @@
 extern crate rustc_driver;
 
 use std::env;
+use std::fmt::Write as _;
 use std::io::Write as _;
 use std::path::PathBuf;
 use std::process::{self, Command, exit};
@@ -47,11 +48,65 @@ pub fn main() {
         return;
     }
 
+    if env::args().any(|a| a == "--list-lints") {
+        process::exit(list_lints(env::args()));
+    }
+
+    if let Some(arg) = env::args().find(|a| a == "--print-config" || a.starts_with("--print-config=")) {
+        process::exit(print_config(&arg));
+    }
+
     if let Err(code) = process(env::args().skip(2)) {
         process::exit(code);
     }
 }
 
+/// Prints the metadata of all lints as either plain text or json depending on the `--format` flag.
+fn list_lints(mut args: impl Iterator<Item = String>) -> i32 {
+    let mut format = None;
+    while let Some(arg) = args.next() {
+        if arg == "--format" {
+            format = args.next();
+        } else if let Some(value) = arg.strip_prefix("--format=") {
+            format = Some(value.into());
+        }
+    }
+
+    let lints = clippy_lints::lint_metadata::lint_metadata();
+    let output = match format.as_deref() {
+        None | Some("text") => lints.iter().fold(String::new(), |mut output, lint| {
+            writeln!(output, "{:<50} {:<12} {}", lint.name, lint.group, lint.level).unwrap();
+            output
+        }),
+        Some("json") => serde_json::to_string_pretty(&lints).unwrap() + "\n",
+        Some(format) => {
+            eprintln!("error: unknown format `{format}` for `--list-lints`, expected `text` or `json`");
+            return rustc_driver::EXIT_FAILURE;
+        },
+    };
+    if write!(&mut anstream::stdout().lock(), "{output}").is_err() {
+        return rustc_driver::EXIT_FAILURE;
+    }
+    rustc_driver::EXIT_SUCCESS
+}
+
+/// Prints the resolved configuration. This is loaded by `clippy-driver` starting from the current
+/// directory.
+fn print_config(arg: &str) -> i32 {
+    let exit_status = Command::new(ClippyCmd::path())
+        .arg(arg)
+        .status()
+        .expect("could not run clippy-driver");
+    exit_status.code().unwrap_or(-1)
+}
+
+/// Creates an argument with a path passed to `clippy-driver`. The driver runs in the workspace
+/// root, so the path is made absolute first.
+fn path_arg(flag: &str, path: &str) -> String {
+    let path = env::current_dir().map_or_else(|_| PathBuf::from(path), |cwd| cwd.join(path));
+    format!("{flag}={}", path.display())
+}
+
 struct ClippyCmd {
     cargo_subcommand: &'static str,
     args: Vec<String>,
@@ -67,17 +122,29 @@ impl ClippyCmd {
         let mut args = vec![];
         let mut clippy_args: Vec<String> = vec![];
 
-        for arg in old_args.by_ref() {
+        while let Some(arg) = old_args.next() {
             match arg.as_str() {
                 "--fix" => {
                     cargo_subcommand = "fix";
                     continue;
                 },
-                "--no-deps" => {
-                    clippy_args.push("--no-deps".into());
+                "--no-deps" | "--verify-suggestions" => {
+                    clippy_args.push(arg);
+                    continue;
+                },
+                "--sarif-output" | "--baseline" | "--write-baseline" => {
+                    if let Some(path) = old_args.next() {
+                        clippy_args.push(path_arg(&arg, &path));
+                    }
                     continue;
                 },
                 "--" => break,
+                _ if let Some((flag @ ("--sarif-output" | "--baseline" | "--write-baseline"), path)) =
+                    arg.split_once('=') =>
+                {
+                    clippy_args.push(path_arg(flag, path));
+                    continue;
+                },
                 _ => {},
             }
 
@@ -96,6 +163,13 @@ impl ClippyCmd {
         }
     }
 
+    /// The baseline file the diagnostics are recorded in, if any.
+    fn write_baseline(&self) -> Option<&str> {
+        self.clippy_args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--write-baseline="))
+    }
+
     fn path() -> PathBuf {
         let mut path = env::current_exe()
             .expect("current executable path invalid")
@@ -130,6 +204,11 @@ where
 {
     let cmd = ClippyCmd::new(old_args);
 
+    if let Some(path) = cmd.write_baseline() {
+        // Each crate adds its diagnostics to the file, so start from an empty baseline.
+        let _ = std::fs::remove_file(path);
+    }
+
     let mut cmd = cmd.into_std_cmd();
 
     let exit_status = cmd
@@ -159,6 +238,12 @@ pub fn help_message() -> &'static str {
     <cyan,bold>-h</>, <cyan,bold>--help</>               Print this message
     <cyan,bold>-V</>, <cyan,bold>--version</>            Print version info and exit
     <cyan,bold>--explain [LINT]</>         Print the documentation for a given lint
+    <cyan,bold>--list-lints</>             Print all lints. Use <cyan>--format=json</> for machine-readable output
+    <cyan,bold>--print-config[=FORMAT]</>  Print the resolved configuration as <cyan>toml</> (default) or <cyan>json</>
+    <cyan,bold>--sarif-output</> <cyan><<DIR>></>    Also write the diagnostics of each crate as a SARIF log to <cyan>DIR</>
+    <cyan,bold>--write-baseline</> <cyan><<FILE>></> Record the current diagnostics in a baseline file
+    <cyan,bold>--baseline</> <cyan><<FILE>></>       Only report diagnostics not recorded in the baseline file
+    <cyan,bold>--verify-suggestions</>     Check that each machine-applicable suggestion compiles before emitting it
 
 See all options with <cyan,bold>cargo check --help</>.
 
@@ -210,6 +295,25 @@ mod tests {
         assert_eq!(cmd.clippy_args.iter().filter(|arg| *arg == "--no-deps").count(), 1);
     }
 
+    #[test]
+    fn verify_suggestions() {
+        let args = "cargo clippy --fix --verify-suggestions"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.clippy_args.iter().any(|arg| arg == "--verify-suggestions"));
+        assert!(!cmd.args.iter().any(|arg| arg == "--verify-suggestions"));
+    }
+
+    #[test]
+    fn write_baseline() {
+        let args = "cargo clippy --write-baseline clippy-baseline.json"
+            .split_whitespace()
+            .map(ToString::to_string);
+        let cmd = ClippyCmd::new(args);
+        assert!(cmd.write_baseline().unwrap().ends_with("clippy-baseline.json"));
+    }
+
     #[test]
     fn check() {
         let args = "cargo clippy".split_whitespace().map(ToString::to_string);
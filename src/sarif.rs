@@ -0,0 +1,389 @@
+//! Records diagnostics as a [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log for code scanning tools.
+//!
+//! Diagnostics are first rendered by rustc's JSON emitter, which is also used to keep printing them
+//! in the format requested on the command line.
+
+use clippy_lints::declared_lints::LINTS;
+use rustc_errors::DiagInner;
+use rustc_errors::emitter::{Emitter, TimingEvent};
+use rustc_errors::json::JsonEmitter;
+use rustc_errors::timings::TimingRecord;
+use rustc_session::config::ErrorOutputType;
+use rustc_span::source_map::SourceMap;
+use serde_json::{Value, json};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::{env, fs};
+
+/// An emitter which prints diagnostics like the default emitter and writes them to a SARIF file
+/// once compilation has finished.
+pub struct SarifEmitter {
+    json: JsonEmitter,
+    diagnostics: Arc<Mutex<Vec<Value>>>,
+    output: PathBuf,
+}
+
+impl SarifEmitter {
+    pub fn new(source_map: Arc<SourceMap>, error_format: ErrorOutputType, output: PathBuf) -> Self {
+        let diagnostics = Arc::new(Mutex::new(Vec::new()));
+        let (pretty, rendered, color_config, forward_json) = match error_format {
+            ErrorOutputType::HumanReadable { kind, color_config } => (false, kind, color_config, false),
+            ErrorOutputType::Json {
+                pretty,
+                json_rendered,
+                color_config,
+            } => (pretty, json_rendered, color_config, true),
+        };
+        let writer = DiagnosticWriter {
+            pending: Vec::new(),
+            forward_json,
+            diagnostics: diagnostics.clone(),
+        };
+        Self {
+            json: JsonEmitter::new(Box::new(writer), Some(source_map), pretty, rendered, color_config),
+            diagnostics,
+            output,
+        }
+    }
+}
+
+impl Emitter for SarifEmitter {
+    fn emit_diagnostic(&mut self, diag: DiagInner) {
+        self.json.emit_diagnostic(diag);
+    }
+
+    fn emit_artifact_notification(&mut self, path: &Path, artifact_type: &str) {
+        self.json.emit_artifact_notification(path, artifact_type);
+    }
+
+    fn emit_timing_section(&mut self, record: TimingRecord, event: TimingEvent) {
+        self.json.emit_timing_section(record, event);
+    }
+
+    fn emit_future_breakage_report(&mut self, diags: Vec<DiagInner>) {
+        self.json.emit_future_breakage_report(diags);
+    }
+
+    fn emit_unused_externs(&mut self, lint_level: rustc_lint_defs::Level, unused_externs: &[&str]) {
+        self.json.emit_unused_externs(lint_level, unused_externs);
+    }
+
+    fn should_show_explain(&self) -> bool {
+        self.json.should_show_explain()
+    }
+
+    fn source_map(&self) -> Option<&SourceMap> {
+        self.json.source_map()
+    }
+}
+
+impl Drop for SarifEmitter {
+    fn drop(&mut self) {
+        let diagnostics = self.diagnostics.lock().unwrap();
+        let log = sarif_log(&diagnostics);
+        let result = self
+            .output
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::write(&self.output, serde_json::to_string_pretty(&log).unwrap()));
+        if let Err(e) = result {
+            eprintln!("error: failed to write `{}`: {e}", self.output.display());
+        }
+    }
+}
+
+/// Receives the output of the JSON emitter. Diagnostics are recorded for the SARIF log and are
+/// printed to stderr either as JSON or as rendered by rustc.
+struct DiagnosticWriter {
+    /// Output which hasn't yet formed a complete JSON value.
+    pending: Vec<u8>,
+    /// Whether the JSON output should be printed as is rather than only printing the rendered
+    /// diagnostics.
+    forward_json: bool,
+    diagnostics: Arc<Mutex<Vec<Value>>>,
+}
+
+impl Write for DiagnosticWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.forward_json {
+            io::stderr().write_all(buf)?;
+        }
+        self.pending.extend_from_slice(buf);
+
+        let mut values = serde_json::Deserializer::from_slice(&self.pending).into_iter::<Value>();
+        let mut consumed = 0;
+        while let Some(Ok(value)) = values.next() {
+            consumed = values.byte_offset();
+            if value["$message_type"] == "diagnostic" {
+                if !self.forward_json
+                    && let Some(rendered) = value["rendered"].as_str()
+                {
+                    io::stderr().write_all(rendered.as_bytes())?;
+                }
+                self.diagnostics.lock().unwrap().push(value);
+            }
+        }
+        self.pending.drain(..consumed);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        io::stderr().flush()
+    }
+}
+
+/// Gets the path of the SARIF file for the crate being compiled. Cargo passes a unique suffix in
+/// `-C extra-filename` for each target so they won't overwrite each other.
+pub fn output_path(dir: &Path, crate_name: &str, extra_filename: &str) -> PathBuf {
+    dir.join(format!("{crate_name}{extra_filename}.sarif"))
+}
+
+fn sarif_log(diagnostics: &[Value]) -> Value {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut rule_names: Vec<&str> = Vec::new();
+    let mut results = Vec::new();
+    for diag in diagnostics {
+        let code = diag["code"]["code"].as_str();
+        let spans = diag["spans"].as_array().map_or(&[][..], Vec::as_slice);
+        // Skip summaries such as "aborting due to previous error".
+        if code.is_none() && spans.is_empty() {
+            continue;
+        }
+
+        let mut result = json!({
+            "level": match diag["level"].as_str() {
+                Some(level) if level.starts_with("error") => "error",
+                Some("warning") => "warning",
+                _ => "note",
+            },
+            "message": { "text": message_text(diag) },
+            "locations": spans
+                .iter()
+                .filter(|span| span["is_primary"] == true)
+                .map(|span| json!({ "physicalLocation": physical_location(span) }))
+                .collect::<Vec<_>>(),
+            "relatedLocations": spans
+                .iter()
+                .filter(|span| span["is_primary"] != true)
+                .map(|span| json!({
+                    "physicalLocation": physical_location(span),
+                    "message": { "text": span["label"].as_str().unwrap_or_default() },
+                }))
+                .collect::<Vec<_>>(),
+            "fixes": diag["children"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(fix)
+                .collect::<Vec<_>>(),
+        });
+        if let Some(code) = code {
+            result["ruleId"] = code.into();
+            if let Some(name) = code.strip_prefix("clippy::") {
+                let index = rule_names.iter().position(|&x| x == name).or_else(|| {
+                    let rule = rule(name)?;
+                    rule_names.push(name);
+                    rules.push(rule);
+                    Some(rules.len() - 1)
+                });
+                if let Some(index) = index {
+                    result["ruleIndex"] = index.into();
+                }
+            }
+        }
+        results.push(result);
+    }
+
+    let version = rustc_tools_util::get_version_info!();
+    let root = env::current_dir().map_or_else(|_| String::new(), |dir| file_uri(&dir) + "/");
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "clippy",
+                    "informationUri": "https://github.com/rust-lang/rust-clippy",
+                    "version": format!("{}.{}.{}", version.major, version.minor, version.patch),
+                    "rules": rules,
+                },
+            },
+            "originalUriBaseIds": { "%SRCROOT%": { "uri": root } },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
+
+/// Creates the rule describing a Clippy lint.
+fn rule(name: &str) -> Option<Value> {
+    let info = LINTS.iter().find(|info| info.name_lower() == name)?;
+    // Use the "What it does" section of the documentation as the full description.
+    let what_it_does = info
+        .explanation
+        .split("###")
+        .find_map(|section| section.trim().strip_prefix("What it does"))
+        .map_or_else(String::new, |section| {
+            section
+                .lines()
+                .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_owned()
+        });
+    Some(json!({
+        "id": format!("clippy::{name}"),
+        "name": name,
+        "shortDescription": { "text": info.lint.desc },
+        "fullDescription": { "text": what_it_does },
+        "helpUri": format!("https://rust-lang.github.io/rust-clippy/{}/index.html#{name}", docs_channel()),
+        "defaultConfiguration": {
+            "level": match info.lint.default_level.as_str() {
+                "deny" | "forbid" => "error",
+                "warn" => "warning",
+                _ => "none",
+            },
+        },
+        "properties": { "tags": [info.category.name()] },
+    }))
+}
+
+/// Gets the version of the documentation matching this build. This matches the links added to
+/// each lint's diagnostic.
+fn docs_channel() -> &'static str {
+    match option_env!("CFG_RELEASE_CHANNEL") {
+        Some("stable") => concat!("rust-1.", env!("CARGO_PKG_VERSION_PATCH"), ".0"),
+        Some("beta") => "beta",
+        _ => "main",
+    }
+}
+
+/// Gets the diagnostic's message followed by the messages of any child diagnostics. Suggestions
+/// which aren't turned into fixes are included in the text.
+fn message_text(diag: &Value) -> String {
+    let mut text = diag["message"].as_str().unwrap_or_default().to_owned();
+    for child in diag["children"].as_array().into_iter().flatten() {
+        if let (Some(level), Some(message)) = (child["level"].as_str(), child["message"].as_str()) {
+            text.extend(["\n", level, ": ", message]);
+            let spans = suggestion_spans(child);
+            if spans.first().is_some_and(|span| !is_fix(span)) {
+                let replacements: Vec<_> = spans
+                    .iter()
+                    .filter_map(|span| span["suggested_replacement"].as_str())
+                    .collect();
+                text.extend([": `", &replacements.join("`, `"), "`"]);
+            }
+        }
+    }
+    text
+}
+
+/// Gets the spans of a child diagnostic which are replaced by its suggestion.
+fn suggestion_spans(child: &Value) -> Vec<&Value> {
+    child["spans"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|span| span["suggested_replacement"].is_string())
+        .collect()
+}
+
+/// Checks whether the suggestion is applicable as is, i.e. it's `MachineApplicable` or
+/// `MaybeIncorrect`. Suggestions with placeholders or an unspecified applicability aren't fixes.
+fn is_fix(span: &Value) -> bool {
+    matches!(
+        span["suggestion_applicability"].as_str(),
+        Some("MachineApplicable" | "MaybeIncorrect")
+    )
+}
+
+/// Converts a child diagnostic containing a suggestion which can be applied as is to a fix. The
+/// suggestion's applicability is kept as a property since SARIF has no equivalent.
+fn fix(child: &Value) -> Option<Value> {
+    let spans = suggestion_spans(child);
+    let first = spans.first().filter(|span| is_fix(span))?;
+
+    let mut changes: Vec<(&str, Vec<Value>)> = Vec::new();
+    for span in &spans {
+        let file = span["file_name"].as_str().unwrap_or_default();
+        let replacement = json!({
+            "deletedRegion": region(span),
+            "insertedContent": { "text": span["suggested_replacement"] },
+        });
+        match changes.iter_mut().find(|(x, _)| *x == file) {
+            Some((_, replacements)) => replacements.push(replacement),
+            None => changes.push((file, vec![replacement])),
+        }
+    }
+
+    Some(json!({
+        "description": { "text": child["message"] },
+        "artifactChanges": changes
+            .into_iter()
+            .map(|(file, replacements)| json!({
+                "artifactLocation": artifact_location(file),
+                "replacements": replacements,
+            }))
+            .collect::<Vec<_>>(),
+        "properties": {
+            "applicability": first["suggestion_applicability"],
+        },
+    }))
+}
+
+fn physical_location(span: &Value) -> Value {
+    json!({
+        "artifactLocation": artifact_location(span["file_name"].as_str().unwrap_or_default()),
+        "region": region(span),
+    })
+}
+
+fn region(span: &Value) -> Value {
+    let start = span["byte_start"].as_u64().unwrap_or_default();
+    let end = span["byte_end"].as_u64().unwrap_or_default();
+    json!({
+        "startLine": span["line_start"],
+        "startColumn": span["column_start"],
+        "endLine": span["line_end"],
+        "endColumn": span["column_end"],
+        "byteOffset": start,
+        "byteLength": end.saturating_sub(start),
+    })
+}
+
+/// Relative paths are relative to the directory the compiler was run in.
+fn artifact_location(file: &str) -> Value {
+    let path = Path::new(file);
+    if path.is_absolute() {
+        json!({ "uri": file_uri(path) })
+    } else {
+        json!({ "uri": encode_uri_path(&file.replace('\\', "/")), "uriBaseId": "%SRCROOT%" })
+    }
+}
+
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let path = encode_uri_path(&path);
+    if path.starts_with('/') {
+        format!("file://{path}")
+    } else {
+        format!("file:///{path}")
+    }
+}
+
+fn encode_uri_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for c in path.chars() {
+        match c {
+            ' ' => encoded.push_str("%20"),
+            '#' => encoded.push_str("%23"),
+            '%' => encoded.push_str("%25"),
+            '?' => encoded.push_str("%3F"),
+            c => encoded.push(c),
+        }
+    }
+    encoded
+}
@@ -0,0 +1,294 @@
+//! Verifies that the machine-applicable suggestions of Clippy's lints compile.
+//!
+//! Each suggestion is applied to an in-memory copy of the source files and the crate is checked
+//! again with the same arguments, but without Clippy's lints. Suggestions which don't compile are
+//! downgraded to `MaybeIncorrect` so `cargo clippy --fix` skips them instead of rolling back the
+//! whole file, and a note asking to report the bug is added to the diagnostic.
+
+use crate::baseline::{TrackDiagnostic, clippy_lint};
+use rustc_driver::Compilation;
+use rustc_errors::emitter::Emitter;
+use rustc_errors::{
+    Applicability, DiagInner, ErrorGuaranteed, Level, MultiSpan, Style, Subdiag, SubstitutionPart, Suggestions,
+    TRACK_DIAGNOSTIC, format_diag_message,
+};
+use rustc_interface::interface;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::config::OutputTypes;
+use rustc_session::lint;
+use rustc_span::source_map::{FileLoader, RealFileLoader, SourceMap, get_source_map};
+use rustc_span::{FileName, Pos as _, SourceFile};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// The compiler arguments used to check the crate again
+static ARGS: OnceLock<Vec<String>> = OnceLock::new();
+static PREV_TRACK_DIAGNOSTIC: OnceLock<TrackDiagnostic> = OnceLock::new();
+
+/// Starts verifying the suggestions of Clippy's lints, checking the crate with `args` for each of
+/// them. This must be called after the compiler's own `TRACK_DIAGNOSTIC` callback has been
+/// installed.
+pub fn install(args: Vec<String>) {
+    let _ = ARGS.set(args);
+    let prev = TRACK_DIAGNOSTIC.swap(&(track_diagnostic as TrackDiagnostic));
+    let _ = PREV_TRACK_DIAGNOSTIC.set(*prev);
+}
+
+fn track_diagnostic(
+    mut diag: DiagInner,
+    f: &mut dyn FnMut(DiagInner) -> Option<ErrorGuaranteed>,
+) -> Option<ErrorGuaranteed> {
+    let prev = PREV_TRACK_DIAGNOSTIC.get().copied().unwrap_or(|diag, f| f(diag));
+    if clippy_lint(&diag).is_some()
+        && let Some(source_map) = get_source_map()
+    {
+        verify(&source_map, &mut diag);
+    }
+    prev(diag, f)
+}
+
+/// Checks each machine-applicable suggestion of the diagnostic, downgrading those which don't
+/// compile.
+fn verify(source_map: &SourceMap, diag: &mut DiagInner) {
+    let suggestions = match &mut diag.suggestions {
+        Suggestions::Enabled(suggestions) => &mut suggestions[..],
+        Suggestions::Sealed(suggestions) => &mut suggestions[..],
+        Suggestions::Disabled => return,
+    };
+    let mut notes = Vec::new();
+    for suggestion in suggestions {
+        if suggestion.applicability != Applicability::MachineApplicable {
+            continue;
+        }
+        // `rustfix` only applies the first substitution.
+        let Some(files) = suggestion
+            .substitutions
+            .first()
+            .and_then(|substitution| apply(source_map, &substitution.parts))
+        else {
+            continue;
+        };
+        if let Err(error) = check(&files) {
+            suggestion.applicability = Applicability::MaybeIncorrect;
+            notes.push(format!(
+                "the suggestion doesn't compile, which is a bug in Clippy\n\
+                please report it at https://github.com/rust-lang/rust-clippy/issues/new with the \
+                following diff and error:\n{}{error}",
+                diff(&files),
+            ));
+        }
+    }
+    diag.children.extend(notes.into_iter().map(|note| Subdiag {
+        level: Level::Note,
+        messages: vec![(note.into(), Style::NoStyle)],
+        span: MultiSpan::new(),
+    }));
+}
+
+struct EditedFile {
+    /// The path used to load the file
+    path: PathBuf,
+    before: Arc<String>,
+    after: String,
+}
+
+/// The byte ranges of a file to replace, and their replacements
+type Replacements<'a> = Vec<(Range<usize>, &'a str)>;
+
+/// Applies the parts of a suggestion to the source files. Returns `None` if a part isn't in a
+/// local source file or if parts overlap.
+fn apply(source_map: &SourceMap, parts: &[SubstitutionPart]) -> Option<Vec<EditedFile>> {
+    let mut files: BTreeMap<PathBuf, (Arc<SourceFile>, Replacements<'_>)> = BTreeMap::new();
+    for part in parts {
+        let lo = source_map.lookup_byte_offset(part.span.lo());
+        let hi = source_map.lookup_byte_offset(part.span.hi());
+        if !Arc::ptr_eq(&lo.sf, &hi.sf) {
+            return None;
+        }
+        let FileName::Real(name) = &lo.sf.name else {
+            return None;
+        };
+        let path = name.local_path()?.to_owned();
+        files
+            .entry(path)
+            .or_insert_with(|| (lo.sf.clone(), Vec::new()))
+            .1
+            .push((lo.pos.to_usize()..hi.pos.to_usize(), &part.snippet));
+    }
+
+    files
+        .into_iter()
+        .map(|(path, (file, mut edits))| {
+            let before = file.src.clone()?;
+            edits.sort_by_key(|(range, _)| range.start);
+            let mut after = String::with_capacity(before.len());
+            let mut pos = 0;
+            for (range, snippet) in edits {
+                if range.start < pos {
+                    return None;
+                }
+                after.push_str(before.get(pos..range.start)?);
+                after.push_str(snippet);
+                pos = range.end;
+            }
+            after.push_str(before.get(pos..)?);
+            Some(EditedFile { path, before, after })
+        })
+        .collect()
+}
+
+/// Renders the changed lines of the edited files as a unified diff.
+fn diff(files: &[EditedFile]) -> String {
+    let mut diff = String::new();
+    for file in files {
+        let before: Vec<_> = file.before.lines().collect();
+        let after: Vec<_> = file.after.lines().collect();
+        let prefix = before.iter().zip(&after).take_while(|(a, b)| a == b).count();
+        let suffix = before[prefix..]
+            .iter()
+            .rev()
+            .zip(after[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let removed = &before[prefix..before.len() - suffix];
+        let added = &after[prefix..after.len() - suffix];
+        let _ = writeln!(
+            diff,
+            "--- {path}\n+++ {path}\n@@ -{line},{} +{line},{} @@",
+            removed.len(),
+            added.len(),
+            path = file.path.display(),
+            line = prefix + 1,
+        );
+        for line in removed {
+            let _ = writeln!(diff, "-{line}");
+        }
+        for line in added {
+            let _ = writeln!(diff, "+{line}");
+        }
+    }
+    diff
+}
+
+/// Checks the crate with the edited files, returning the first error if it doesn't compile.
+fn check(files: &[EditedFile]) -> Result<(), String> {
+    let Some(args) = ARGS.get() else {
+        return Ok(());
+    };
+    let error = Arc::new(Mutex::new(None));
+    let mut callbacks = CheckCallbacks {
+        files: files
+            .iter()
+            .map(|file| (file.path.clone(), file.after.clone()))
+            .collect(),
+        error: error.clone(),
+    };
+    // The compiler installs its own `TRACK_DIAGNOSTIC` callback when it starts, so the current one
+    // has to be restored afterwards.
+    let current = TRACK_DIAGNOSTIC.swap(&(track_diagnostic as TrackDiagnostic));
+    let result = rustc_driver::catch_fatal_errors(|| rustc_driver::run_compiler(args, &mut callbacks));
+    TRACK_DIAGNOSTIC.swap(current);
+
+    match error.lock().unwrap().take() {
+        Some(error) => Err(error),
+        None if result.is_err() => Err("error: the compiler aborted".to_owned()),
+        None => Ok(()),
+    }
+}
+
+struct CheckCallbacks {
+    files: BTreeMap<PathBuf, String>,
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl rustc_driver::Callbacks for CheckCallbacks {
+    fn config(&mut self, config: &mut interface::Config) {
+        config.file_loader = Some(Box::new(OverlayFileLoader {
+            files: std::mem::take(&mut self.files),
+        }));
+        config.opts.incremental = None;
+        config.opts.lint_cap = Some(lint::Level::Allow);
+        config.opts.output_types = OutputTypes::new(&[]);
+        let error = self.error.clone();
+        config.psess_created = Some(Box::new(move |psess| {
+            psess.dcx().set_emitter(Box::new(ErrorCollector {
+                source_map: psess.clone_source_map(),
+                error,
+            }));
+        }));
+    }
+
+    fn after_analysis(&mut self, _: &interface::Compiler, _: TyCtxt<'_>) -> Compilation {
+        Compilation::Stop
+    }
+}
+
+/// Reads the edited files from memory and all others from the file system.
+struct OverlayFileLoader {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl FileLoader for OverlayFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.files.contains_key(path) || RealFileLoader.file_exists(path)
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        match self.files.get(path) {
+            Some(src) => Ok(src.clone()),
+            None => RealFileLoader.read_file(path),
+        }
+    }
+
+    fn read_binary_file(&self, path: &Path) -> io::Result<Arc<[u8]>> {
+        match self.files.get(path) {
+            Some(src) => Ok(src.as_bytes().into()),
+            None => RealFileLoader.read_binary_file(path),
+        }
+    }
+
+    fn current_directory(&self) -> io::Result<PathBuf> {
+        RealFileLoader.current_directory()
+    }
+}
+
+/// An emitter keeping the first error instead of printing the diagnostics.
+struct ErrorCollector {
+    source_map: Arc<SourceMap>,
+    error: Arc<Mutex<Option<String>>>,
+}
+
+impl Emitter for ErrorCollector {
+    fn emit_diagnostic(&mut self, diag: DiagInner) {
+        let mut error = self.error.lock().unwrap();
+        if error.is_some() || !diag.is_error() {
+            return;
+        }
+        let mut message = match diag.code {
+            Some(code) => format!("error[{code}]: "),
+            None => "error: ".to_owned(),
+        };
+        for (msg, _) in &diag.messages {
+            message.push_str(&format_diag_message(msg, &diag.args));
+        }
+        if let Some(span) = diag.span.primary_span() {
+            let loc = self.source_map.lookup_char_pos(span.lo());
+            let _ = write!(
+                message,
+                "\n --> {}:{}:{}",
+                loc.file.name.prefer_local_unconditionally(),
+                loc.line,
+                loc.col_display + 1,
+            );
+        }
+        *error = Some(message);
+    }
+
+    fn source_map(&self) -> Option<&SourceMap> {
+        Some(&self.source_map)
+    }
+}
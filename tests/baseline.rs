@@ -0,0 +1,79 @@
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use test_utils::IS_RUSTC_TEST_SUITE;
+
+mod test_utils;
+
+fn run_clippy(dir: &Path, arg: &str) -> Output {
+    let output = Command::new(env!("CARGO_BIN_EXE_clippy-driver"))
+        .current_dir(dir)
+        .env("CLIPPY_ARGS", arg)
+        .args(["--edition=2024", "--emit=metadata", "baseline_test.rs"])
+        .output()
+        .unwrap();
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+#[test]
+fn baseline() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("baseline");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let baseline = dir.join("clippy-baseline.json");
+    fs::write(
+        dir.join("baseline_test.rs"),
+        "#![deny(clippy::len_zero)]\n\
+        fn f(x: &[u8]) -> bool {\n    x.len() == 0\n}\n\
+        fn main() {\n    let _ = f(&[]);\n}\n",
+    )
+    .unwrap();
+
+    let output = run_clippy(&dir, &format!("--write-baseline={}", baseline.display()));
+    assert!(!output.status.success());
+    let entries: Value = serde_json::from_str(&fs::read_to_string(&baseline).unwrap()).unwrap();
+    assert_eq!(
+        entries,
+        serde_json::json!([{
+            "lint": "clippy::len_zero",
+            "file": "baseline_test.rs",
+            "item": "f",
+            "snippet": "x.len() == 0",
+            "count": 1,
+        }])
+    );
+
+    // Moving the existing diagnostic and reformatting it doesn't make it new.
+    fs::write(
+        dir.join("baseline_test.rs"),
+        "#![deny(clippy::len_zero)]\n\n\
+        fn f(x: &[u8]) -> bool {\n    x.len()\n        == 0\n}\n\
+        fn main() {\n    let _ = f(&[]);\n}\n",
+    )
+    .unwrap();
+    let output = run_clippy(&dir, &format!("--baseline={}", baseline.display()));
+    assert!(output.status.success());
+
+    // A new occurrence in another function is reported.
+    fs::write(
+        dir.join("baseline_test.rs"),
+        "#![deny(clippy::len_zero)]\n\
+        fn f(x: &[u8]) -> bool {\n    x.len() == 0\n}\n\
+        fn g(x: &[u8]) -> bool {\n    x.len() == 0\n}\n\
+        fn main() {\n    let _ = f(&[]) && g(&[]);\n}\n",
+    )
+    .unwrap();
+    let output = run_clippy(&dir, &format!("--baseline={}", baseline.display()));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("baseline_test.rs:6:5"));
+    assert!(!stderr.contains("baseline_test.rs:3:5"));
+}
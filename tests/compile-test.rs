@@ -132,10 +132,17 @@ impl TestContext {
     fn new() -> Self {
         let mut args = Args::test().unwrap();
         args.bless |= var_os("RUSTC_BLESS").is_some_and(|v| v != "0");
-        let (diagnostic_collector, collector_thread) = var_os("COLLECT_METADATA")
-            .is_some()
-            .then(DiagnosticCollector::spawn)
-            .unzip();
+        let collect_metadata = var_os("COLLECT_METADATA").is_some();
+        // The applicabilities are checked against `clippy_lints/lint_applicabilities.txt` when
+        // running all the tests.
+        let filtered = env::var("TESTNAME").is_ok_and(|filters| !filters.is_empty())
+            || !args.filters.is_empty()
+            || !args.skip.is_empty()
+            || args.list;
+        let (diagnostic_collector, collector_thread) =
+            (!IS_RUSTC_TEST_SUITE && !RUN_INTERNAL_TESTS && (collect_metadata || !filtered))
+                .then(|| DiagnosticCollector::spawn(collect_metadata))
+                .unzip();
         Self {
             args,
             diagnostic_collector,
@@ -498,7 +505,9 @@ enum DiagnosticOrMessage {
 }
 
 /// Collects applicabilities from the diagnostics produced for each UI test, producing the
-/// `util/gh-pages/index.html` file used by <https://rust-lang.github.io/rust-clippy/>
+/// `util/gh-pages/index.html` file used by <https://rust-lang.github.io/rust-clippy/> and the
+/// applicabilities embedded in `clippy_lints`. Unless collecting metadata, the embedded
+/// applicabilities are only checked to be up to date.
 #[derive(Debug, Clone)]
 struct DiagnosticCollector {
     sender: Sender<Vec<u8>>,
@@ -506,12 +515,12 @@ struct DiagnosticCollector {
 
 impl DiagnosticCollector {
     #[expect(clippy::assertions_on_constants)]
-    fn spawn() -> (Self, thread::JoinHandle<()>) {
+    fn spawn(collect_metadata: bool) -> (Self, thread::JoinHandle<()>) {
         assert!(!IS_RUSTC_TEST_SUITE && !RUN_INTERNAL_TESTS);
 
         let (sender, receiver) = channel::<Vec<u8>>();
 
-        let handle = thread::spawn(|| {
+        let handle = thread::spawn(move || {
             let mut applicabilities = HashMap::new();
 
             for stderr in receiver {
@@ -541,6 +550,25 @@ impl DiagnosticCollector {
                 }
             }
 
+            // Embedded in `clippy_lints` for `cargo clippy --list-lints`
+            let mut lint_applicabilities = String::new();
+            let mut names: Vec<_> = LINTS.iter().map(|lint| lint.name_lower()).collect();
+            names.sort_unstable();
+            for name in names {
+                if let Some(applicability) = applicabilities.get(&name) {
+                    writeln!(lint_applicabilities, "{name} {}", applicability_str(applicability)).unwrap();
+                }
+            }
+            let path = "clippy_lints/lint_applicabilities.txt";
+            if !collect_metadata {
+                assert!(
+                    fs::read_to_string(path).unwrap() == lint_applicabilities,
+                    "`{path}` is out of date, run `cargo collect-metadata` to update it"
+                );
+                return;
+            }
+            fs::write(path, lint_applicabilities).unwrap();
+
             let configs = clippy_config::Conf::get_metadata();
             let mut metadata: Vec<LintMetadata> = LINTS
                 .iter()
@@ -675,13 +703,17 @@ impl LintMetadata {
     }
 
     fn applicability_str(&self) -> &str {
-        match self.applicability {
-            Applicability::MachineApplicable => "MachineApplicable",
-            Applicability::HasPlaceholders => "HasPlaceholders",
-            Applicability::MaybeIncorrect => "MaybeIncorrect",
-            Applicability::Unspecified => "Unspecified",
-            _ => panic!("needs to update this code"),
-        }
+        applicability_str(&self.applicability)
+    }
+}
+
+fn applicability_str(applicability: &Applicability) -> &'static str {
+    match applicability {
+        Applicability::MachineApplicable => "MachineApplicable",
+        Applicability::HasPlaceholders => "HasPlaceholders",
+        Applicability::MaybeIncorrect => "MaybeIncorrect",
+        Applicability::Unspecified => "Unspecified",
+        _ => panic!("needs to update this code"),
     }
 }
 
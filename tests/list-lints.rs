@@ -0,0 +1,59 @@
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use serde_json::Value;
+use std::process::Command;
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+#[test]
+fn list_lints_json() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .args(["clippy", "--list-lints", "--format=json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let lints: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+    let lint = |name: &str| {
+        lints
+            .iter()
+            .find(|lint| lint["name"] == name)
+            .unwrap_or_else(|| panic!("missing lint `{name}`"))
+    };
+
+    let manual_strip = lint("manual_strip");
+    assert_eq!(manual_strip["group"], "complexity");
+    assert_eq!(manual_strip["level"], "warn");
+    assert_eq!(manual_strip["version"], "1.48.0");
+    assert_eq!(manual_strip["msrv"], true);
+    assert_eq!(manual_strip["configs"], serde_json::json!(["msrv"]));
+    assert_eq!(manual_strip["applicability"], "MachineApplicable");
+
+    let too_many_lines = lint("too_many_lines");
+    assert_eq!(too_many_lines["msrv"], false);
+    assert_eq!(too_many_lines["applicability"], "Unspecified");
+    assert_eq!(
+        too_many_lines["configs"],
+        serde_json::json!(["too-many-lines-threshold"])
+    );
+
+    assert_eq!(lint("assign_ops")["group"], "deprecated");
+}
+
+#[test]
+fn list_lints_unknown_format() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .args(["clippy", "--list-lints", "--format=yaml"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
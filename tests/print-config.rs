@@ -0,0 +1,106 @@
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use test_utils::{CARGO_CLIPPY_PATH, IS_RUSTC_TEST_SUITE};
+
+mod test_utils;
+
+fn run_print_config(dir: &str, format: &str) -> Output {
+    let output = Command::new(&*CARGO_CLIPPY_PATH)
+        .current_dir(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(dir))
+        .env_remove("CLIPPY_CONF_DIR")
+        .env_remove("CARGO_MANIFEST_DIR")
+        .env_remove("CARGO_PKG_RUST_VERSION")
+        .arg("clippy")
+        .arg(format!("--print-config={format}"))
+        .output()
+        .unwrap();
+
+    println!("status: {}", output.status);
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    output
+}
+
+fn print_config(dir: &str, format: &str) -> String {
+    let output = run_print_config(dir, format);
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn print_config_json() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let output: Value = serde_json::from_str(&print_config("tests/ui-toml/conf_extends", "json")).unwrap();
+    assert!(
+        output["config_file"]
+            .as_str()
+            .unwrap()
+            .ends_with("conf_extends/clippy.toml")
+    );
+    let field = |name: &str| {
+        output["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|field| field["name"] == name)
+            .unwrap_or_else(|| panic!("missing field `{name}`"))
+            .clone()
+    };
+
+    // Set in the config file with the inherited and default values merged in.
+    let names = field("disallowed-names");
+    assert_eq!(
+        names["value"],
+        serde_json::json!(["tata", "toto", "foo", "baz", "quux"])
+    );
+    assert!(
+        names["source"]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("conf_extends/clippy.toml")
+    );
+    assert_eq!(names["source"]["line"], 2);
+    assert_eq!(names["lints"], serde_json::json!(["disallowed_names"]));
+
+    // Inherited from the extended file.
+    let methods = field("disallowed-methods");
+    assert!(
+        methods["source"]["file"]
+            .as_str()
+            .unwrap()
+            .ends_with("conf_extends/clippy-base.toml")
+    );
+
+    // Not set anywhere.
+    let segments = field("absolute-paths-max-segments");
+    assert_eq!(segments["value"], 2);
+    assert_eq!(segments["source"], Value::Null);
+}
+
+#[test]
+fn print_config_toml() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let output = print_config("tests/ui-toml/conf_override", "toml");
+    assert!(output.contains("\ntoo-many-arguments-threshold = 2\n"));
+    assert!(output.contains("\n[[override]]\nmodules = [\"crate::ffi\"]\ntoo-many-arguments-threshold = 4\n"));
+    assert!(output.contains("\n# Source: default\nabsolute-paths-max-segments = 2\n"));
+}
+
+#[test]
+fn print_config_invalid() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let output = run_print_config("tests/ui-toml/bad_toml_type", "toml");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expected an array"));
+}
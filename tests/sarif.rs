@@ -0,0 +1,73 @@
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::IS_RUSTC_TEST_SUITE;
+
+mod test_utils;
+
+#[test]
+fn sarif_output() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("sarif");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("sarif_test.rs"),
+        "fn main() {\n    let x = [1u8];\n    let _ = x.len() == 0;\n    let _ = f(1.0);\n}\n\
+        #[warn(clippy::float_cmp)]\n\
+        fn f(x: f64) -> bool {\n    x == 0.2\n}\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_clippy-driver"))
+        .current_dir(&dir)
+        .env("CLIPPY_SARIF_OUTPUT", "out")
+        .args(["--edition=2024", "--emit=metadata", "sarif_test.rs"])
+        .output()
+        .unwrap();
+    println!("stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert!(output.status.success());
+    // The human readable output is still emitted.
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning: length comparison to zero"));
+
+    let log: Value = serde_json::from_str(&fs::read_to_string(dir.join("out/sarif_test.sarif")).unwrap()).unwrap();
+    let run = &log["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "clippy");
+    let rule = &run["tool"]["driver"]["rules"][0];
+    assert_eq!(rule["id"], "clippy::len_zero");
+    assert!(rule["helpUri"].as_str().unwrap().ends_with("index.html#len_zero"));
+
+    let result = &run["results"][0];
+    assert_eq!(result["ruleId"], "clippy::len_zero");
+    assert_eq!(result["ruleIndex"], 0);
+    assert_eq!(result["level"], "warning");
+    let region = &result["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 3);
+    assert_eq!(region["startColumn"], 13);
+
+    let fix = &result["fixes"][0];
+    assert_eq!(fix["properties"]["applicability"], "MachineApplicable");
+    let replacement = &fix["artifactChanges"][0]["replacements"][0];
+    assert_eq!(replacement["insertedContent"]["text"], "x.is_empty()");
+
+    // Suggestions with placeholders aren't fixes, but are kept in the message.
+    let result = run["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|result| result["ruleId"] == "clippy::float_cmp")
+        .unwrap();
+    assert_eq!(result["fixes"], serde_json::json!([]));
+    assert!(
+        result["message"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("`(x - 0.2).abs() < error_margin`")
+    );
+}
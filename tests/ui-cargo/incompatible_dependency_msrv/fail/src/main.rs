@@ -0,0 +1,3 @@
+#![warn(clippy::incompatible_dependency_msrv)]
+
+fn main() {}
@@ -0,0 +1,13 @@
+#![warn(clippy::unused_cargo_dependencies)]
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        fn os() -> &'static str { "unix" }
+    } else {
+        fn os() -> &'static str { "other" }
+    }
+}
+
+fn main() {
+    println!("{}", os());
+}
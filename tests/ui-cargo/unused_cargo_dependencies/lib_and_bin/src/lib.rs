@@ -0,0 +1,11 @@
+#![warn(clippy::unused_cargo_dependencies)]
+
+// `itoa` is only used by the binary, which is compiled separately
+
+cfg_if::cfg_if! {
+    if #[cfg(unix)] {
+        pub fn os() -> &'static str { "unix" }
+    } else {
+        pub fn os() -> &'static str { "other" }
+    }
+}
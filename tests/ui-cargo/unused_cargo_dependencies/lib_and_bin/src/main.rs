@@ -0,0 +1,6 @@
+#![warn(clippy::unused_cargo_dependencies)]
+
+fn main() {
+    let mut buffer = itoa::Buffer::new();
+    println!("{} {}", unused_cargo_dependencies::os(), buffer.format(1));
+}
@@ -0,0 +1,8 @@
+#![warn(clippy::unused_cargo_dependencies)]
+
+extern crate cfg_if;
+
+fn main() {
+    let mut buffer = itoa::Buffer::new();
+    println!("{}", buffer.format(1));
+}
@@ -0,0 +1,5 @@
+fn main() {
+    if std::env::var_os("CARGO_FEATURE_FROM_BUILD_SCRIPT").is_some() {
+        println!("cargo::rustc-cfg=from_build_script");
+    }
+}
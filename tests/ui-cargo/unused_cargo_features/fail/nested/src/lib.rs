@@ -0,0 +1,2 @@
+#[cfg(feature = "in-nested-package")]
+pub fn nested() {}
@@ -0,0 +1,13 @@
+#![warn(clippy::unused_cargo_features)]
+#![cfg_attr(feature = "in-cfg-attr", allow(dead_code))]
+
+fn main() {
+    #[cfg(feature = "std")]
+    println!("std");
+    let _ = not_cfgs();
+}
+
+// Not a `cfg`: feature = "in-comment"
+fn not_cfgs() -> &'static str {
+    "feature = \"in-string\""
+}
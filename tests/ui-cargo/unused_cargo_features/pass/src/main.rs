@@ -0,0 +1,7 @@
+#![warn(clippy::unused_cargo_features)]
+
+fn main() {
+    if cfg!(feature = "std") {
+        println!("std");
+    }
+}
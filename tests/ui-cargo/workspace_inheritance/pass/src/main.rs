@@ -0,0 +1,7 @@
+#![warn(
+    clippy::inconsistent_workspace_dependency_features,
+    clippy::missing_workspace_lints,
+    clippy::non_inherited_workspace_dependencies
+)]
+
+fn main() {}
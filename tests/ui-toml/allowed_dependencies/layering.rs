@@ -0,0 +1,78 @@
+//@no-rustfix
+#![warn(clippy::disallowed_layer_dependencies)]
+#![allow(dead_code)]
+
+mod domain {
+    pub mod model {
+        pub struct Order;
+    }
+
+    pub mod ports {
+        pub trait Repository {
+            fn save(&self, order: &super::model::Order);
+        }
+    }
+
+    pub fn helper() {}
+
+    // The domain may use itself and everything outside of the layers
+    use self::model::Order;
+    use crate::shared::Id;
+
+    fn uses_infra(_: crate::infra::Db) {}
+    //~^ disallowed_layer_dependencies
+
+    fn calls_infra() {
+        crate::infra::connect();
+        //~^ disallowed_layer_dependencies
+    }
+}
+
+mod infra {
+    use crate::domain::model::Order;
+    use crate::domain::ports::Repository;
+
+    pub struct Db;
+
+    pub fn connect() {}
+
+    pub trait Store {}
+
+    impl Repository for Db {
+        fn save(&self, _: &Order) {}
+    }
+}
+
+mod api {
+    use crate::domain::helper;
+    use crate::domain::ports::Repository;
+
+    use crate::domain::model::Order;
+    //~^ disallowed_layer_dependencies
+    use crate::infra::{Db, connect};
+    //~^ disallowed_layer_dependencies
+    //~| disallowed_layer_dependencies
+
+    fn handle(repo: &dyn Repository) {
+        helper();
+        let db = Db;
+        //~^ disallowed_layer_dependencies
+        let _: Option<crate::domain::model::Order> = None;
+        //~^ disallowed_layer_dependencies
+    }
+
+    struct Handler;
+
+    impl crate::infra::Store for Handler {}
+    //~^ disallowed_layer_dependencies
+}
+
+mod shared {
+    pub struct Id;
+}
+
+fn main() {
+    // Code outside of the layers may use any of them
+    let _ = infra::Db;
+    domain::helper();
+}
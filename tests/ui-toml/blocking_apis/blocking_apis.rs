@@ -0,0 +1,31 @@
+#![warn(clippy::blocking_in_async)]
+
+mod client {
+    pub fn get(_url: &str) -> String {
+        String::new()
+    }
+
+    pub struct Client;
+
+    impl Client {
+        pub fn send(&self) {}
+    }
+}
+
+fn fetch_user() -> String {
+    client::get("/user")
+}
+
+async fn handler(client: &client::Client) {
+    let _ = fetch_user();
+    //~^ blocking_in_async
+
+    client.send();
+    //~^ blocking_in_async
+
+    // Built-in blocking functions are still checked for
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    //~^ blocking_in_async
+}
+
+fn main() {}
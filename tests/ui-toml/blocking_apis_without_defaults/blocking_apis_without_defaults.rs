@@ -0,0 +1,13 @@
+#![warn(clippy::blocking_in_async)]
+
+use std::sync::Mutex;
+
+async fn handler(counter: &Mutex<u32>) {
+    // The built-in blocking functions are replaced by the configured ones
+    *counter.lock().unwrap() += 1;
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    //~^ blocking_in_async
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+//@error-in-other-file: `std::process::current_exe` does not refer to a reachable function
+#![warn(clippy::disallowed_methods, clippy::disallowed_names, clippy::too_many_arguments)]
+
+fn main() {
+    // `foo` is part of the default configuration
+    let foo = 0;
+    //~^ disallowed_names
+    // `toto` is inherited from `clippy-base.toml`
+    let toto = 0;
+    //~^ disallowed_names
+    let tata = 0;
+    //~^ disallowed_names
+    let titi = 0;
+
+    let _ = Vec::<u32>::new().leak();
+    //~^ disallowed_methods
+}
+
+fn two_args(_: u32, _: u32) {}
+
+fn three_args(_: u32, _: u32, _: u32) {}
+//~^ too_many_arguments
@@ -0,0 +1,4 @@
+//@error-in-other-file: config file extends itself
+//@error-in-other-file: expected a path
+
+fn main() {}
@@ -0,0 +1,3 @@
+pub fn three_args(_: u32, _: u32, _: u32) {}
+
+pub fn four_args(_: u32, _: u32, _: u32, _: u32) {}
@@ -0,0 +1,23 @@
+//@error-in-other-file: this function has too many arguments (4/3)
+#![warn(clippy::too_many_arguments)]
+
+#[path = "auxiliary/generated.rs"]
+mod generated;
+
+mod ffi {
+    pub fn four_args(_: u32, _: u32, _: u32, _: u32) {}
+
+    pub fn five_args(_: u32, _: u32, _: u32, _: u32, _: u32) {}
+    //~^ too_many_arguments
+
+    mod sys {
+        pub fn four_args(_: u32, _: u32, _: u32, _: u32) {}
+    }
+}
+
+fn two_args(_: u32, _: u32) {}
+
+fn three_args(_: u32, _: u32, _: u32) {}
+//~^ too_many_arguments
+
+fn main() {}
@@ -0,0 +1,6 @@
+//@no-rustfix
+//@error-in-other-file: expected at least one of `modules` or `paths`
+//@error-in-other-file: unknown field name
+//@error-in-other-file: this configuration can't be set in an `[[override]]` table
+
+fn main() {}
@@ -0,0 +1,36 @@
+#![warn(clippy::custom_rules)]
+#![allow(clippy::len_zero)]
+
+use std::sync::{Mutex, MutexGuard};
+
+fn lock_or_poison<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct State {
+    counter: Mutex<u32>,
+}
+
+fn main() {
+    let v: Vec<u8> = Vec::with_capacity(0);
+    //~^ custom_rules
+    let w: Vec<u8> = Vec::with_capacity(1);
+    let _ = v.len() == 0;
+    //~^ custom_rules
+    let _ = "".len() == 0;
+    let _ = w.len() == 1;
+
+    let (a, b) = (1, 2);
+    let _ = std::cmp::max(a, a);
+    //~^ custom_rules
+    let _ = std::cmp::max(a, b);
+    let _ = std::cmp::max(a + 1, a + 1);
+    //~^ custom_rules
+
+    let state = State { counter: Mutex::new(0) };
+    *state.counter.lock().unwrap() += 1;
+    //~^ custom_rules
+    *lock_or_poison(&state.counter) += 1;
+    let other = std::cell::RefCell::new(0);
+    *other.borrow_mut() += 1;
+}
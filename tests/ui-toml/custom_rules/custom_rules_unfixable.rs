@@ -0,0 +1,11 @@
+//@no-rustfix
+#![warn(clippy::custom_rules)]
+
+fn main() {
+    let _ = 1u64 << 32;
+    //~^ custom_rules
+    let _ = 1u64 << 31;
+    let x = 1u32;
+    let _ = x << 32;
+    //~^ custom_rules
+}
@@ -0,0 +1,13 @@
+//@error-in-other-file: cannot find `Vec::with_capacity`
+//@error-in-other-file: expected a single Rust expression
+//@error-in-other-file: unsupported expression in pattern
+//@error-in-other-file: `$y` isn't bound by the pattern
+//@error-in-other-file: `$z` isn't bound by the pattern
+//@error-in-other-file: unknown field `severity`
+//@error-in-other-file: expected a string
+//@error-in-other-file: missing field `message`
+//@error-in-other-file: expected `allow`, `warn` or `deny`
+//@error-in-other-file: cannot find the type `Vec`
+//@error-in-other-file: unknown key `rules`
+
+fn main() {}
@@ -0,0 +1,21 @@
+//@no-rustfix
+#![warn(clippy::custom_rules)]
+
+fn main() {
+    let _ = 1u64 << 32;
+    //~^ custom_rules
+    let _ = std::cmp::max(1, 1);
+    let _ = std::cmp::min(1, 1);
+    //~^ custom_rules
+}
+
+#[allow(clippy::custom_rules)]
+fn allowed() {
+    let _ = 1u64 << 32;
+}
+
+#[deny(clippy::custom_rules)]
+fn denied() {
+    let _ = std::cmp::min(1, 1);
+    //~^ custom_rules
+}
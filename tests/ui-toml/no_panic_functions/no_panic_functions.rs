@@ -0,0 +1,18 @@
+#![warn(clippy::panic_in_no_panic_fn)]
+
+fn scale(x: u32) -> u32 {
+    x.wrapping_mul(3)
+}
+
+fn handler(x: u32, v: &[u32]) -> u32 {
+    let _ = x.checked_add(1);
+    let _ = scale(x);
+    let _ = x.saturating_add(1);
+    //~^ panic_in_no_panic_fn
+    v[0]
+    //~^ panic_in_no_panic_fn
+}
+
+fn main() {
+    let _ = handler(1, &[1]);
+}
@@ -0,0 +1,18 @@
+//@no-rustfix
+#![crate_type = "lib"]
+#![warn(clippy::redundant_clone)]
+
+pub fn exported(s: String) {
+    println!("{}", s.len());
+}
+
+pub struct Config {
+    name: String,
+}
+
+impl Config {
+    pub fn show(&self) {
+        exported(self.name.clone());
+        //~^ redundant_clone
+    }
+}
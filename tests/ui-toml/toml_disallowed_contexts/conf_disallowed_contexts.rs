@@ -0,0 +1,87 @@
+//@no-rustfix
+//@compile-flags: --test
+#![warn(clippy::disallowed_macros, clippy::disallowed_methods, clippy::disallowed_types)]
+#![allow(unnecessary_transmutes)]
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+async fn async_fn() {
+    std::thread::sleep(Duration::from_secs(1));
+    //~^ disallowed_methods
+    let _ = std::fs::read_to_string("file");
+    //~^ disallowed_methods
+
+    // Closures aren't necessarily run on the executor, e.g. with `spawn_blocking`
+    let _ = || std::thread::sleep(Duration::from_secs(1));
+
+    async {
+        std::thread::sleep(Duration::from_secs(1));
+        //~^ disallowed_methods
+    }
+    .await;
+}
+
+fn sync_fn(mutex: &Mutex<u32>) -> MutexGuard<'_, u32> {
+    std::thread::sleep(Duration::from_secs(1));
+    let _ = std::fs::read_to_string("file");
+    let _ = dbg!(1);
+    mutex.lock().unwrap()
+}
+
+async fn async_guard(mutex: &Mutex<u32>) {
+    let _guard: MutexGuard<'_, u32> = mutex.lock().unwrap();
+    //~^ disallowed_types
+}
+
+mod hot_path {
+    pub fn read() {
+        let _ = std::fs::read_to_string("file");
+        //~^ disallowed_methods
+        let _ = dbg!(1);
+        //~^ disallowed_macros
+    }
+
+    mod nested {
+        fn read() {
+            let _ = std::fs::read_to_string("file");
+            //~^ disallowed_methods
+        }
+    }
+}
+
+unsafe fn unsafe_fn(x: u32) -> f32 {
+    unsafe { std::mem::transmute(x) }
+    //~^ disallowed_methods
+}
+
+fn safe_fn(x: u32) -> f32 {
+    let f = unsafe { std::mem::transmute::<u32, f32>(x) };
+    //~^ disallowed_methods
+    f32::from_bits(x)
+}
+
+const EMPTY: Vec<u8> = Vec::new();
+//~^ disallowed_methods
+
+const fn empty() -> Vec<u8> {
+    Vec::new()
+    //~^ disallowed_methods
+}
+
+fn not_const() -> Vec<u8> {
+    std::mem::drop(String::new());
+    Vec::new()
+}
+
+fn main() {
+    if false {
+        std::process::exit(0);
+    }
+}
+
+#[test]
+fn test() {
+    std::process::exit(0);
+    //~^ disallowed_methods
+}
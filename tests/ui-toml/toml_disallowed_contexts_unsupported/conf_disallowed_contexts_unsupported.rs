@@ -0,0 +1,2 @@
+//@error-in-other-file: `contexts` isn't supported by this option
+fn main() {}
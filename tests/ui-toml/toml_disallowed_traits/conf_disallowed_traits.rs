@@ -0,0 +1,111 @@
+//@no-rustfix
+#![warn(clippy::disallowed_impls, clippy::disallowed_traits)]
+
+use serde::Deserialize;
+use std::fmt;
+use std::ops::Deref;
+
+struct Meters(f64);
+
+impl Deref for Meters {
+    //~^ disallowed_traits
+    type Target = f64;
+
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+#[derive(Deserialize)]
+//~^ disallowed_traits
+struct Config {
+    name: String,
+}
+
+// Also expands to an implementation of `StructuralPartialEq`
+#[derive(PartialEq)]
+//~^ disallowed_traits
+struct Point(u8);
+
+#[allow(clippy::disallowed_traits)]
+#[derive(Deserialize)]
+struct AllowedConfig {
+    name: String,
+}
+
+impl fmt::Display for Meters {
+    //~^ disallowed_traits
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}m", self.0)
+    }
+}
+
+fn bound<T: Deref<Target = f64>>(x: T) -> f64 {
+    //~^ disallowed_traits
+    *x
+}
+
+fn where_clause<T>(x: T) -> f64
+where
+    T: Deref<Target = f64>,
+    //~^ disallowed_traits
+{
+    *x
+}
+
+fn dyn_trait(x: &dyn fmt::Display) {}
+//~^ disallowed_traits
+
+fn impl_trait() -> impl fmt::Display {
+    //~^ disallowed_traits
+    0
+}
+
+#[derive(Default)]
+//~^ disallowed_impls
+struct UserId(u64);
+
+#[derive(Clone, Default)]
+//~^ disallowed_impls
+struct Token;
+
+impl Clone for UserId {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+#[derive(Default)]
+struct OtherId(u64);
+
+trait Marker {}
+
+impl Marker for u8 {}
+//~^ disallowed_impls
+
+impl Marker for u16 {}
+
+struct Byte(u8);
+
+impl std::hash::Hash for Byte {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+macro_rules! impl_deref {
+    ($ty:ty) => {
+        impl std::ops::Deref for $ty {
+            type Target = f64;
+
+            fn deref(&self) -> &f64 {
+                &0.0
+            }
+        }
+    };
+}
+
+struct FromMacro;
+impl_deref!(FromMacro);
+
+fn main() {}
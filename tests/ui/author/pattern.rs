@@ -0,0 +1,20 @@
+//@ check-pass
+
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+fn main() {
+    let v: Vec<u32> = Vec::new();
+    let p = Point { x: 1, y: 2 };
+
+    #[clippy::author = "pattern"]
+    let _ = ::std::cmp::min(v.len() as u32 + 1, p.x);
+
+    #[clippy::author = "pattern"]
+    let _ = v.iter().map(|x| x * 2).fold(0, |a, b| a.max(b)) == v[0] && !(p.y > 'a' as u32);
+
+    #[clippy::author = "pattern"]
+    let _ = (&p.x, [1.5, 2.0], "s", true);
+}
@@ -0,0 +1,101 @@
+#![warn(clippy::blocking_in_async)]
+#![allow(clippy::manual_async_fn, clippy::redundant_closure)]
+
+use std::sync::Mutex;
+
+fn load_config() -> String {
+    std::fs::read_to_string("config.toml").unwrap()
+}
+
+fn config_len() -> usize {
+    load_config().len()
+}
+
+fn recursive(n: u32) -> u32 {
+    if n == 0 { 0 } else { recursive(n - 1) }
+}
+
+fn ping(n: u32) {
+    if n > 0 {
+        pong(n - 1);
+    }
+    std::thread::sleep(std::time::Duration::from_millis(1));
+}
+
+fn pong(n: u32) {
+    ping(n);
+}
+
+fn pure(n: u32) -> u32 {
+    n + 1
+}
+
+struct State {
+    counter: Mutex<u32>,
+}
+
+impl State {
+    fn increment(&self) {
+        *self.counter.lock().unwrap() += 1;
+    }
+}
+
+trait Repository {
+    fn load(&self) -> Vec<u8>;
+}
+
+struct FileRepository;
+
+impl Repository for FileRepository {
+    fn load(&self) -> Vec<u8> {
+        std::fs::read("data.bin").unwrap()
+    }
+}
+
+async fn helper() -> usize {
+    config_len()
+    //~^ blocking_in_async
+}
+
+async fn handler(state: &State, repo: &FileRepository) {
+    std::thread::sleep(std::time::Duration::from_secs(1));
+    //~^ blocking_in_async
+
+    let _ = config_len();
+    //~^ blocking_in_async
+
+    state.increment();
+    //~^ blocking_in_async
+
+    let _ = repo.load();
+    //~^ blocking_in_async
+
+    // Awaiting an async function is fine, `helper` is checked on its own
+    let _ = helper().await;
+
+    let _ = recursive(3);
+    let _ = pure(1);
+
+    ping(1);
+    //~^ blocking_in_async
+
+    // Blocks through `ping`, which was being visited when `pong` was first reached
+    pong(1);
+    //~^ blocking_in_async
+
+    // Closures are usually run on another thread
+    let _ = std::thread::spawn(|| load_config());
+}
+
+fn block_inside_async_block() -> impl Future<Output = String> {
+    async {
+        load_config()
+        //~^ blocking_in_async
+    }
+}
+
+fn not_async() {
+    let _ = load_config();
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+//@ check-pass
+#![feature(stmt_expr_attributes)]
+
+struct Point {
+    x: u32,
+}
+
+#[clippy::dump(hir, types, mir)]
+fn add(n: u32, point: &Point) -> u32 {
+    let x = point.x;
+    n + x
+}
+
+fn main() {
+    let point = Point { x: 1 };
+    #[clippy::dump(hir)]
+    let n = add(2, &point).max(2);
+    let _ = #[clippy::dump(types)]
+    (n as u64, "s");
+}
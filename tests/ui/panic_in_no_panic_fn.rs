@@ -0,0 +1,113 @@
+#![warn(clippy::panic_in_no_panic_fn)]
+
+const LEN: usize = 4;
+
+#[clippy::no_panic]
+fn direct(data: &[u8], arr: [u8; LEN], x: u32, i: i32, opt: Option<u8>) -> u32 {
+    let _ = data[0];
+    //~^ panic_in_no_panic_fn
+    let _ = arr[3];
+    let _ = arr[LEN - 1];
+    let _ = x + 1;
+    //~^ panic_in_no_panic_fn
+    let _ = x / 2;
+    let _ = i / -1;
+    //~^ panic_in_no_panic_fn
+    let _ = x % x;
+    //~^ panic_in_no_panic_fn
+    let _ = x << 2;
+    let _ = -i;
+    //~^ panic_in_no_panic_fn
+    let _ = 1u32 + 2;
+    let _ = 1.0f32 + 2.0;
+    let _ = opt.unwrap();
+    //~^ panic_in_no_panic_fn
+    if x == 0 {
+        panic!("zero");
+        //~^ panic_in_no_panic_fn
+    }
+    assert!(x > 1);
+    //~^ panic_in_no_panic_fn
+    let _ = data.len();
+    //~^ panic_in_no_panic_fn
+    x
+}
+
+fn leaf(x: u32) -> u32 {
+    x.wrapping_mul(2)
+}
+
+fn middle(x: u32) -> u32 {
+    leaf(x)
+}
+
+fn safe(x: u32) -> u32 {
+    if x > 10 { x } else { 10 }
+}
+
+fn recursive(x: u32) -> u32 {
+    if x < 10 { recursive(x) } else { x }
+}
+
+fn ping(x: u32) -> u32 {
+    if x > 10 { pong(x) } else { unreachable!() }
+}
+
+fn pong(x: u32) -> u32 {
+    ping(x)
+}
+
+fn unreachable_in_callee(x: u32) -> u32 {
+    if x == 0 {
+        unreachable!();
+    }
+    safe(x)
+}
+
+#[clippy::no_panic]
+fn checked_elsewhere(x: u32) -> u32 {
+    x
+}
+
+#[clippy::no_panic]
+fn through_calls(x: u32, f: fn(u32) -> u32, g: &dyn Fn(u32) -> u32) -> u32 {
+    let _ = safe(x);
+    let _ = recursive(x);
+    let _ = checked_elsewhere(x);
+    let _ = middle(x);
+    //~^ panic_in_no_panic_fn
+    let _ = unreachable_in_callee(x);
+    //~^ panic_in_no_panic_fn
+    let _ = ping(x);
+    //~^ panic_in_no_panic_fn
+    // Panics through `ping`, which was being visited when `pong` was first reached
+    let _ = pong(x);
+    //~^ panic_in_no_panic_fn
+    let _ = f(x);
+    //~^ panic_in_no_panic_fn
+    let _ = g(x);
+    //~^ panic_in_no_panic_fn
+    let _ = Some(x);
+    let closure = |y: u32| y + 1;
+    //~^ panic_in_no_panic_fn
+    closure(x)
+}
+
+struct S;
+
+impl S {
+    #[clippy::no_panic]
+    fn method(&self, v: &[u32]) -> u32 {
+        v.iter().fold(0, |acc, x| acc ^ x)
+        //~^ panic_in_no_panic_fn
+        //~| panic_in_no_panic_fn
+    }
+}
+
+fn not_checked(data: &[u8]) -> u8 {
+    data[0]
+}
+
+fn main() {
+    let _ = not_checked(&[1]);
+}
@@ -0,0 +1,133 @@
+//@no-rustfix
+#![warn(clippy::redundant_clone)]
+
+use std::cell::{Cell, RefCell};
+
+struct Config {
+    name: String,
+}
+
+fn print_len(s: String) {
+    println!("{}", s.len());
+}
+
+fn show(s: String) {
+    print_len(s);
+}
+
+fn show_any(s: impl std::fmt::Display) {
+    println!("{s}");
+}
+
+fn keep(s: String) -> String {
+    s
+}
+
+fn push_to(mut s: String) {
+    s.push('!');
+}
+
+fn collect(s: String, v: &mut Vec<String>) {
+    v.push(s);
+}
+
+fn countdown(s: String, n: u32) {
+    if n > 0 {
+        countdown(s, n - 1);
+    } else {
+        println!("{s}");
+    }
+}
+
+fn wrap(s: String) -> Config {
+    Config { name: s }
+}
+
+#[derive(Clone)]
+struct Counter {
+    hits: Cell<u32>,
+}
+
+fn record(c: Counter) {
+    c.hits.set(c.hits.get() + 1);
+}
+
+#[derive(Clone)]
+struct Cache {
+    entries: RefCell<Vec<String>>,
+}
+
+fn remember(c: Cache, s: &str) {
+    c.entries.borrow_mut().push(s.to_owned());
+}
+
+fn record_all(c: Vec<Counter>) {
+    for c in &c {
+        c.hits.set(0);
+    }
+}
+
+trait Greet {
+    fn greet_all(&self, s: String) {
+        println!("{s}");
+    }
+}
+
+impl Greet for Config {}
+
+impl Config {
+    fn greet(&self, other: String) {
+        println!("{} {other}", self.name);
+    }
+
+    fn check(&self) {
+        print_len(self.name.clone());
+        //~^ redundant_clone
+    }
+}
+
+fn main() {
+    let s = String::from("foo");
+    print_len(s.clone());
+    //~^ redundant_clone
+    show(s.clone());
+    //~^ redundant_clone
+    let c = Config { name: s.clone() };
+    c.greet(s.clone());
+    //~^ redundant_clone
+
+    // The parameter is returned, mutated or moved out
+    let _ = keep(s.clone());
+    push_to(s.clone());
+    let mut v = Vec::new();
+    collect(s.clone(), &mut v);
+    countdown(s.clone(), 3);
+    let _ = wrap(s.clone());
+    // Trait methods may be overridden
+    c.greet_all(s.clone());
+    // A reference may not satisfy the bounds
+    show_any(s.clone());
+
+    // The parameter is mutated through interior mutability
+    let counter = Counter { hits: Cell::new(0) };
+    record(counter.clone());
+    let cache = Cache {
+        entries: RefCell::new(Vec::new()),
+    };
+    remember(cache.clone(), "a");
+    let counters = vec![counter.clone()];
+    record_all(counters.clone());
+    println!(
+        "{} {} {}",
+        counter.hits.get(),
+        cache.entries.borrow().len(),
+        counters.len()
+    );
+
+    // `s` is mutated before the call
+    let mut s = s;
+    let t = s.clone();
+    s.push('a');
+    print_len(t);
+    println!("{s}");
+}
@@ -0,0 +1,24 @@
+//@no-rustfix
+#![crate_type = "lib"]
+#![warn(clippy::redundant_clone)]
+
+pub fn exported(s: String) {
+    println!("{}", s.len());
+}
+
+fn private(s: String) {
+    println!("{}", s.len());
+}
+
+pub struct Config {
+    name: String,
+}
+
+impl Config {
+    pub fn show(&self) {
+        // Changing the parameter of `exported` would break other crates
+        exported(self.name.clone());
+        private(self.name.clone());
+        //~^ redundant_clone
+    }
+}
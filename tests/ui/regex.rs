@@ -2,7 +2,9 @@
 #![warn(clippy::invalid_regex, clippy::regex_creation_in_loops, clippy::trivial_regex)]
 #![expect(clippy::needless_borrows_for_generic_args)]
 
+extern crate fancy_regex;
 extern crate regex;
+extern crate regex_lite;
 
 use regex::bytes::{Regex as BRegex, RegexBuilder as BRegexBuilder, RegexSet as BRegexSet};
 use regex::{Regex, RegexBuilder, RegexSet};
@@ -119,6 +121,39 @@ fn trivial_regex() {
     let _ = BRegex::new(r"\b{start}word\b{end}");
 }
 
+fn builder_flags() {
+    let ignored_whitespace = RegexBuilder::new("a b+ # comment (").ignore_whitespace(true);
+    let case_insensitive = RegexBuilder::new("foobar").case_insensitive(true);
+    let case_sensitive = RegexBuilder::new("foobar").case_insensitive(false);
+    //~^ ERROR: trivial regex
+    let both = RegexBuilder::new("a b+ # (").size_limit(10).ignore_whitespace(true);
+    let size_limit = RegexBuilder::new("(").size_limit(10);
+    //~^ ERROR: regex syntax error: unclosed group
+    let no_unicode = RegexBuilder::new(".").unicode(false);
+    //~^ ERROR: regex syntax error: pattern can match invalid UTF-8
+    let no_unicode_bytes = BRegexBuilder::new(".").unicode(false);
+
+    let flag = true;
+    let unknown_flag = RegexBuilder::new("(").ignore_whitespace(flag);
+}
+
+fn other_crates() {
+    let lite_error = regex_lite::Regex::new("[z-a]");
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    let lite_trivial = regex_lite::Regex::new("foobar");
+    //~^ ERROR: trivial regex
+    let lite_builder = regex_lite::RegexBuilder::new("a b+ # (").ignore_whitespace(true);
+
+    let fancy_error = fancy_regex::Regex::new("[z-a]");
+    //~^ ERROR: regex syntax error: invalid character class range, the start must be <= th
+    let fancy_trivial = fancy_regex::Regex::new("foobar");
+    //~^ ERROR: trivial regex
+    let fancy_backreference = fancy_regex::Regex::new(r"(\w+) \1");
+    let fancy_lookahead = fancy_regex::Regex::new("a(?=b)");
+    let fancy_atomic = fancy_regex::Regex::new("(?>a+)b");
+    let fancy_builder = fancy_regex::RegexBuilder::new("FOO").case_insensitive(true);
+}
+
 fn regex_creation_in_loops() {
     loop {
         static STATIC_REGEX: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| Regex::new("a.b").unwrap());
@@ -0,0 +1,90 @@
+#![warn(clippy::regex_creation_in_functions)]
+
+extern crate regex;
+
+use regex::Regex;
+
+const PATTERN: &str = "a+b";
+
+fn receiver(s: &str) -> bool {
+    Regex::new("a+b").unwrap().is_match(s)
+    //~^ regex_creation_in_functions
+}
+
+fn binding(s: &str) -> bool {
+    let re = Regex::new(PATTERN).expect("valid regex");
+    //~^ regex_creation_in_functions
+    re.is_match(s) && re.find(s).is_some()
+}
+
+fn two_regexes(s: &str) -> bool {
+    Regex::new("a+").unwrap().is_match(s) && Regex::new("b+").unwrap().is_match(s)
+    //~^ regex_creation_in_functions
+    //~| regex_creation_in_functions
+}
+
+fn shadowed_binding(s: &str) -> bool {
+    let re = Regex::new("a+").unwrap();
+    //~^ regex_creation_in_functions
+    let found = re.is_match(s);
+    let re = Regex::new("b+").unwrap();
+    //~^ regex_creation_in_functions
+    found && re.is_match(s)
+}
+
+fn moved(s: &str) -> Regex {
+    let re = Regex::new("a+b").unwrap();
+    let _ = re.is_match(s);
+    re
+}
+
+fn argument(s: &str) -> Vec<String> {
+    split(Regex::new(",").unwrap(), s)
+}
+
+fn split(re: Regex, s: &str) -> Vec<String> {
+    re.split(s).map(String::from).collect()
+}
+
+struct S;
+
+impl S {
+    fn method(&self, s: &str) -> bool {
+        let re = regex::bytes::Regex::new("a+b").unwrap();
+        //~^ regex_creation_in_functions
+        re.is_match(s.as_bytes())
+    }
+}
+
+fn dynamic_pattern(s: &str, pattern: &str) -> bool {
+    Regex::new(pattern).unwrap().is_match(s)
+}
+
+fn handled_error(s: &str) -> Result<bool, regex::Error> {
+    Ok(Regex::new("a+b")?.is_match(s))
+}
+
+fn already_static(s: &str) -> bool {
+    static RE: std::sync::LazyLock<Regex> = std::sync::LazyLock::new(|| Regex::new("a+b").unwrap());
+    RE.is_match(s)
+}
+
+fn in_closure(items: &[&str]) -> usize {
+    items.iter().filter(|s| Regex::new("a+b").unwrap().is_match(s)).count()
+}
+
+fn in_loop(items: &[&str]) {
+    for s in items {
+        #[allow(clippy::regex_creation_in_loops)]
+        let _ = Regex::new("a+b").unwrap().is_match(s);
+    }
+}
+
+#[clippy::msrv = "1.79"]
+fn msrv_1_79(s: &str) -> bool {
+    Regex::new("a+b").unwrap().is_match(s)
+}
+
+fn main() {
+    let _ = Regex::new("a+b").unwrap().is_match("ab");
+}
@@ -0,0 +1,33 @@
+//@no-rustfix
+#![warn(clippy::regex_creation_in_functions)]
+
+extern crate regex;
+
+use regex::Regex;
+
+fn conflicting_item(s: &str) -> bool {
+    const REGEX: &str = "a+";
+    Regex::new("b+").unwrap().is_match(s) && s.contains(REGEX)
+    //~^ regex_creation_in_functions
+}
+
+// A static inside the function can't use `Self` or the function's generic parameters
+struct Matcher;
+
+impl Matcher {
+    const PATTERN: &str = "a+";
+
+    fn is_match(s: &str) -> bool {
+        Regex::new(Self::PATTERN).unwrap().is_match(s)
+    }
+}
+
+trait Pattern {
+    const PATTERN: &str;
+}
+
+fn generic_pattern<P: Pattern>(s: &str) -> bool {
+    Regex::new(P::PATTERN).unwrap().is_match(s)
+}
+
+fn main() {}
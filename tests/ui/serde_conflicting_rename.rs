@@ -0,0 +1,57 @@
+#![warn(clippy::serde_conflicting_rename)]
+
+extern crate serde;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    user_id: u64,
+    #[serde(rename = "userId")]
+    //~^ serde_conflicting_rename
+    legacy_id: u64,
+    #[serde(rename = "legacyUserId")]
+    other_id: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Explicit {
+    #[serde(rename = "id")]
+    first: u64,
+    #[serde(rename = "id")]
+    //~^ serde_conflicting_rename
+    second: u64,
+    #[serde(skip, rename = "first")]
+    skipped: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Plain {
+    name: String,
+    #[serde(rename = "name")]
+    //~^ serde_conflicting_rename
+    display_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "kebab-case")]
+enum Event {
+    UserCreated {
+        user_name: String,
+        #[serde(rename = "user-name")]
+        //~^ serde_conflicting_rename
+        name: String,
+    },
+    #[serde(rename = "USER_CREATED")]
+    //~^ serde_conflicting_rename
+    Created,
+    #[serde(rename_all = "camelCase")]
+    UserDeleted {
+        user_name: String,
+        #[serde(rename = "user-name")]
+        name: String,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,64 @@
+#![warn(clippy::serde_default_without_default)]
+
+extern crate serde;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct Page<T> {
+    #[serde(default)]
+    //~^ serde_default_without_default
+    items: T,
+    #[serde(default)]
+    total: u64,
+}
+
+#[derive(Deserialize)]
+struct Bounded<T: Default> {
+    #[serde(default)]
+    items: T,
+}
+
+#[derive(Serialize)]
+struct NoDefault;
+
+#[derive(Serialize)]
+struct SerializeOnly {
+    #[serde(default)]
+    //~^ serde_default_without_default
+    value: NoDefault,
+    #[serde(default = "make_no_default")]
+    other: NoDefault,
+}
+
+fn make_no_default() -> NoDefault {
+    NoDefault
+}
+
+#[derive(Serialize)]
+#[serde(default)]
+//~^ serde_default_without_default
+struct Container {
+    value: u32,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct DefaultContainer {
+    value: u32,
+}
+
+#[derive(Deserialize)]
+enum Shape<T> {
+    Circle {
+        #[serde(default)]
+        //~^ serde_default_without_default
+        radius: T,
+    },
+    Square {
+        #[serde(default)]
+        side: u32,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+#![warn(clippy::serde_deny_unknown_fields_with_flatten)]
+
+extern crate serde;
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    id: u64,
+    #[serde(flatten)]
+    //~^ serde_deny_unknown_fields_with_flatten
+    extra: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Pagination {
+    page: u32,
+}
+
+#[derive(Deserialize)]
+struct Query {
+    filter: String,
+    #[serde(flatten)]
+    //~^ serde_deny_unknown_fields_with_flatten
+    pagination: Pagination,
+}
+
+#[derive(Deserialize)]
+struct Response {
+    id: u64,
+    #[serde(flatten)]
+    extra: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Strict {
+    id: u64,
+    page: Pagination,
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+#![warn(clippy::serde_mismatched_skip_serializing_if)]
+#![allow(clippy::box_collection)]
+
+extern crate serde;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+struct Tags {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    //~^ serde_mismatched_skip_serializing_if
+    names: Vec<String>,
+    #[serde(skip_serializing_if = "std::collections::HashMap::is_empty")]
+    //~^ serde_mismatched_skip_serializing_if
+    labels: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    values: Vec<u32>,
+    #[serde(skip_serializing_if = "Vec::<u32>::is_empty")]
+    boxed: Box<Vec<u32>>,
+    #[serde(skip_serializing_if = "is_zero")]
+    count: u32,
+}
+
+fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
+#[derive(Serialize)]
+struct Checked<T: Serialize> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<T>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    name: String,
+}
+
+fn main() {}
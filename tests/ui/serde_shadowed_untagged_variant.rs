@@ -0,0 +1,75 @@
+#![warn(clippy::serde_shadowed_untagged_variant)]
+
+extern crate serde;
+
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Event {
+    Ping { id: u64 },
+    Message { id: u64, text: String },
+    //~^ serde_shadowed_untagged_variant
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Fixed {
+    Message { id: u64, text: String },
+    Ping { id: u64 },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Optional {
+    Any { name: Option<String>, tag: Option<String> },
+    Named { name: String },
+    //~^ serde_shadowed_untagged_variant
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Count(i64),
+    //~^ serde_shadowed_untagged_variant
+    Null,
+    Nothing,
+    //~^ serde_shadowed_untagged_variant
+}
+
+#[derive(Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+enum Strict {
+    Ping { id: u64 },
+    Message { id: u64, text: String },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Different {
+    Ping { id: u64 },
+    Pong { id: String, text: String },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Defaulted {
+    // A single `u64` isn't a valid `Vec<u64>`
+    Many {
+        #[serde(default)]
+        ids: Vec<u64>,
+    },
+    One {
+        ids: u64,
+    },
+}
+
+#[derive(Deserialize)]
+enum Tagged {
+    Ping { id: u64 },
+    Message { id: u64, text: String },
+}
+
+fn main() {}
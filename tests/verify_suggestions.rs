@@ -0,0 +1,79 @@
+#![warn(rust_2018_idioms, unused_lifetimes)]
+
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use test_utils::IS_RUSTC_TEST_SUITE;
+
+mod test_utils;
+
+#[test]
+fn verify_suggestions() {
+    if IS_RUSTC_TEST_SUITE {
+        return;
+    }
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("verify_suggestions");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    // `String::new()` refers to the local `String` in `shadowed`.
+    fs::write(
+        dir.join("verify_test.rs"),
+        "#![warn(clippy::manual_string_new)]\n\
+        mod shadowed {\n    pub struct String;\n    pub fn f() -> std::string::String {\n        \
+        let _ = String;\n        \"\".to_string()\n    }\n}\n\
+        fn main() {\n    let s = \"\".to_string();\n    println!(\"{s}{}\", shadowed::f());\n}\n",
+    )
+    .unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_clippy-driver"))
+        .current_dir(&dir)
+        .env("CLIPPY_ARGS", "--verify-suggestions")
+        .args([
+            "--edition=2024",
+            "--emit=metadata",
+            "--error-format=json",
+            "verify_test.rs",
+        ])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    println!("stderr: {stderr}");
+    assert!(output.status.success());
+
+    let diagnostics: Vec<Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .filter(|diag: &Value| diag["code"]["code"] == "clippy::manual_string_new")
+        .collect();
+    let [shadowed, unshadowed] = &diagnostics[..] else {
+        panic!("expected two `manual_string_new` diagnostics");
+    };
+
+    let children = shadowed["children"].as_array().unwrap();
+    let suggestion = children
+        .iter()
+        .find(|child| child["spans"][0]["suggested_replacement"].is_string())
+        .unwrap();
+    assert_eq!(suggestion["spans"][0]["suggestion_applicability"], "MaybeIncorrect");
+    let note = children
+        .iter()
+        .filter_map(|child| child["message"].as_str())
+        .find(|message| message.starts_with("the suggestion doesn't compile"))
+        .unwrap();
+    assert!(note.contains("-        \"\".to_string()\n+        String::new()\n"));
+    assert!(note.contains("error[E0599]"));
+
+    let children = unshadowed["children"].as_array().unwrap();
+    let suggestion = children
+        .iter()
+        .find(|child| child["spans"][0]["suggested_replacement"].is_string())
+        .unwrap();
+    assert_eq!(suggestion["spans"][0]["suggestion_applicability"], "MachineApplicable");
+    assert!(
+        !children
+            .iter()
+            .any(|child| child["message"].as_str().unwrap().starts_with("the suggestion"))
+    );
+}